@@ -0,0 +1,69 @@
+//! ROKIO Audit Log - Record of sensitive vault access and cookie changes
+//!
+//! Anything that hands a decrypted cookie to the user (e.g. `reveal_cookie`)
+//! writes a row here first, successful or not, so access can be reviewed later.
+//! Cookies replaced behind the user's back (Roblox session rotation) are
+//! recorded here too, as part of the account's history.
+
+use crate::db;
+use rusqlite::params;
+use serde::Serialize;
+use std::path::Path;
+
+/// One audited action
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub id: i64,
+    /// Unix time in seconds
+    pub at: i64,
+    pub action: String,
+    pub account_id: Option<String>,
+    pub success: bool,
+}
+
+/// Record an action
+pub fn record(
+    app_data_dir: &Path,
+    action: &str,
+    account_id: Option<&str>,
+    success: bool,
+) -> Result<(), String> {
+    log::info!(
+        "Audit: {} {} ({})",
+        action,
+        account_id.unwrap_or("-"),
+        if success { "ok" } else { "denied" }
+    );
+    db::open(app_data_dir)?
+        .execute(
+            "INSERT INTO audit_log (at, action, account_id, success) VALUES (?1, ?2, ?3, ?4)",
+            params![db::now(), action, account_id, success],
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Most recent entries first
+pub fn list(app_data_dir: &Path, limit: u32) -> Result<Vec<AuditEntry>, String> {
+    let conn = db::open(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, at, action, account_id, success FROM audit_log
+             ORDER BY id DESC LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let entries = stmt
+        .query_map(params![limit], |row| {
+            Ok(AuditEntry {
+                id: row.get(0)?,
+                at: row.get(1)?,
+                action: row.get(2)?,
+                account_id: row.get(3)?,
+                success: row.get(4)?,
+            })
+        })
+        .and_then(|rows| rows.collect())
+        .map_err(|e| e.to_string())?;
+    Ok(entries)
+}
@@ -0,0 +1,672 @@
+//! ROKIO CLI - Headless access to the vault, launcher and server list
+//! Built on `rokio_core` only (no webview), so ROKIO can be scripted over SSH or from cron.
+//!
+//! Every command prints JSON to stdout. Errors are printed to stderr as
+//! `{"error": "..."}` with exit code 1.
+//!
+//! The vault is opened per invocation with `ROKIO_SESSION` (printed by
+//! `vault unlock`, valid until `vault lock`) or `ROKIO_PASSWORD`.
+//! Running instances are the open rows of the launch history, shared with the app.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as BASE64, Engine};
+use rand::RngCore;
+use rokio_core::cookie_files::CookieFormat;
+use rokio_core::crypto::{
+    decrypt_string, derive_key, encrypt_string, vault_exists, verify_password, write_empty_vault,
+};
+use rokio_core::launcher::{launch_account, ActiveInstance};
+use rokio_core::organize::AccountQuery;
+use rokio_core::process_utils::is_process_running;
+use rokio_core::profiles::Profile;
+use rokio_core::roblox_link::RobloxLink;
+use rokio_core::settings::load_settings;
+use rokio_core::{
+    bulk_import, cookie_files, game_detection, history, organize, presets, security, session, trash, vault, vaults,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, Read};
+use std::path::{Path, PathBuf};
+
+/// Tauri bundle identifier, used to find the GUI's app data directory
+const APP_IDENTIFIER: &str = "com.nguyennam.rokio";
+/// Encrypted vault key for the current CLI session, kept in the active vault's directory
+const SESSION_FILE: &str = "cli_session";
+
+const USAGE: &str = "\
+Usage: rokio [--data-dir <dir>] <command>
+
+Commands:
+  vault status
+  vault unlock                      Reads the password from ROKIO_PASSWORD or stdin,
+                                    prints a session for ROKIO_SESSION
+  vault lock                        Ends the current session
+  vault change-password             Reads the new password from ROKIO_NEW_PASSWORD or stdin
+  vault list
+  vault create <name>               Reads its password from ROKIO_NEW_PASSWORD or stdin;
+                                    sets up the first vault when there is none yet
+  vault rename <vault> <name>
+  vault switch <vault>              Later commands use that vault and its own session
+  vault delete <vault>              Deletes an inactive vault for good; reads its
+                                    password from ROKIO_PASSWORD or stdin
+  accounts list [--tag <tag>] [--folder <folder> [--recursive]]
+  accounts add [<cookie>|-]         Reads the cookie from stdin when omitted or '-'
+  accounts remove <account> [--sign-out]
+                                    Moves the account to the recycle bin, or signs it
+                                    out on Roblox and deletes it for good
+  accounts export                   Prints the encrypted backup
+  accounts import <file> [--merge]
+  accounts bulk-import [<file>|-]   Cookie list, CSV or another manager's JSON export;
+                                    reads stdin when omitted or '-'
+  accounts export-cookies <account>... [--format netscape|json] [--out <dir>]
+                                    One cookie file per account (default: current dir)
+  accounts import-cookies [<file>|-]
+                                    Netscape cookies.txt or JSON cookie export
+  sessions list <account>           Where the account is logged in
+  sessions sign-out-others <account>
+                                    Ends every session but ROKIO's
+  security                          Email, 2-step verification, phone and PIN per account
+  trash list
+  trash restore <account-id>
+  trash purge <account-id>|--all    Deletes for good
+  launch <account> [<place|link>]   Opens the menu when no place is given
+  presets list
+  presets run <preset>              Preset ID or name
+  instances list                    Clients launched by ROKIO (app or CLI) still running
+  instances kill <pid>
+  history list [--limit <n>]
+  history playtime [<account-id>]
+  history servers [--limit <n>]     Recent servers joined by job ID
+  servers <place> [--cursor <cursor>]
+
+<account> is an account ID, username, alias or Roblox user ID.
+<vault> is a vault ID or name.";
+
+/// Account as printed by the CLI (no cookie)
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountRow {
+    id: String,
+    user_id: i64,
+    username: String,
+    display_name: String,
+    alias: String,
+    tags: Vec<String>,
+    folder: String,
+    is_favorite: bool,
+    last_played_at: u64,
+}
+
+impl From<&Profile> for AccountRow {
+    fn from(p: &Profile) -> Self {
+        Self {
+            id: p.id.clone(),
+            user_id: p.user_id,
+            username: p.username.clone(),
+            display_name: p.display_name.clone(),
+            alias: p.alias.clone(),
+            tags: p.tags.clone(),
+            folder: p.folder.clone(),
+            is_favorite: p.is_favorite,
+            last_played_at: p.last_played_at,
+        }
+    }
+}
+
+// ============================================================================
+// ENTRY POINT
+// ============================================================================
+
+fn main() {
+    std::process::exit(run(std::env::args().skip(1).collect()))
+}
+
+/// Run the CLI with the given arguments (without the program name).
+/// Returns the process exit code.
+fn run(mut args: Vec<String>) -> i32 {
+    let data_dir = take_option(&mut args, "--data-dir");
+
+    if wants_help(&args) {
+        println!("{}", USAGE);
+        return 0;
+    }
+
+    let data_dir = match data_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => match default_data_dir() {
+            Ok(dir) => dir,
+            Err(e) => return fail(&e),
+        },
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => return fail(&format!("Failed to start async runtime: {}", e)),
+    };
+
+    match runtime.block_on(dispatch(&data_dir, args)) {
+        Ok(output) => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output).unwrap_or_default()
+            );
+            0
+        }
+        Err(e) => fail(&e),
+    }
+}
+
+/// No command, `help` as the command, or a `-h`/`--help` flag anywhere.
+/// A plain "help" later on is an argument (alias, preset name, ...).
+fn wants_help(args: &[String]) -> bool {
+    args.first().is_none_or(|first| first == "help")
+        || args.iter().any(|a| a == "-h" || a == "--help")
+}
+
+fn fail(message: &str) -> i32 {
+    eprintln!("{}", json!({ "error": message }));
+    1
+}
+
+/// Same directory Tauri resolves as `app_data_dir()` for the GUI
+fn default_data_dir() -> Result<PathBuf, String> {
+    if let Ok(dir) = std::env::var("ROKIO_DATA_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| "Cannot determine app data directory".to_string())
+}
+
+async fn dispatch(data_dir: &Path, mut args: Vec<String>) -> Result<Value, String> {
+    let group = args.remove(0);
+    let command = if args.is_empty() {
+        String::new()
+    } else {
+        args.remove(0)
+    };
+
+    match (group.as_str(), command.as_str()) {
+        ("vault", "status") => Ok(json!({
+            "vault": vaults::active(data_dir),
+            "exists": vault_exists(data_dir),
+            "sessionActive": open_vault(data_dir).is_ok(),
+        })),
+        ("vault", "unlock") => vault_unlock(data_dir),
+        ("vault", "lock") => vault_lock(data_dir),
+        ("vault", "change-password") => vault_change_password(data_dir),
+        ("vault", "list") => Ok(json!(vaults::list(data_dir)?)),
+        ("vault", "create") => {
+            let name = required(&args, "<name>")?;
+            let password = match std::env::var("ROKIO_NEW_PASSWORD") {
+                Ok(password) => password,
+                Err(_) => read_secret_line("password for the new vault")?,
+            };
+            if password.is_empty() {
+                return Err("Password cannot be empty".to_string());
+            }
+            vault_create(data_dir, name, &derive_key(&password))
+        }
+        ("vault", "rename") => {
+            let vault = required(&args, "<vault>")?;
+            let name = args.get(1).ok_or("Missing argument <name>")?;
+            Ok(json!(vaults::rename(data_dir, vault, name)?))
+        }
+        ("vault", "switch") => Ok(json!(vaults::switch(data_dir, required(&args, "<vault>")?)?)),
+        ("vault", "delete") => {
+            let vault = required(&args, "<vault>")?;
+            let password = match std::env::var("ROKIO_PASSWORD") {
+                Ok(password) => password,
+                Err(_) => read_secret_line("password of the vault to delete")?,
+            };
+            vaults::delete(data_dir, vault, &password)?;
+            Ok(json!({ "deleted": vault }))
+        }
+
+        ("accounts", "list") => {
+            let key = open_vault(data_dir)?;
+            let query = AccountQuery {
+                tag: take_option(&mut args, "--tag"),
+                folder: take_option(&mut args, "--folder"),
+                include_subfolders: take_flag(&mut args, "--recursive"),
+            };
+            let accounts = vault::load_accounts(data_dir, &key)?;
+            let accounts = organize::query_accounts(accounts, &query);
+            Ok(json!(accounts.iter().map(AccountRow::from).collect::<Vec<_>>()))
+        }
+        ("accounts", "add") => {
+            let key = open_vault(data_dir)?;
+            let cookie = match args.first().map(String::as_str) {
+                None | Some("-") => read_secret_line("cookie")?,
+                Some(cookie) => cookie.to_string(),
+            };
+            let profile = vault::add_account_with_cookie(data_dir, &key, &cookie).await?;
+            Ok(json!(AccountRow::from(&profile)))
+        }
+        ("accounts", "remove") => {
+            let key = open_vault(data_dir)?;
+            let sign_out = take_flag(&mut args, "--sign-out");
+            let account = find_account(data_dir, &key, required(&args, "<account>")?)?;
+            if sign_out {
+                session::sign_out_and_remove(data_dir, &key, &account.id).await?;
+                return Ok(json!({ "signedOut": account.id }));
+            }
+            vault::remove_account(data_dir, &account.id)?;
+            Ok(json!({ "trashed": account.id }))
+        }
+        ("accounts", "export") => {
+            open_vault(data_dir)?;
+            serde_json::from_str(&vault::export_backup(data_dir)?).map_err(|e| e.to_string())
+        }
+        ("accounts", "import") => {
+            let key = open_vault(data_dir)?;
+            let merge = take_flag(&mut args, "--merge");
+            let path = required(&args, "<file>")?;
+            let data = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            let imported = vault::import_backup(data_dir, &key, &data, merge)?;
+            Ok(json!({ "imported": imported }))
+        }
+        ("accounts", "bulk-import") => {
+            let key = open_vault(data_dir)?;
+            let data = read_input(args.first().map(String::as_str))?;
+            Ok(json!(bulk_import::import_accounts(data_dir, &key, &data).await?))
+        }
+        ("accounts", "export-cookies") => {
+            let key = open_vault(data_dir)?;
+            let format = match take_option(&mut args, "--format").as_deref() {
+                None | Some("netscape") => CookieFormat::Netscape,
+                Some("json") => CookieFormat::Json,
+                Some(other) => return Err(format!("Unknown cookie format '{}'", other)),
+            };
+            let out_dir = PathBuf::from(take_option(&mut args, "--out").unwrap_or(".".into()));
+            required(&args, "<account>")?;
+
+            let accounts = args
+                .iter()
+                .map(|query| find_account(data_dir, &key, query))
+                .collect::<Result<Vec<_>, _>>()?;
+            let paths = cookie_files::export_sessions(data_dir, &accounts, format, &out_dir)?;
+            Ok(json!({ "written": paths }))
+        }
+        ("accounts", "import-cookies") => {
+            let key = open_vault(data_dir)?;
+            let data = read_input(args.first().map(String::as_str))?;
+            Ok(json!(cookie_files::import_cookie_file(data_dir, &key, &data).await?))
+        }
+
+        ("sessions", "list") => {
+            let key = open_vault(data_dir)?;
+            let account = find_account(data_dir, &key, required(&args, "<account>")?)?;
+            let sessions = session::list_sessions(&account.cookie).await?;
+            Ok(json!(sessions))
+        }
+        ("sessions", "sign-out-others") => {
+            let key = open_vault(data_dir)?;
+            let account = find_account(data_dir, &key, required(&args, "<account>")?)?;
+            session::sign_out_other_sessions(data_dir, &key, &account.id).await?;
+            Ok(json!({ "signedOutOthers": account.id }))
+        }
+
+        ("security", "") => {
+            let key = open_vault(data_dir)?;
+            let accounts = vault::load_accounts(data_dir, &key)?;
+            Ok(json!(security::audit_accounts(accounts).await))
+        }
+
+        ("trash", "list") => {
+            open_vault(data_dir)?;
+            let retention = load_settings(data_dir)?.trash_retention_days;
+            trash::purge_expired(data_dir, retention)?;
+            Ok(json!(trash::list_trash(data_dir, retention)?))
+        }
+        ("trash", "restore") => {
+            open_vault(data_dir)?;
+            let id = required(&args, "<account-id>")?.to_string();
+            Ok(json!({ "restored": trash::restore(data_dir, &[id])? }))
+        }
+        ("trash", "purge") => {
+            open_vault(data_dir)?;
+            let purged = if take_flag(&mut args, "--all") {
+                trash::purge(data_dir, None)?
+            } else {
+                let id = required(&args, "<account-id>")?.to_string();
+                trash::purge(data_dir, Some(&[id]))?
+            };
+            Ok(json!({ "purged": purged }))
+        }
+
+        ("launch", account) if !account.is_empty() => {
+            let key = open_vault(data_dir)?;
+            let account = find_account(data_dir, &key, account)?;
+            let target = match args.first() {
+                Some(raw) => Some(RobloxLink::parse(raw)?),
+                None => None,
+            };
+            Ok(json!(launch_account(data_dir, &key, &account.id, target).await?))
+        }
+
+        ("presets", "list") => Ok(json!(presets::load_presets(data_dir)?)),
+        ("presets", "run") => {
+            let key = open_vault(data_dir)?;
+            let preset = presets::find_preset(data_dir, required(&args, "<preset>")?)?;
+            Ok(json!(presets::run_preset(data_dir, &key, &preset, &|_| {}).await?))
+        }
+
+        ("instances", "list") => Ok(json!(running_instances(data_dir)?)),
+        ("instances", "kill") => {
+            let pid: u32 = required(&args, "<pid>")?
+                .parse()
+                .map_err(|_| "Invalid PID".to_string())?;
+
+            // Only clients ROKIO launched for this vault and that still run
+            let instance = running_instances(data_dir)?
+                .into_iter()
+                .find(|i| i.pid == pid)
+                .ok_or_else(|| format!("No running instance with PID {}", pid))?;
+            instance.backend(data_dir)?.terminate(pid)?;
+            history::record_killed(data_dir, &instance)?;
+            Ok(json!({ "killed": pid }))
+        }
+
+        ("history", "list") => Ok(json!(history::list_launches(data_dir, limit_option(&mut args)?)?)),
+        ("history", "playtime") => {
+            let account_id = args.first().map(String::as_str);
+            Ok(json!(history::playtime(data_dir, account_id, None)?))
+        }
+        ("history", "servers") => Ok(json!(history::recent_servers(data_dir, limit_option(&mut args)?)?)),
+
+        ("servers", place) if !place.is_empty() => {
+            let place_id = RobloxLink::parse(place)?
+                .place_id()
+                .ok_or("Not a place ID or game link")?;
+            let cursor = take_option(&mut args, "--cursor");
+            let (servers, next_page_cursor) =
+                game_detection::get_game_servers(place_id, cursor).await?;
+            Ok(json!({ "data": servers, "nextPageCursor": next_page_cursor }))
+        }
+
+        _ => Err(format!("Unknown command: {} {}\n\n{}", group, command, USAGE)),
+    }
+}
+
+// ============================================================================
+// VAULT SESSION
+// ============================================================================
+
+/// Vault key from `ROKIO_SESSION` or `ROKIO_PASSWORD`
+fn open_vault(data_dir: &Path) -> Result<[u8; 32], String> {
+    // The whole run stays on this vault, even if the app switches meanwhile
+    vaults::pin(data_dir, &vaults::active(data_dir).id);
+    if !vault_exists(data_dir) {
+        return Err("No vault found. Create one with `rokio vault create <name>`".to_string());
+    }
+
+    let key = if let Ok(session) = std::env::var("ROKIO_SESSION") {
+        read_session(data_dir, &session)?
+    } else if let Ok(password) = std::env::var("ROKIO_PASSWORD") {
+        verify_password(data_dir, &password)?.ok_or("Wrong password")?
+    } else {
+        return Err(
+            "Vault is locked. Run `rokio vault unlock` and export ROKIO_SESSION".to_string(),
+        );
+    };
+
+    // Keep vault cookies in step with Roblox session rotation
+    let dir = data_dir.to_path_buf();
+    session::set_rotation_handler(move |old_cookie, new_cookie| {
+        if let Err(e) = session::apply_rotation(&dir, &key, old_cookie, new_cookie) {
+            eprintln!("Failed to store rotated cookie: {}", e);
+        }
+    });
+    Ok(key)
+}
+
+/// Create a vault. Without any vault yet, this sets up the active (default)
+/// one under `name`, as the app does on first start.
+fn vault_create(data_dir: &Path, name: &str, key: &[u8; 32]) -> Result<Value, String> {
+    if vault_exists(data_dir) {
+        return Ok(json!(vaults::create(data_dir, name, key)?));
+    }
+
+    let active = vaults::active(data_dir);
+    write_empty_vault(data_dir, key)?;
+    if active.name == name {
+        return Ok(json!(active));
+    }
+    Ok(json!(vaults::rename(data_dir, &active.id, name)?))
+}
+
+/// The session file holds the vault key encrypted with a random session key.
+/// Only the session key is printed, so neither half is useful on its own.
+fn vault_unlock(data_dir: &Path) -> Result<Value, String> {
+    let password = match std::env::var("ROKIO_PASSWORD") {
+        Ok(password) => password,
+        Err(_) => read_secret_line("password")?,
+    };
+    vaults::pin(data_dir, &vaults::active(data_dir).id);
+    let key = verify_password(data_dir, &password)?.ok_or("Wrong password")?;
+
+    let mut session_key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut session_key);
+
+    let sealed = encrypt_string(&BASE64.encode(key), &session_key)?;
+    write_private(&session_path(data_dir), &sealed)?;
+
+    Ok(json!({ "session": BASE64.encode(session_key) }))
+}
+
+fn vault_lock(data_dir: &Path) -> Result<Value, String> {
+    let path = session_path(data_dir);
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to end session: {}", e))?;
+    }
+    Ok(json!({ "locked": true }))
+}
+
+fn vault_change_password(data_dir: &Path) -> Result<Value, String> {
+    let key = open_vault(data_dir)?;
+    let new_password = match std::env::var("ROKIO_NEW_PASSWORD") {
+        Ok(password) => password,
+        Err(_) => read_secret_line("new password")?,
+    };
+    if new_password.is_empty() {
+        return Err("New password cannot be empty".to_string());
+    }
+
+    vault::change_password(data_dir, &key, &new_password)?;
+
+    // The old session key still unlocks the old vault key
+    vault_lock(data_dir)?;
+    Ok(json!({ "changed": true }))
+}
+
+// Helper: Session file of the active vault
+fn session_path(data_dir: &Path) -> PathBuf {
+    vaults::active_dir(data_dir).join(SESSION_FILE)
+}
+
+fn read_session(data_dir: &Path, session: &str) -> Result<[u8; 32], String> {
+    let expired = || "Session expired. Run `rokio vault unlock` again".to_string();
+
+    let session_key: [u8; 32] = BASE64
+        .decode(session.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Invalid ROKIO_SESSION")?;
+
+    let sealed = fs::read_to_string(session_path(data_dir)).map_err(|_| expired())?;
+    let key = decrypt_string(sealed.trim(), &session_key).map_err(|_| expired())?;
+
+    BASE64
+        .decode(key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(expired)
+}
+
+/// Read one line from stdin (secrets stay out of the process list)
+fn read_secret_line(what: &str) -> Result<String, String> {
+    eprintln!("Enter {}:", what);
+    let mut line = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(|e| format!("Failed to read {}: {}", what, e))?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Look up an account by ID, username, alias or user ID
+fn find_account(data_dir: &Path, key: &[u8; 32], query: &str) -> Result<Profile, String> {
+    vault::load_accounts(data_dir, key)?
+        .into_iter()
+        .find(|a| {
+            a.id == query
+                || a.username.eq_ignore_ascii_case(query)
+                || (!a.alias.is_empty() && a.alias.eq_ignore_ascii_case(query))
+                || a.user_id.to_string() == query
+        })
+        .ok_or_else(|| "Account not found".to_string())
+}
+
+/// `--limit <n>`, 50 when omitted
+fn limit_option(args: &mut Vec<String>) -> Result<u32, String> {
+    match take_option(args, "--limit") {
+        Some(n) => n.parse().map_err(|_| "Invalid --limit".to_string()),
+        None => Ok(50),
+    }
+}
+
+/// Running clients of the active vault's accounts, wherever they were
+/// launched from. Launches whose client is gone are closed first.
+fn running_instances(data_dir: &Path) -> Result<Vec<ActiveInstance>, String> {
+    history::close_orphaned(data_dir, is_process_running)?;
+    let vault_id = vaults::active(data_dir).id;
+    Ok(history::running(data_dir)?
+        .into_iter()
+        .filter(|i| i.vault_id == vault_id)
+        .collect())
+}
+
+/// Contents of a file, or of stdin when `path` is omitted or '-'
+fn read_input(path: Option<&str>) -> Result<String, String> {
+    match path {
+        None | Some("-") => {
+            let mut data = String::new();
+            std::io::stdin()
+                .read_to_string(&mut data)
+                .map_err(|e| format!("Failed to read stdin: {}", e))?;
+            Ok(data)
+        }
+        Some(path) => {
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))
+        }
+    }
+}
+
+/// Write a file only the current user can read
+fn write_private(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to secure {}: {}", path.display(), e))?;
+    }
+
+    Ok(())
+}
+
+fn required<'a>(args: &'a [String], name: &str) -> Result<&'a str, String> {
+    args.first()
+        .map(String::as_str)
+        .ok_or_else(|| format!("Missing argument {}", name))
+}
+
+/// Remove `--name` from the arguments, returning whether it was present
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let before = args.len();
+    args.retain(|a| a != name);
+    args.len() != before
+}
+
+/// Remove `--name <value>` from the arguments, returning the value
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let index = args.iter().position(|a| a == name)?;
+    args.remove(index);
+    (index < args.len()).then(|| args.remove(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_option_and_flag() {
+        let mut list = args(&["--data-dir", "/tmp/x", "accounts", "import", "f.json", "--merge"]);
+        assert_eq!(take_option(&mut list, "--data-dir").as_deref(), Some("/tmp/x"));
+        assert!(take_flag(&mut list, "--merge"));
+        assert!(!take_flag(&mut list, "--merge"));
+        assert_eq!(list, args(&["accounts", "import", "f.json"]));
+
+        let mut dangling = args(&["servers", "1818", "--cursor"]);
+        assert_eq!(take_option(&mut dangling, "--cursor"), None);
+        assert_eq!(dangling, args(&["servers", "1818"]));
+    }
+
+    #[test]
+    fn test_wants_help() {
+        assert!(wants_help(&[]));
+        assert!(wants_help(&args(&["help"])));
+        assert!(wants_help(&args(&["launch", "--help"])));
+        assert!(!wants_help(&args(&["launch", "help"])));
+        assert!(!wants_help(&args(&["presets", "run", "help"])));
+    }
+
+    #[test]
+    fn test_vault_create_bootstraps_first_vault() {
+        let dir = std::env::temp_dir().join(format!("rokio-cli-{}", uuid::Uuid::new_v4()));
+        let key = derive_key("pw");
+
+        vault_create(&dir, "Main", &key).unwrap();
+        assert!(verify_password(&dir, "pw").unwrap().is_some());
+        assert_eq!(vaults::active(&dir).name, "Main");
+
+        // Once there is a vault, a new one is added next to it
+        vault_create(&dir, "Work", &key).unwrap();
+        assert_eq!(vaults::list(&dir).unwrap().len(), 2);
+        assert_eq!(vaults::active(&dir).name, "Main");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_session_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("rokio-cli-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let key = [7u8; 32];
+        let session_key = [9u8; 32];
+        let sealed = encrypt_string(&BASE64.encode(key), &session_key).unwrap();
+        write_private(&session_path(&dir), &sealed).unwrap();
+
+        assert_eq!(read_session(&dir, &BASE64.encode(session_key)).unwrap(), key);
+        assert!(read_session(&dir, &BASE64.encode([1u8; 32])).is_err());
+        assert!(read_session(&dir, "not a session").is_err());
+
+        vault_lock(&dir).unwrap();
+        assert!(read_session(&dir, &BASE64.encode(session_key)).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,108 @@
+use crate::binarycookies::page::Page;
+
+pub const FILE_HEADER: &[u8] = &[0x63, 0x6F, 0x6F, 0x6B]; // cook
+pub const FILE_FOOTER: &[u8] = &[0x07, 0x17, 0x20, 0x05, 0x00, 0x00, 0x00, 0x4B];
+
+#[derive(Debug, Clone)]
+pub struct BinaryCookies {
+    pub pages: Vec<Page>,
+}
+
+impl BinaryCookies {
+    pub fn new(pages: Vec<Page>) -> Self {
+        Self { pages }
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let mut pages_size_bytes = vec![];
+        let mut pages_bytes = vec![];
+
+        let mut checksum = 0;
+        for page in &self.pages {
+            let page_bytes = page.build();
+            pages_size_bytes.push(page_bytes.len() as u32);
+
+            // calculate checksum
+            for i in (0..page_bytes.len()).step_by(4) {
+                checksum += page_bytes[i] as u32;
+            }
+
+            pages_bytes.push(page_bytes);
+        }
+
+        let mut bytes = vec![];
+        bytes.extend_from_slice(FILE_HEADER); // magic (cook)
+        bytes.extend_from_slice(&(self.pages.len() as u32).to_be_bytes()); // number of pages
+        for page_size in pages_size_bytes {
+            // repeat for n pages
+            bytes.extend_from_slice(&page_size.to_be_bytes()); // page n size
+        }
+        for page_bytes in pages_bytes {
+            // repeat for n pages
+            bytes.extend_from_slice(&page_bytes); // page n
+        }
+        bytes.extend_from_slice(&checksum.to_be_bytes()); // checksum
+        bytes.extend_from_slice(FILE_FOOTER); // footer (0x071720050000004B)
+
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binarycookies::utils::from_cocoa_timestamp;
+    use crate::binarycookies::Cookie;
+    use std::time::{Duration, SystemTime};
+
+    fn be_u32(bytes: &[u8], at: usize) -> u32 {
+        u32::from_be_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    fn le_u32(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    fn c_str(bytes: &[u8], at: usize) -> &str {
+        let end = at + bytes[at..].iter().position(|&b| b == 0).unwrap();
+        std::str::from_utf8(&bytes[at..end]).unwrap()
+    }
+
+    #[test]
+    fn test_build_file_layout() {
+        let expires = SystemTime::UNIX_EPOCH + Duration::from_secs(2_000_000_000);
+        let cookie = Cookie::new(
+            ".roblox.com".into(),
+            ".ROBLOSECURITY".into(),
+            None,
+            "token".into(),
+            Some(true),
+            Some(true),
+            Some(expires),
+            None,
+        );
+        let bytes = BinaryCookies::new(vec![Page::new(vec![cookie])]).build();
+
+        assert_eq!(&bytes[..4], FILE_HEADER);
+        assert_eq!(be_u32(&bytes, 4), 1);
+        let page_size = be_u32(&bytes, 8) as usize;
+        let page = &bytes[12..12 + page_size];
+        assert_eq!(bytes.len(), 12 + page_size + 4 + FILE_FOOTER.len());
+        assert!(bytes.ends_with(FILE_FOOTER));
+
+        let checksum: u32 = page.iter().step_by(4).map(|&b| b as u32).sum();
+        assert_eq!(be_u32(&bytes, 12 + page_size), checksum);
+
+        assert_eq!(le_u32(page, 4), 1);
+        let cookie = &page[le_u32(page, 8) as usize..];
+        assert_eq!(le_u32(cookie, 0) as usize, cookie.len());
+        assert_eq!(le_u32(cookie, 8), 1 | 1 << 2);
+        assert_eq!(c_str(cookie, le_u32(cookie, 16) as usize), ".roblox.com");
+        assert_eq!(c_str(cookie, le_u32(cookie, 20) as usize), ".ROBLOSECURITY");
+        assert_eq!(c_str(cookie, le_u32(cookie, 24) as usize), "/");
+        assert_eq!(c_str(cookie, le_u32(cookie, 28) as usize), "token");
+
+        let expiration = f64::from_le_bytes(cookie[40..48].try_into().unwrap());
+        assert_eq!(from_cocoa_timestamp(expiration), expires);
+    }
+}
@@ -14,6 +14,7 @@ pub struct Cookie {
 }
 
 impl Cookie {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         domain: String,
         name: String,
@@ -3,6 +3,7 @@
     Reference: https://github.com/interstateone/BinaryCookies
 */
 
+#[allow(clippy::module_inception)]
 pub mod binarycookies;
 pub mod cookie;
 pub mod page;
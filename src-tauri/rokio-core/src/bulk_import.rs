@@ -0,0 +1,433 @@
+//! ROKIO Bulk Import - Many accounts at once from plain cookie data
+//!
+//! Accepts, detected from the content:
+//! - a cookie list, one per line (`user:pass:cookie` combos work too)
+//! - CSV with a header row: a `cookie` column plus optional `alias`,
+//!   `notes`/`description`, `tags` and `folder`/`group` columns
+//! - unencrypted JSON exports from other managers (e.g. Roblox Account
+//!   Manager's `AccountData.json`), or any array of objects with a cookie field
+//!
+//! Cookies are validated a few at a time, then all new accounts are written
+//! in one vault save. Every input row gets a result.
+
+use crate::organize::{normalize_folder, normalize_tags};
+use crate::profiles::Profile;
+use crate::roblox::{validate_and_get_user, RobloxUserData};
+use crate::vault::{next_sort_order, update_accounts};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Cookies validated at the same time
+const MAX_CONCURRENT_VALIDATIONS: usize = 5;
+
+/// Start of every `.ROBLOSECURITY` value
+const COOKIE_MARKER: &str = "_|WARNING";
+
+/// Header names (lowercase) accepted for each column / JSON field
+const COOKIE_KEYS: &[&str] = &["cookie", "securitytoken", "roblosecurity", ".roblosecurity"];
+const ALIAS_KEYS: &[&str] = &["alias"];
+const NOTES_KEYS: &[&str] = &["notes", "note", "description"];
+const TAGS_KEYS: &[&str] = &["tags", "tag"];
+const FOLDER_KEYS: &[&str] = &["folder", "group"];
+
+/// One account found in the input
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportRow {
+    /// Line number (cookie list, CSV) or position (JSON), 1-based
+    pub row: usize,
+    /// Empty when the row holds no cookie
+    pub cookie: String,
+    pub alias: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub folder: String,
+}
+
+/// What happened to one input row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RowStatus {
+    Added,
+    /// The Roblox user is already in the vault (or earlier in the input)
+    Duplicate,
+    Invalid,
+}
+
+/// Per-row import report
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RowResult {
+    pub row: usize,
+    pub status: RowStatus,
+    pub account_id: Option<String>,
+    pub username: Option<String>,
+    pub message: Option<String>,
+}
+
+// ============================================================================
+// PARSING
+// ============================================================================
+
+/// Pull the cookie out of a value such as `.ROBLOSECURITY=_|WARNING...` or
+/// `user:pass:_|WARNING...`
+fn extract_cookie(text: &str) -> Option<String> {
+    let text = text.trim().trim_matches(|c| c == '"' || c == '\'');
+    let cookie = match text.find(COOKIE_MARKER) {
+        Some(start) => text[start..]
+            .split(|c: char| c.is_whitespace() || matches!(c, ';' | ',' | '"' | '\''))
+            .next()
+            .unwrap_or_default(),
+        None => {
+            let value = text.strip_prefix(".ROBLOSECURITY=").unwrap_or(text);
+            value.rsplit(':').next().unwrap_or_default().trim()
+        }
+    };
+    (!cookie.is_empty() && !cookie.contains(char::is_whitespace)).then(|| cookie.to_string())
+}
+
+/// Split one CSV line, honouring double quotes
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+// Helper: Tags given as "a; b", "a|b" or "a, b"
+fn split_tags(text: &str) -> Vec<String> {
+    normalize_tags(
+        &text
+            .split([';', '|', ','])
+            .map(str::to_string)
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn parse_csv(lines: &[(usize, &str)]) -> Result<Vec<ImportRow>, String> {
+    let (_, header) = lines.first().ok_or("Empty CSV")?;
+    let header: Vec<String> = split_csv_line(header)
+        .into_iter()
+        .map(|h| h.to_lowercase())
+        .collect();
+    let column = |keys: &[&str]| header.iter().position(|h| keys.contains(&h.as_str()));
+    let cookie_col = column(COOKIE_KEYS).ok_or("CSV has no cookie column")?;
+    let (alias_col, notes_col, tags_col, folder_col) = (
+        column(ALIAS_KEYS),
+        column(NOTES_KEYS),
+        column(TAGS_KEYS),
+        column(FOLDER_KEYS),
+    );
+
+    Ok(lines[1..]
+        .iter()
+        .map(|(row, line)| {
+            let fields = split_csv_line(line);
+            let get =
+                |col: Option<usize>| col.and_then(|c| fields.get(c)).cloned().unwrap_or_default();
+            ImportRow {
+                row: *row,
+                cookie: extract_cookie(&get(Some(cookie_col))).unwrap_or_default(),
+                alias: get(alias_col),
+                description: get(notes_col),
+                tags: split_tags(&get(tags_col)),
+                folder: normalize_folder(&get(folder_col)),
+            }
+        })
+        .collect())
+}
+
+fn parse_json(data: &str) -> Result<Vec<ImportRow>, String> {
+    let value: Value = serde_json::from_str(data).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let items = match value {
+        Value::Array(items) => items,
+        // {"accounts": [...]} and similar wrappers
+        Value::Object(map) => map
+            .into_iter()
+            .find_map(|(_, v)| match v {
+                Value::Array(items) => Some(items),
+                _ => None,
+            })
+            .ok_or("No account list found in the JSON")?,
+        _ => return Err("No account list found in the JSON".to_string()),
+    };
+
+    Ok(items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            // Field lookup ignoring case
+            let field = |keys: &[&str]| {
+                item.as_object()?
+                    .iter()
+                    .find(|(k, _)| keys.contains(&k.to_lowercase().as_str()))
+                    .map(|(_, v)| v)
+            };
+            let text = |keys: &[&str]| {
+                field(keys)
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            };
+            let tags = match field(TAGS_KEYS) {
+                Some(Value::Array(tags)) => normalize_tags(
+                    &tags
+                        .iter()
+                        .filter_map(|t| t.as_str().map(str::to_string))
+                        .collect::<Vec<_>>(),
+                ),
+                Some(Value::String(tags)) => split_tags(tags),
+                _ => Vec::new(),
+            };
+            let cookie = match item {
+                Value::String(s) => extract_cookie(s),
+                _ => extract_cookie(&text(COOKIE_KEYS)),
+            };
+
+            ImportRow {
+                row: i + 1,
+                cookie: cookie.unwrap_or_default(),
+                alias: text(ALIAS_KEYS),
+                description: text(NOTES_KEYS),
+                tags,
+                folder: normalize_folder(&text(FOLDER_KEYS)),
+            }
+        })
+        .collect())
+}
+
+/// Find the accounts in `data`, detecting its format
+pub fn parse_import(data: &str) -> Result<Vec<ImportRow>, String> {
+    let data = data.trim_start_matches('\u{feff}').trim();
+    if data.starts_with('[') || data.starts_with('{') {
+        return parse_json(data);
+    }
+
+    let lines: Vec<(usize, &str)> = data
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+
+    // A CSV header names its columns instead of holding a cookie
+    let is_csv = lines.first().is_some_and(|(_, first)| {
+        !first.contains(COOKIE_MARKER)
+            && split_csv_line(first)
+                .iter()
+                .any(|h| COOKIE_KEYS.contains(&h.to_lowercase().as_str()))
+    });
+    if is_csv {
+        return parse_csv(&lines);
+    }
+
+    Ok(lines
+        .into_iter()
+        .map(|(row, line)| ImportRow {
+            row,
+            cookie: extract_cookie(line).unwrap_or_default(),
+            ..Default::default()
+        })
+        .collect())
+}
+
+// ============================================================================
+// IMPORT
+// ============================================================================
+
+// Helper: Result for a row that was not added
+fn rejected(row: usize, status: RowStatus, message: String, username: Option<String>) -> RowResult {
+    RowResult {
+        row,
+        status,
+        account_id: None,
+        username,
+        message: Some(message),
+    }
+}
+
+/// Import accounts from `data` in any supported format (see `parse_import`)
+pub async fn import_accounts(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    data: &str,
+) -> Result<Vec<RowResult>, String> {
+    import_rows(app_data_dir, key, parse_import(data)?).await
+}
+
+/// Validate every row's cookie (a few at a time) and add the new accounts.
+/// Returns one result per row, in input order.
+pub async fn import_rows(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    rows: Vec<ImportRow>,
+) -> Result<Vec<RowResult>, String> {
+    if rows.is_empty() {
+        return Err("No accounts found in the input".to_string());
+    }
+
+    let limit = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_VALIDATIONS));
+    let mut validations = tokio::task::JoinSet::new();
+    // Row of each task, to report a task that panicked or was cancelled
+    let mut task_rows = HashMap::new();
+    for row in rows {
+        let limit = limit.clone();
+        let row_number = row.row;
+        let task = validations.spawn(async move {
+            if row.cookie.is_empty() {
+                return (row, Err("No cookie found".to_string()));
+            }
+            let _permit = limit.acquire_owned().await.ok();
+            let user = validate_and_get_user(&row.cookie).await;
+            (row, user)
+        });
+        task_rows.insert(task.id(), row_number);
+    }
+
+    let mut validated: Vec<(ImportRow, Result<RobloxUserData, String>)> = Vec::new();
+    let mut results = Vec::new();
+    while let Some(result) = validations.join_next_with_id().await {
+        match result {
+            Ok((_, pair)) => validated.push(pair),
+            Err(e) => results.push(rejected(
+                task_rows[&e.id()],
+                RowStatus::Invalid,
+                format!("Validation failed: {}", e),
+                None,
+            )),
+        }
+    }
+    validated.sort_by_key(|(row, _)| row.row);
+
+    // Load after validating so the vault isn't held stale across requests
+    let now = chrono::Utc::now().timestamp() as u64;
+    update_accounts(app_data_dir, key, |accounts| {
+        let mut next_order = next_sort_order(accounts);
+        for (row, user) in validated {
+            let user = match user {
+                Ok(user) => user,
+                Err(e) => {
+                    results.push(rejected(row.row, RowStatus::Invalid, e, None));
+                    continue;
+                }
+            };
+            if accounts.iter().any(|a| a.user_id == user.id) {
+                let message = "Already in the vault".to_string();
+                results.push(rejected(
+                    row.row,
+                    RowStatus::Duplicate,
+                    message,
+                    Some(user.name),
+                ));
+                continue;
+            }
+
+            let profile = Profile {
+                id: uuid::Uuid::new_v4().to_string(),
+                cookie: row.cookie,
+                user_id: user.id,
+                username: user.name,
+                display_name: user.display_name,
+                thumbnail: user.thumbnail,
+                alias: row.alias,
+                description: row.description,
+                tags: row.tags,
+                folder: row.folder,
+                sort_order: next_order,
+                is_favorite: false,
+                last_played_at: 0,
+                password: None,
+                created_at: Some(now),
+                is_premium: None,
+            };
+            next_order += 1;
+            results.push(RowResult {
+                row: row.row,
+                status: RowStatus::Added,
+                account_id: Some(profile.id.clone()),
+                username: Some(profile.username.clone()),
+                message: None,
+            });
+            accounts.push(profile);
+        }
+        Ok(())
+    })?;
+    results.sort_by_key(|r| r.row);
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOKIE: &str =
+        "_|WARNING:-DO-NOT-SHARE-THIS.--Sharing-this-will-allow-someone-to-log-in-as-you.|_ABC123";
+
+    #[test]
+    fn test_parse_cookie_list() {
+        let data = format!(
+            "{c}\n\n# comment\n.ROBLOSECURITY={c}\nuser:pass:{c}\nnot a cookie",
+            c = COOKIE
+        );
+        let rows = parse_import(&data).unwrap();
+        assert_eq!(rows.len(), 4);
+        assert!(rows[..3].iter().all(|r| r.cookie == COOKIE));
+        assert_eq!(rows[1].row, 4);
+        assert_eq!(rows[3].cookie, "");
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let data = format!(
+            "Alias,Cookie,Notes,Tags\nMain,{c},\"farm, day 1\",grinder; Trade\n,,,",
+            c = COOKIE
+        );
+        let rows = parse_import(&data).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].alias, "Main");
+        assert_eq!(rows[0].cookie, COOKIE);
+        assert_eq!(rows[0].description, "farm, day 1");
+        assert_eq!(rows[0].tags, vec!["grinder", "Trade"]);
+        assert_eq!(rows[1].cookie, "");
+    }
+
+    #[test]
+    fn test_parse_account_manager_json() {
+        let data = serde_json::json!([
+            {
+                "SecurityToken": COOKIE,
+                "Username": "alt1",
+                "Alias": "Alt",
+                "Description": "notes",
+                "Group": "Farm",
+                "UserID": 1
+            },
+            COOKIE
+        ])
+        .to_string();
+        let rows = parse_import(&data).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].cookie, COOKIE);
+        assert_eq!(rows[0].alias, "Alt");
+        assert_eq!(rows[0].description, "notes");
+        assert_eq!(rows[0].folder, "Farm");
+        assert_eq!(rows[1].cookie, COOKIE);
+    }
+}
@@ -0,0 +1,227 @@
+//! ROKIO Cookie Files - Sessions in formats other tools understand
+//!
+//! Exports an account's `.ROBLOSECURITY` as a Netscape `cookies.txt` (curl,
+//! wget, yt-dlp) or the JSON array used by cookie-editor browser extensions,
+//! one file per account, readable only by the current user. Imports pick the
+//! `.ROBLOSECURITY` cookie for `roblox.com` out of either format and add it
+//! through the bulk importer, which validates it with Roblox.
+
+use crate::audit;
+use crate::bulk_import::{import_rows, ImportRow, RowResult};
+use crate::profiles::Profile;
+use crate::roblox_link::is_roblox_domain;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const COOKIE_NAME: &str = ".ROBLOSECURITY";
+const COOKIE_DOMAIN: &str = ".roblox.com";
+/// Expiry written to exported cookies (Roblox decides the real one)
+const EXPORT_LIFETIME_SECS: i64 = 365 * 24 * 60 * 60;
+
+/// Cookie file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CookieFormat {
+    /// Netscape `cookies.txt`
+    Netscape,
+    /// cookie-editor style JSON array
+    Json,
+}
+
+impl CookieFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            CookieFormat::Netscape => "cookies.txt",
+            CookieFormat::Json => "cookies.json",
+        }
+    }
+}
+
+/// File contents holding one account's session
+pub fn to_cookie_file(cookie: &str, format: CookieFormat) -> String {
+    let expires = chrono::Utc::now().timestamp() + EXPORT_LIFETIME_SECS;
+    match format {
+        CookieFormat::Netscape => format!(
+            "# Netscape HTTP Cookie File\n#HttpOnly_{}\tTRUE\t/\tTRUE\t{}\t{}\t{}\n",
+            COOKIE_DOMAIN, expires, COOKIE_NAME, cookie
+        ),
+        CookieFormat::Json => {
+            let cookies = json!([{
+                "domain": COOKIE_DOMAIN,
+                "expirationDate": expires,
+                "hostOnly": false,
+                "httpOnly": true,
+                "name": COOKIE_NAME,
+                "path": "/",
+                "sameSite": "lax",
+                "secure": true,
+                "session": false,
+                "storeId": null,
+                "value": cookie,
+            }]);
+            serde_json::to_string_pretty(&cookies).unwrap_or_default()
+        }
+    }
+}
+
+// Helper: Create (or replace) a file only the current user can read
+fn write_private(path: &Path, content: &str) -> Result<(), String> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    // `mode` only applies to new files
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to secure {}: {}", path.display(), e))?;
+    }
+
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Write one cookie file per account into `out_dir` (`<username>.cookies.txt`
+/// or `.cookies.json`) and record each export in the audit log.
+/// Returns the written paths.
+pub fn export_sessions(
+    app_data_dir: &Path,
+    accounts: &[Profile],
+    format: CookieFormat,
+    out_dir: &Path,
+) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(out_dir)
+        .map_err(|e| format!("Failed to create {}: {}", out_dir.display(), e))?;
+
+    let mut written = Vec::new();
+    for account in accounts {
+        // Usernames are [A-Za-z0-9_], safe as file names
+        let path = out_dir.join(format!("{}.{}", account.username, format.extension()));
+        audit::record(app_data_dir, "cookie_exported", Some(&account.id), true)?;
+        write_private(&path, &to_cookie_file(&account.cookie, format))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// The `.ROBLOSECURITY` values for roblox.com in a Netscape or JSON cookie file
+pub fn parse_cookie_file(data: &str) -> Result<Vec<String>, String> {
+    let data = data.trim_start_matches('\u{feff}').trim();
+    let mut found = Vec::new();
+
+    if data.starts_with('[') || data.starts_with('{') {
+        let value: Value =
+            serde_json::from_str(data).map_err(|e| format!("Invalid JSON: {}", e))?;
+        // A plain array, or wrapped as {"cookies": [...]}
+        let cookies = match &value {
+            Value::Array(items) => items.as_slice(),
+            Value::Object(map) => match map.get("cookies") {
+                Some(Value::Array(items)) => items.as_slice(),
+                _ => std::slice::from_ref(&value),
+            },
+            _ => &[],
+        };
+        for cookie in cookies {
+            let text = |key: &str| cookie.get(key).and_then(Value::as_str).unwrap_or_default();
+            if text("name") == COOKIE_NAME && is_roblox_domain(text("domain")) {
+                found.push(text("value").to_string());
+            }
+        }
+    } else {
+        for line in data.lines() {
+            // curl marks HttpOnly cookies with a "#HttpOnly_" domain prefix
+            let line = line
+                .trim()
+                .strip_prefix("#HttpOnly_")
+                .unwrap_or(line.trim());
+            if line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() >= 7 && fields[5] == COOKIE_NAME && is_roblox_domain(fields[0]) {
+                found.push(fields[6].trim().to_string());
+            }
+        }
+    }
+
+    found.retain(|c| !c.is_empty());
+    found.dedup();
+    if found.is_empty() {
+        return Err("No .ROBLOSECURITY cookie for roblox.com found".to_string());
+    }
+    Ok(found)
+}
+
+/// Add the accounts found in a cookie file, validating each with Roblox
+pub async fn import_cookie_file(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    data: &str,
+) -> Result<Vec<RowResult>, String> {
+    let rows = parse_cookie_file(data)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, cookie)| ImportRow {
+            row: i + 1,
+            cookie,
+            ..Default::default()
+        })
+        .collect();
+    import_rows(app_data_dir, key, rows).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{profile, temp_dir};
+
+    #[test]
+    fn test_round_trip() {
+        for format in [CookieFormat::Netscape, CookieFormat::Json] {
+            let file = to_cookie_file("_|WARNING|_ABC", format);
+            assert_eq!(parse_cookie_file(&file).unwrap(), vec!["_|WARNING|_ABC"]);
+        }
+    }
+
+    #[test]
+    fn test_ignores_other_cookies_and_domains() {
+        let data = "# Netscape HTTP Cookie File\n\
+            .example.com\tTRUE\t/\tTRUE\t0\t.ROBLOSECURITY\tNOPE\n\
+            .roblox.com\tTRUE\t/\tFALSE\t0\tRBXEventTrackerV2\tx\n\
+            www.roblox.com\tFALSE\t/\tTRUE\t0\t.ROBLOSECURITY\tYES\n";
+        assert_eq!(parse_cookie_file(data).unwrap(), vec!["YES"]);
+        assert!(parse_cookie_file("[]").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_export_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("cookies");
+        let account = Profile {
+            cookie: "SECRET".to_string(),
+            ..profile(1)
+        };
+
+        let paths = export_sessions(&dir, &[account], CookieFormat::Netscape, &dir).unwrap();
+        assert_eq!(paths[0].file_name().unwrap(), "user1.cookies.txt");
+        let mode = fs::metadata(&paths[0]).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(audit::list(&dir, 1).unwrap()[0].action, "cookie_exported");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
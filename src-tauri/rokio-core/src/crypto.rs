@@ -0,0 +1,261 @@
+//! ROKIO Crypto Module
+//! AES-256-GCM encryption with PBKDF2 key derivation from machine ID + password.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use sha2::Sha256;
+
+// ============================================================================
+// HARDWARE ID (Platform-specific)
+// ============================================================================
+
+/// Get a unique machine identifier for key derivation
+#[cfg(target_os = "macos")]
+pub fn get_machine_id() -> String {
+    use mac_address2::get_mac_address;
+
+    match get_mac_address() {
+        Ok(Some(ma)) => {
+            format!("ROKIO-MAC-{}", ma.to_string().replace(":", ""))
+        }
+        _ => {
+            let hostname = std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_string());
+            let user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+            format!("ROKIO-FALLBACK-{}-{}", hostname, user)
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn get_machine_id() -> String {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+
+    if let Ok(key) = hklm.open_subkey("SOFTWARE\\Microsoft\\Cryptography") {
+        if let Ok(guid) = key.get_value::<String, _>("MachineGuid") {
+            return format!("ROKIO-WIN-{}", guid.replace("-", ""));
+        }
+    }
+
+    let computer = std::env::var("COMPUTERNAME").unwrap_or_else(|_| "unknown".to_string());
+    let user = std::env::var("USERNAME").unwrap_or_else(|_| "user".to_string());
+    format!("ROKIO-FALLBACK-{}-{}", computer, user)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn get_machine_id() -> String {
+    let hostname = std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_string());
+    let user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+    format!("ROKIO-LINUX-{}-{}", hostname, user)
+}
+
+// ============================================================================
+// KEY DERIVATION
+// ============================================================================
+
+const PBKDF2_ITERATIONS: u32 = 100_000;
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+/// Known plaintext stored in the vault to check the master password
+pub const VAULT_VERIFICATION: &str = "ROKIO_VAULT_V1";
+
+/// Derive a 32-byte encryption key from password + machine ID using PBKDF2
+pub fn derive_key(password: &str) -> [u8; 32] {
+    let machine_id = get_machine_id();
+    let salt = format!("ROKIO-VAULT-{}", machine_id);
+
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2_hmac::<Sha256>(
+        password.as_bytes(),
+        salt.as_bytes(),
+        PBKDF2_ITERATIONS,
+        &mut key,
+    );
+    key
+}
+
+// ============================================================================
+// ENCRYPTION / DECRYPTION
+// ============================================================================
+
+/// Encrypt a string using AES-256-GCM
+/// Returns: base64(nonce || ciphertext)
+pub fn encrypt_string(plaintext: &str, key: &[u8; 32]) -> Result<String, String> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| format!("Cipher init error: {}", e))?;
+
+    // Generate random 12-byte nonce
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    // Encrypt
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext.as_bytes())
+        .map_err(|e| format!("Encryption error: {}", e))?;
+
+    // Combine nonce + ciphertext and encode as base64
+    let mut combined = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    combined.extend_from_slice(&nonce_bytes);
+    combined.extend_from_slice(&ciphertext);
+
+    Ok(BASE64.encode(&combined))
+}
+
+/// Decrypt a string using AES-256-GCM
+/// Input: base64(nonce || ciphertext)
+pub fn decrypt_string(encrypted: &str, key: &[u8; 32]) -> Result<String, String> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| format!("Cipher init error: {}", e))?;
+
+    // Decode base64
+    let combined = BASE64
+        .decode(encrypted)
+        .map_err(|e| format!("Base64 decode error: {}", e))?;
+
+    if combined.len() < NONCE_LEN {
+        return Err("Invalid ciphertext: too short".to_string());
+    }
+
+    // Split nonce and ciphertext
+    let (nonce_bytes, ciphertext) = combined.split_at(NONCE_LEN);
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    // Decrypt
+    let plaintext = cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| "Decryption failed: invalid key or corrupted data".to_string())?;
+
+    String::from_utf8(plaintext).map_err(|e| format!("UTF-8 decode error: {}", e))
+}
+
+// ============================================================================
+// VAULT FILE OPERATIONS
+// ============================================================================
+
+/// Vault file name inside a vault directory
+pub const VAULT_FILE: &str = "vault.dat";
+
+/// Check if the active vault's file exists
+pub fn vault_exists(app_data_dir: &std::path::Path) -> bool {
+    vault_path(app_data_dir).exists()
+}
+
+/// Get the active vault's file path (see `vaults`)
+pub fn vault_path(app_data_dir: &std::path::Path) -> std::path::PathBuf {
+    crate::vaults::active_dir(app_data_dir).join(VAULT_FILE)
+}
+
+/// Write a new, empty active vault protected by `key`
+pub fn write_empty_vault(app_data_dir: &std::path::Path, key: &[u8; 32]) -> Result<(), String> {
+    write_empty_vault_at(&vault_path(app_data_dir), key)
+}
+
+/// Write a new, empty vault file at `vault_file`
+pub fn write_empty_vault_at(vault_file: &std::path::Path, key: &[u8; 32]) -> Result<(), String> {
+    // Create the vault directory if it doesn't exist
+    if let Some(dir) = vault_file.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    // Create empty vault with a verification string
+    let verification = encrypt_string(VAULT_VERIFICATION, key)?;
+
+    let vault_data = serde_json::json!({
+        "version": 1,
+        "verification": verification,
+        "accounts": []
+    });
+
+    crate::vault::write_atomic(vault_file, vault_data.to_string().as_bytes())
+        .map_err(|e| format!("Failed to write vault: {}", e))
+}
+
+/// Check a master password against the active vault.
+/// Returns the derived key when it is correct, `None` otherwise.
+pub fn verify_password(
+    app_data_dir: &std::path::Path,
+    password: &str,
+) -> Result<Option<[u8; 32]>, String> {
+    verify_password_at(&vault_path(app_data_dir), password)
+}
+
+/// `verify_password` against the vault file at `vault_file`
+pub fn verify_password_at(
+    vault_file: &std::path::Path,
+    password: &str,
+) -> Result<Option<[u8; 32]>, String> {
+    // Read vault file
+    let vault_content = std::fs::read_to_string(vault_file).map_err(|e| e.to_string())?;
+
+    let vault: serde_json::Value =
+        serde_json::from_str(&vault_content).map_err(|e| e.to_string())?;
+
+    // Derive key and verify
+    let key = derive_key(password);
+    let verification = vault["verification"]
+        .as_str()
+        .ok_or("Invalid vault format")?;
+
+    match decrypt_string(verification, &key) {
+        Ok(decrypted) if decrypted == VAULT_VERIFICATION => Ok(Some(key)),
+        _ => Ok(None),
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let key = derive_key("test_password");
+        let plaintext = "Hello, ROKIO!";
+
+        let encrypted = encrypt_string(plaintext, &key).unwrap();
+        let decrypted = decrypt_string(&encrypted, &key).unwrap();
+
+        assert_eq!(plaintext, decrypted);
+    }
+
+    #[test]
+    fn test_wrong_key_fails() {
+        let key1 = derive_key("password1");
+        let key2 = derive_key("password2");
+
+        let encrypted = encrypt_string("secret", &key1).unwrap();
+        let result = decrypt_string(&encrypted, &key2);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_machine_id_not_empty() {
+        let hwid = get_machine_id();
+        assert!(!hwid.is_empty());
+        assert!(hwid.starts_with("ROKIO-"));
+    }
+
+    #[test]
+    fn test_verify_password() {
+        let dir = temp_dir("crypto");
+        let key = derive_key("correct horse");
+        write_empty_vault(&dir, &key).unwrap();
+
+        assert!(vault_exists(&dir));
+        assert_eq!(verify_password(&dir, "correct horse").unwrap(), Some(key));
+        assert_eq!(verify_password(&dir, "wrong").unwrap(), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
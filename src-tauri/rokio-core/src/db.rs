@@ -0,0 +1,116 @@
+//! ROKIO Database - Local SQLite store for caches, history and the audit log
+//!
+//! One `rokio.db` file in the app data directory. Connections are cheap and
+//! short-lived (open, query, drop) so they never live across an `.await`.
+
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+
+/// Schema steps, applied in order; `PRAGMA user_version` records how many ran
+const MIGRATIONS: &[&str] = &[
+    // 1: game metadata cache
+    "CREATE TABLE place_universe (
+        place_id    INTEGER PRIMARY KEY,
+        universe_id INTEGER NOT NULL
+    );
+    CREATE TABLE game_details (
+        universe_id INTEGER PRIMARY KEY,
+        details     TEXT NOT NULL,
+        fetched_at  INTEGER NOT NULL
+    );
+    CREATE TABLE game_icons (
+        universe_id INTEGER PRIMARY KEY,
+        url         TEXT NOT NULL,
+        bytes       BLOB,
+        fetched_at  INTEGER NOT NULL
+    );",
+    // 2: launch history
+    "CREATE TABLE launch_history (
+        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+        account_id  TEXT NOT NULL,
+        username    TEXT NOT NULL,
+        place_id    INTEGER NOT NULL,
+        job_id      TEXT,
+        target_type TEXT NOT NULL,
+        pid         INTEGER NOT NULL,
+        started_at  INTEGER NOT NULL,
+        ended_at    INTEGER,
+        exit_reason TEXT
+    );
+    CREATE INDEX launch_history_account ON launch_history (account_id, place_id);
+    CREATE INDEX launch_history_started ON launch_history (started_at);",
+    // 3: audit log for sensitive vault access
+    "CREATE TABLE audit_log (
+        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+        at          INTEGER NOT NULL,
+        action      TEXT NOT NULL,
+        account_id  TEXT,
+        success     INTEGER NOT NULL
+    );",
+    // 4: exit code of launches whose client ROKIO spawned and waited on
+    "ALTER TABLE launch_history ADD COLUMN exit_code INTEGER;",
+    // 5: server a launched client actually joined, from its presence
+    "ALTER TABLE launch_history ADD COLUMN game_id TEXT;",
+    // 6: enough of a launch to track and kill it from any process
+    "ALTER TABLE launch_history ADD COLUMN vault_id TEXT;
+    ALTER TABLE launch_history ADD COLUMN options TEXT;",
+];
+
+/// Get the database path
+pub fn db_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("rokio.db")
+}
+
+/// Open the database, creating and migrating it as needed
+pub fn open(app_data_dir: &Path) -> Result<Connection, String> {
+    std::fs::create_dir_all(app_data_dir).map_err(|e| e.to_string())?;
+    let mut conn = Connection::open(db_path(app_data_dir)).map_err(|e| e.to_string())?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))
+        .map_err(|e| e.to_string())?;
+    migrate(&mut conn)?;
+    Ok(conn)
+}
+
+fn migrate(conn: &mut Connection) -> Result<(), String> {
+    let version: usize = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+
+    for (i, step) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+        tx.execute_batch(step)
+            .map_err(|e| format!("Database migration {} failed: {}", i + 1, e))?;
+        tx.pragma_update(None, "user_version", i + 1)
+            .map_err(|e| e.to_string())?;
+        tx.commit().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Current Unix time in seconds
+pub fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_open_migrates_once() {
+        let dir = temp_dir("db");
+
+        let conn = open(&dir).unwrap();
+        let version: usize = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+        drop(conn);
+
+        // Reopening must not re-run the steps
+        open(&dir).unwrap();
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,347 @@
+//! Game Discovery - Live popular sorts and keyword search
+//!
+//! Uses Roblox's explore and omni-search APIs. Results are enriched with
+//! icons in one batch and kept in a TTL cache; when the APIs can't be reached
+//! the bundled list from `game_detection` is served instead.
+
+use crate::game_detection::{
+    batch_get_game_icons, batch_get_game_info, bundled_popular_games, PopularGame,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const EXPLORE_SORTS_API: &str = "https://apis.roblox.com/explore-api/v1/get-sorts";
+const OMNI_SEARCH_API: &str = "https://apis.roblox.com/search-api/omni-search";
+/// Per-request timeout (the old thumbnail fetch hung without one)
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(8);
+/// How long discovery and search results stay fresh
+pub const DISCOVERY_TTL: Duration = Duration::from_secs(5 * 60);
+/// Search queries kept in the cache
+const MAX_CACHED_SEARCHES: usize = 50;
+
+/// A discovery sort ("Top Trending", "Popular", ...)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoverySort {
+    pub id: String,
+    pub title: String,
+    pub games: Vec<PopularGame>,
+}
+
+/// Discovery sorts, and whether they came from the bundled fallback
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Discovery {
+    pub sorts: Vec<DiscoverySort>,
+    pub offline: bool,
+}
+
+/// Game entry as returned by the explore and search APIs
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiGame {
+    universe_id: u64,
+    root_place_id: u64,
+    name: String,
+    #[serde(default)]
+    player_count: u64,
+}
+
+impl From<ApiGame> for PopularGame {
+    fn from(g: ApiGame) -> Self {
+        PopularGame {
+            universe_id: g.universe_id,
+            place_id: g.root_place_id,
+            name: g.name,
+            player_count: g.player_count,
+            thumbnail: None,
+        }
+    }
+}
+
+fn client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+fn parse_sorts(json: &str) -> Result<Vec<DiscoverySort>, String> {
+    #[derive(Deserialize)]
+    struct SortsResponse {
+        sorts: Vec<ApiSort>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ApiSort {
+        sort_id: String,
+        #[serde(default)]
+        sort_display_name: String,
+        // Filter and banner sorts carry no games
+        #[serde(default)]
+        games: Vec<ApiGame>,
+    }
+
+    let data: SortsResponse =
+        serde_json::from_str(json).map_err(|e| format!("Parse error: {}", e))?;
+
+    Ok(data
+        .sorts
+        .into_iter()
+        .filter(|s| !s.games.is_empty())
+        .map(|s| DiscoverySort {
+            title: if s.sort_display_name.is_empty() {
+                s.sort_id.clone()
+            } else {
+                s.sort_display_name
+            },
+            id: s.sort_id,
+            games: s.games.into_iter().map(PopularGame::from).collect(),
+        })
+        .collect())
+}
+
+fn parse_search(json: &str) -> Result<Vec<PopularGame>, String> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct SearchResponse {
+        #[serde(default)]
+        search_results: Vec<SearchGroup>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct SearchGroup {
+        content_group_type: String,
+        #[serde(default)]
+        contents: Vec<serde_json::Value>,
+    }
+
+    let data: SearchResponse =
+        serde_json::from_str(json).map_err(|e| format!("Parse error: {}", e))?;
+
+    // Other groups (players, ads) have a different shape, skip anything that isn't a game
+    Ok(data
+        .search_results
+        .into_iter()
+        .filter(|g| g.content_group_type == "Game")
+        .flat_map(|g| g.contents)
+        .filter_map(|v| serde_json::from_value::<ApiGame>(v).ok())
+        .map(PopularGame::from)
+        .collect())
+}
+
+async fn get_text(client: &reqwest::Client, url: &str) -> Result<String, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("API error: {}", response.status()));
+    }
+
+    response.text().await.map_err(|e| e.to_string())
+}
+
+/// Fill in icons for every game with one batched lookup (best effort)
+async fn attach_icons<'a>(games: impl Iterator<Item = &'a mut PopularGame>) {
+    let mut games: Vec<&mut PopularGame> = games.collect();
+    let mut ids: Vec<u64> = games.iter().map(|g| g.universe_id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    match batch_get_game_icons(ids).await {
+        Ok(icons) => {
+            for game in games.iter_mut() {
+                game.thumbnail = icons.get(&game.universe_id).cloned();
+            }
+        }
+        Err(e) => log::warn!("Game icon lookup failed: {}", e),
+    }
+}
+
+/// Fetch the live discovery sorts with icons
+pub async fn fetch_sorts() -> Result<Vec<DiscoverySort>, String> {
+    let url = format!(
+        "{}?sessionId={}",
+        EXPLORE_SORTS_API,
+        uuid::Uuid::new_v4()
+    );
+    let mut sorts = parse_sorts(&get_text(&client()?, &url).await?)?;
+    if sorts.is_empty() {
+        return Err("No discovery sorts returned".to_string());
+    }
+
+    attach_icons(sorts.iter_mut().flat_map(|s| s.games.iter_mut())).await;
+    Ok(sorts)
+}
+
+/// Search games by keyword, with icons
+pub async fn search_games(query: &str) -> Result<Vec<PopularGame>, String> {
+    let url = format!(
+        "{}?searchQuery={}&sessionId={}&pageType=all",
+        OMNI_SEARCH_API,
+        urlencoding::encode(query),
+        uuid::Uuid::new_v4()
+    );
+    let mut games = parse_search(&get_text(&client()?, &url).await?)?;
+
+    attach_icons(games.iter_mut()).await;
+    Ok(games)
+}
+
+/// The bundled list as a single sort, refreshed with live counts and icons
+/// when the games API still answers
+pub async fn fallback_sorts() -> Vec<DiscoverySort> {
+    let mut games = bundled_popular_games();
+    let ids: Vec<u64> = games.iter().map(|g| g.universe_id).collect();
+
+    if let Ok(live) = batch_get_game_info(&ids).await {
+        let counts: HashMap<u64, u64> = live.iter().map(|g| (g.universe_id, g.playing)).collect();
+        for game in games.iter_mut() {
+            if let Some(count) = counts.get(&game.universe_id) {
+                game.player_count = *count;
+            }
+        }
+        games.sort_by_key(|g| std::cmp::Reverse(g.player_count));
+        attach_icons(games.iter_mut()).await;
+    }
+
+    vec![DiscoverySort {
+        id: "bundled".to_string(),
+        title: "Popular".to_string(),
+        games,
+    }]
+}
+
+/// Live sorts, or the bundled fallback when discovery fails
+pub async fn discover() -> Discovery {
+    match fetch_sorts().await {
+        Ok(sorts) => Discovery {
+            sorts,
+            offline: false,
+        },
+        Err(e) => {
+            log::warn!("Game discovery failed, using bundled list: {}", e);
+            Discovery {
+                sorts: fallback_sorts().await,
+                offline: true,
+            }
+        }
+    }
+}
+
+// ============================================================================
+// CACHE
+// ============================================================================
+
+/// Discovery and search results with their fetch time
+#[derive(Debug, Default)]
+pub struct DiscoveryCache {
+    sorts: Option<(Instant, Discovery)>,
+    searches: HashMap<String, (Instant, Vec<PopularGame>)>,
+}
+
+impl DiscoveryCache {
+    /// Cached sorts if still fresh (fallback results are never cached)
+    pub fn sorts(&self) -> Option<&Discovery> {
+        self.sorts
+            .as_ref()
+            .filter(|(at, _)| at.elapsed() < DISCOVERY_TTL)
+            .map(|(_, d)| d)
+    }
+
+    pub fn insert_sorts(&mut self, discovery: Discovery) {
+        if !discovery.offline {
+            self.sorts = Some((Instant::now(), discovery));
+        }
+    }
+
+    pub fn search(&self, query: &str) -> Option<&Vec<PopularGame>> {
+        self.searches
+            .get(&normalize_query(query))
+            .filter(|(at, _)| at.elapsed() < DISCOVERY_TTL)
+            .map(|(_, games)| games)
+    }
+
+    pub fn insert_search(&mut self, query: &str, games: Vec<PopularGame>) {
+        if self.searches.len() >= MAX_CACHED_SEARCHES {
+            self.searches.retain(|_, (at, _)| at.elapsed() < DISCOVERY_TTL);
+            if self.searches.len() >= MAX_CACHED_SEARCHES {
+                self.searches.clear();
+            }
+        }
+        self.searches
+            .insert(normalize_query(query), (Instant::now(), games));
+    }
+}
+
+fn normalize_query(query: &str) -> String {
+    query.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sorts_skips_empty() {
+        let json = r#"{ "sorts": [
+            { "sortId": "top-trending", "sortDisplayName": "Top Trending", "contentType": "Games",
+              "games": [ { "universeId": 994732206, "rootPlaceId": 2753915549, "name": "Blox Fruits", "playerCount": 412000 } ] },
+            { "sortId": "filters", "contentType": "Filters", "filters": [] }
+        ] }"#;
+
+        let sorts = parse_sorts(json).unwrap();
+        assert_eq!(sorts.len(), 1);
+        assert_eq!(sorts[0].title, "Top Trending");
+        assert_eq!(sorts[0].games[0].place_id, 2753915549);
+        assert_eq!(sorts[0].games[0].player_count, 412000);
+    }
+
+    #[test]
+    fn test_parse_search_keeps_games_only() {
+        let json = r#"{ "searchResults": [
+            { "contentGroupType": "User", "contents": [ { "username": "someone" } ] },
+            { "contentGroupType": "Game", "contents": [
+                { "universeId": 111958650, "rootPlaceId": 286090429, "name": "Arsenal", "playerCount": 9000 },
+                { "universeId": 1, "name": "Broken" }
+            ] }
+        ] }"#;
+
+        let games = parse_search(json).unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].name, "Arsenal");
+    }
+
+    #[test]
+    fn test_bundled_ids_are_unique() {
+        let games = bundled_popular_games();
+        let mut places: Vec<u64> = games.iter().map(|g| g.place_id).collect();
+        let mut universes: Vec<u64> = games.iter().map(|g| g.universe_id).collect();
+        places.sort_unstable();
+        places.dedup();
+        universes.sort_unstable();
+        universes.dedup();
+        assert_eq!(places.len(), games.len());
+        assert_eq!(universes.len(), games.len());
+    }
+
+    #[test]
+    fn test_cache_skips_offline_and_normalizes_queries() {
+        let mut cache = DiscoveryCache::default();
+        cache.insert_sorts(Discovery {
+            sorts: Vec::new(),
+            offline: true,
+        });
+        assert!(cache.sorts().is_none());
+
+        cache.insert_search("  Arsenal ", Vec::new());
+        assert!(cache.search("arsenal").is_some());
+        assert!(cache.search("bedwars").is_none());
+    }
+}
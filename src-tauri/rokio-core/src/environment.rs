@@ -0,0 +1,305 @@
+//! Isolated Environments - Per-account HOME/AppData directories for multi-instance
+
+use crate::binarycookies::{BinaryCookies, Cookie, Page};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Profile directory for an account in the active vault's environment root
+pub fn profile_dir_in(app_data_dir: &Path, account_id: &str) -> PathBuf {
+    crate::vaults::active_dir(app_data_dir)
+        .join("environments")
+        .join(account_id)
+}
+
+/// Create the isolated HOME/AppData structure inside `profile_dir`
+pub fn prepare_home_dir(profile_dir: &Path) -> Result<(), String> {
+    // Create base directories
+    fs::create_dir_all(profile_dir).map_err(|e| e.to_string())?;
+
+    // Platform-specific directory structures
+    #[cfg(target_os = "windows")]
+    {
+        // Windows: Create LocalAppData and AppData structure
+        let local_appdata = profile_dir.join("LocalAppData");
+        let appdata = profile_dir.join("AppData");
+
+        // Roblox LocalAppData structure
+        let roblox_local = local_appdata.join("Roblox");
+        fs::create_dir_all(&roblox_local.join("LocalStorage")).map_err(|e| e.to_string())?;
+        fs::create_dir_all(&roblox_local.join("Versions")).map_err(|e| e.to_string())?;
+        fs::create_dir_all(&roblox_local.join("Downloads")).map_err(|e| e.to_string())?;
+
+        // Roblox AppData structure
+        let roblox_appdata = appdata.join("Local").join("Roblox");
+        fs::create_dir_all(&roblox_appdata).map_err(|e| e.to_string())?;
+
+        // Create Documents for compatibility
+        let documents_dir = profile_dir.join("Documents");
+        fs::create_dir_all(&documents_dir).map_err(|e| e.to_string())?;
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        // macOS/Linux: Create HOME directory structure
+
+        // Documents - required for various executors
+        let documents_dir = profile_dir.join("Documents");
+        fs::create_dir_all(&documents_dir).map_err(|e| e.to_string())?;
+
+        // Downloads - Create symlink to real Downloads folder to bypass sandbox check
+        let downloads_dir = profile_dir.join("Downloads");
+        if !downloads_dir.exists() {
+            // Get the real user Downloads folder
+            if let Some(home) = dirs::home_dir() {
+                let real_downloads = home.join("Downloads");
+                if real_downloads.exists() {
+                    // Create symlink to real Downloads
+                    #[cfg(unix)]
+                    {
+                        std::os::unix::fs::symlink(&real_downloads, &downloads_dir)
+                            .map_err(|e| format!("Failed to create Downloads symlink: {}", e))?;
+                    }
+                } else {
+                    fs::create_dir_all(&downloads_dir).map_err(|e| e.to_string())?;
+                }
+            } else {
+                fs::create_dir_all(&downloads_dir).map_err(|e| e.to_string())?;
+            }
+        }
+
+        // Library for application support
+        let library_dir = profile_dir.join("Library");
+        fs::create_dir_all(&library_dir).map_err(|e| e.to_string())?;
+
+        // Application Support
+        let app_support_dir = library_dir.join("Application Support");
+        fs::create_dir_all(&app_support_dir).map_err(|e| e.to_string())?;
+
+        // Preferences
+        let preferences_dir = library_dir.join("Preferences");
+        fs::create_dir_all(&preferences_dir).map_err(|e| e.to_string())?;
+
+        // Caches
+        let caches_dir = library_dir.join("Caches");
+        fs::create_dir_all(&caches_dir).map_err(|e| e.to_string())?;
+
+        // Keychains directory
+        let keychains_dir = library_dir.join("Keychains");
+        fs::create_dir_all(&keychains_dir).map_err(|e| e.to_string())?;
+
+        // Roblox custom assets path (macOS-specific)
+        #[cfg(target_os = "macos")]
+        {
+            let content_dir = profile_dir
+                .join("Applications")
+                .join("Roblox.app")
+                .join("Contents")
+                .join("Resources")
+                .join("content");
+            fs::create_dir_all(&content_dir).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create the login keychain inside an isolated HOME directory
+#[cfg(target_os = "macos")]
+pub fn create_keychain_at(profile_dir: &Path) -> Result<(), String> {
+    use std::process::Command;
+
+    // Check if keychain already exists
+    let keychain_path = profile_dir
+        .join("Library")
+        .join("Keychains")
+        .join("login.keychain-db");
+    if keychain_path.exists() {
+        return Ok(()); // Already exists, no need to create
+    }
+
+    // Create keychain with empty password
+    let status = Command::new("/usr/bin/security")
+        .arg("create-keychain")
+        .arg("-p")
+        .arg("") // Empty password
+        .arg("login.keychain")
+        .env("HOME", profile_dir)
+        .status()
+        .map_err(|e| format!("Failed to create keychain: {}", e))?;
+
+    if !status.success() {
+        // Keychain might already exist, which is fine
+        return Ok(());
+    }
+
+    Ok(())
+}
+
+/// Unlock the login keychain inside an isolated HOME directory
+#[cfg(target_os = "macos")]
+pub fn unlock_keychain_at(profile_dir: &Path) -> Result<(), String> {
+    use std::process::Command;
+
+    // Unlock keychain with empty password
+    let _ = Command::new("/usr/bin/security")
+        .arg("unlock-keychain")
+        .arg("-p")
+        .arg("") // Empty password
+        .arg("login.keychain")
+        .env("HOME", profile_dir)
+        .status();
+
+    // Also set default keychain for this HOME
+    let _ = Command::new("/usr/bin/security")
+        .arg("default-keychain")
+        .arg("-s")
+        .arg("login.keychain")
+        .env("HOME", profile_dir)
+        .status();
+
+    Ok(())
+}
+
+/// Write the `.ROBLOSECURITY` cookie into every cookie store under `profile_dir`
+pub fn write_session_cookie(profile_dir: &Path, cookie: &str) -> Result<(), String> {
+    // Create the cookie data once
+    let cookie = Cookie {
+        domain: ".roblox.com".into(),
+        name: ".ROBLOSECURITY".into(),
+        path: Some("/".into()),
+        value: cookie.to_string(),
+        secure: Some(true),
+        http_only: Some(true),
+        expiration: Some(SystemTime::now() + Duration::from_secs(60 * 60 * 24 * 30)), // 30 days
+        creation: Some(SystemTime::now()),
+    };
+
+    let page = Page::new(vec![cookie]);
+    let binary_cookies = BinaryCookies::new(vec![page]);
+    let bytes = binary_cookies.build();
+
+    // Write to multiple locations to ensure Roblox finds the cookies
+
+    // 1. HTTPStorages (where Roblox typically looks for cookies)
+    let http_storages_dir = profile_dir.join("Library").join("HTTPStorages");
+    fs::create_dir_all(&http_storages_dir).map_err(|e| e.to_string())?;
+    let cookie_file = http_storages_dir.join("com.roblox.RobloxPlayer.binarycookies");
+    let mut file = File::create(&cookie_file).map_err(|e| e.to_string())?;
+    file.write_all(&bytes).map_err(|e| e.to_string())?;
+
+    // 2. Library/Cookies (standard macOS cookie location)
+    let cookies_dir = profile_dir.join("Library").join("Cookies");
+    fs::create_dir_all(&cookies_dir).map_err(|e| e.to_string())?;
+    let cookie_file2 = cookies_dir.join("Cookies.binarycookies");
+    let mut file2 = File::create(&cookie_file2).map_err(|e| e.to_string())?;
+    file2.write_all(&bytes).map_err(|e| e.to_string())?;
+
+    // 3. Also write with bundle ID suffix for compatibility
+    let cookie_file3 = cookies_dir.join("com.roblox.RobloxPlayer.binarycookies");
+    let mut file3 = File::create(&cookie_file3).map_err(|e| e.to_string())?;
+    file3.write_all(&bytes).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Remove an account's isolated environment (and legacy per-account cookie files)
+pub fn remove_profile(app_data_dir: &Path, account_id: &str) -> Result<(), String> {
+    let profile_dir = profile_dir_in(app_data_dir, account_id);
+
+    // Remove profile directory
+    if profile_dir.exists() {
+        fs::remove_dir_all(&profile_dir).map_err(|e| e.to_string())?;
+    }
+
+    // Also clean up the cookies file
+    if let Some(library_dir) = dirs::data_dir().as_deref().and_then(Path::parent) {
+        let http_storages_dir = library_dir.join("HTTPStorages");
+
+        // Remove binary cookies file
+        let cookie_file = http_storages_dir.join(format!(
+            "com.roblox.RobloxPlayer.{}.binarycookies",
+            account_id
+        ));
+        if cookie_file.exists() {
+            fs::remove_file(&cookie_file).map_err(|e| e.to_string())?;
+        }
+
+        // Remove storage folder
+        let storage_dir = http_storages_dir.join(format!("com.roblox.RobloxPlayer.{}", account_id));
+        if storage_dir.exists() {
+            fs::remove_dir_all(&storage_dir).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_profile_dir_layout() {
+        let root = Path::new("/data/rokio");
+        assert_eq!(
+            profile_dir_in(root, "acc-1"),
+            root.join("environments").join("acc-1")
+        );
+    }
+
+    #[test]
+    fn test_prepare_home_dir_is_idempotent() {
+        let root = temp_dir("env");
+        let home = profile_dir_in(&root, "acc-1");
+
+        prepare_home_dir(&home).unwrap();
+        prepare_home_dir(&home).unwrap();
+
+        assert!(home.join("Documents").is_dir());
+        #[cfg(not(target_os = "windows"))]
+        assert!(home.join("Library").join("Preferences").is_dir());
+        #[cfg(target_os = "windows")]
+        assert!(home.join("LocalAppData").join("Roblox").is_dir());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_write_session_cookie_to_every_store() {
+        let root = temp_dir("env");
+        let home = profile_dir_in(&root, "acc-1");
+
+        write_session_cookie(&home, "SECRET").unwrap();
+
+        let stores = [
+            home.join("Library/HTTPStorages/com.roblox.RobloxPlayer.binarycookies"),
+            home.join("Library/Cookies/Cookies.binarycookies"),
+            home.join("Library/Cookies/com.roblox.RobloxPlayer.binarycookies"),
+        ];
+        for store in &stores {
+            let bytes = fs::read(store).unwrap();
+            assert!(bytes.starts_with(b"cook"), "{}", store.display());
+            assert!(bytes.windows(6).any(|w| w == b"SECRET"));
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_remove_profile() {
+        let root = temp_dir("env");
+        let home = profile_dir_in(&root, "acc-1");
+        prepare_home_dir(&home).unwrap();
+
+        remove_profile(&root, "acc-1").unwrap();
+        assert!(!home.exists());
+
+        // Removing a missing environment is not an error
+        remove_profile(&root, "acc-1").unwrap();
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
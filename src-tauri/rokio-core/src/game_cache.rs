@@ -0,0 +1,363 @@
+//! Game Cache - SQLite-backed place, universe, details and icon cache
+//!
+//! Place → universe mappings never change and are kept forever. Details and
+//! icons expire after a TTL but stale rows are still served when Roblox
+//! can't be reached, so known games keep working offline.
+
+use crate::db;
+use crate::game_detection::{self, GameInfo};
+use base64::Engine;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Seconds before cached details (player counts, visits) are refetched
+pub const DETAILS_TTL_SECS: i64 = 10 * 60;
+/// Seconds before cached icon URLs are refetched
+pub const ICON_TTL_SECS: i64 = 24 * 60 * 60;
+
+// ============================================================================
+// STORAGE
+// ============================================================================
+
+fn lookup_universe(conn: &Connection, place_id: u64) -> Result<Option<u64>, String> {
+    conn.query_row(
+        "SELECT universe_id FROM place_universe WHERE place_id = ?1",
+        params![place_id as i64],
+        |row| row.get::<_, i64>(0),
+    )
+    .optional()
+    .map(|id| id.map(|id| id as u64))
+    .map_err(|e| e.to_string())
+}
+
+fn store_universe(conn: &Connection, place_id: u64, universe_id: u64) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO place_universe (place_id, universe_id) VALUES (?1, ?2)",
+        params![place_id as i64, universe_id as i64],
+    )
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Cached details with their age check (`fresh` is false once past the TTL)
+fn lookup_details(conn: &Connection, universe_id: u64) -> Result<Option<(GameInfo, bool)>, String> {
+    let row: Option<(String, i64)> = conn
+        .query_row(
+            "SELECT details, fetched_at FROM game_details WHERE universe_id = ?1",
+            params![universe_id as i64],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    Ok(row.and_then(|(json, fetched_at)| {
+        let info: GameInfo = serde_json::from_str(&json).ok()?;
+        Some((info, db::now() - fetched_at < DETAILS_TTL_SECS))
+    }))
+}
+
+fn store_details(conn: &Connection, info: &GameInfo) -> Result<(), String> {
+    let json = serde_json::to_string(info).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT OR REPLACE INTO game_details (universe_id, details, fetched_at) VALUES (?1, ?2, ?3)",
+        params![info.universe_id as i64, json, db::now()],
+    )
+    .map_err(|e| e.to_string())?;
+    store_universe(conn, info.place_id, info.universe_id)
+}
+
+/// Icon URLs by universe ID
+type IconMap = HashMap<u64, String>;
+
+/// Cached icon URLs split into fresh and stale
+fn lookup_icons(conn: &Connection, universe_ids: &[u64]) -> Result<(IconMap, IconMap), String> {
+    let mut stmt = conn
+        .prepare("SELECT url, fetched_at FROM game_icons WHERE universe_id = ?1")
+        .map_err(|e| e.to_string())?;
+
+    let mut fresh = HashMap::new();
+    let mut stale = HashMap::new();
+    for &id in universe_ids {
+        let row: Option<(String, i64)> = stmt
+            .query_row(params![id as i64], |row| Ok((row.get(0)?, row.get(1)?)))
+            .optional()
+            .map_err(|e| e.to_string())?;
+        if let Some((url, fetched_at)) = row {
+            if db::now() - fetched_at < ICON_TTL_SECS {
+                fresh.insert(id, url);
+            } else {
+                stale.insert(id, url);
+            }
+        }
+    }
+    Ok((fresh, stale))
+}
+
+fn store_icons(conn: &mut Connection, icons: &IconMap) -> Result<(), String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    {
+        // Keep downloaded bytes only while the URL is unchanged
+        let mut stmt = tx
+            .prepare(
+                "INSERT INTO game_icons (universe_id, url, bytes, fetched_at) VALUES (?1, ?2, NULL, ?3)
+                 ON CONFLICT(universe_id) DO UPDATE SET
+                    bytes = CASE WHEN url = excluded.url THEN bytes ELSE NULL END,
+                    url = excluded.url,
+                    fetched_at = excluded.fetched_at",
+            )
+            .map_err(|e| e.to_string())?;
+        for (id, url) in icons {
+            stmt.execute(params![*id as i64, url, db::now()])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// CACHED LOOKUPS
+// ============================================================================
+
+/// Universe ID for a place (cached permanently)
+pub async fn get_universe_id(app_data_dir: &Path, place_id: u64) -> Result<u64, String> {
+    if let Some(id) = lookup_universe(&db::open(app_data_dir)?, place_id)? {
+        return Ok(id);
+    }
+
+    let universe_id = game_detection::get_universe_id(place_id).await?;
+    store_universe(&db::open(app_data_dir)?, place_id, universe_id)?;
+    Ok(universe_id)
+}
+
+/// Game details, refetched after `DETAILS_TTL_SECS` (stale copy if offline)
+pub async fn get_game_info(app_data_dir: &Path, universe_id: u64) -> Result<GameInfo, String> {
+    let cached = lookup_details(&db::open(app_data_dir)?, universe_id)?;
+    if let Some((info, true)) = &cached {
+        return Ok(info.clone());
+    }
+
+    match game_detection::get_game_info(universe_id).await {
+        Ok(info) => {
+            store_details(&db::open(app_data_dir)?, &info)?;
+            Ok(info)
+        }
+        Err(e) => cached.map(|(info, _)| info).ok_or(e),
+    }
+}
+
+/// Details for many universes; only missing or expired ones are fetched
+pub async fn batch_get_game_info(
+    app_data_dir: &Path,
+    universe_ids: &[u64],
+) -> Result<Vec<GameInfo>, String> {
+    let mut found = HashMap::new();
+    let mut stale = HashMap::new();
+    {
+        let conn = db::open(app_data_dir)?;
+        for &id in universe_ids {
+            match lookup_details(&conn, id)? {
+                Some((info, true)) => {
+                    found.insert(id, info);
+                }
+                Some((info, false)) => {
+                    stale.insert(id, info);
+                }
+                None => {}
+            }
+        }
+    }
+
+    let misses: Vec<u64> = universe_ids
+        .iter()
+        .copied()
+        .filter(|id| !found.contains_key(id))
+        .collect();
+
+    if !misses.is_empty() {
+        match game_detection::batch_get_game_info(&misses).await {
+            Ok(fetched) => {
+                let conn = db::open(app_data_dir)?;
+                for info in fetched {
+                    store_details(&conn, &info)?;
+                    found.insert(info.universe_id, info);
+                }
+            }
+            Err(e) if found.is_empty() && stale.is_empty() => return Err(e),
+            Err(e) => log::warn!("Game details fetch failed, using cache: {}", e),
+        }
+    }
+
+    // Stale rows fill whatever the network didn't answer
+    for (id, info) in stale {
+        found.entry(id).or_insert(info);
+    }
+
+    Ok(universe_ids
+        .iter()
+        .filter_map(|id| found.remove(id))
+        .collect())
+}
+
+/// Icon URLs for many universes; only missing or expired ones are fetched
+pub async fn batch_get_game_icons(
+    app_data_dir: &Path,
+    universe_ids: Vec<u64>,
+) -> Result<HashMap<u64, String>, String> {
+    let (mut icons, stale) = lookup_icons(&db::open(app_data_dir)?, &universe_ids)?;
+
+    let misses: Vec<u64> = universe_ids
+        .into_iter()
+        .filter(|id| !icons.contains_key(id))
+        .collect();
+
+    if !misses.is_empty() {
+        match game_detection::batch_get_game_icons(misses).await {
+            Ok(fetched) => {
+                store_icons(&mut db::open(app_data_dir)?, &fetched)?;
+                icons.extend(fetched);
+            }
+            Err(e) if icons.is_empty() && stale.is_empty() => return Err(e),
+            Err(e) => log::warn!("Game icon fetch failed, using cache: {}", e),
+        }
+    }
+
+    for (id, url) in stale {
+        icons.entry(id).or_insert(url);
+    }
+    Ok(icons)
+}
+
+/// Icon as a `data:` URL so it renders without network access.
+/// The image is downloaded once and stored next to its URL.
+pub async fn get_icon_data_url(app_data_dir: &Path, universe_id: u64) -> Result<String, String> {
+    let cached: Option<(String, Option<Vec<u8>>)> = db::open(app_data_dir)?
+        .query_row(
+            "SELECT url, bytes FROM game_icons WHERE universe_id = ?1",
+            params![universe_id as i64],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    let bytes = match cached {
+        Some((_, Some(bytes))) => bytes,
+        _ => {
+            let url = match cached {
+                Some((url, None)) => url,
+                _ => batch_get_game_icons(app_data_dir, vec![universe_id])
+                    .await?
+                    .remove(&universe_id)
+                    .ok_or("No icon for this game")?,
+            };
+
+            let bytes = reqwest::get(&url)
+                .await
+                .map_err(|e| format!("Failed to download icon: {}", e))?
+                .bytes()
+                .await
+                .map_err(|e| e.to_string())?
+                .to_vec();
+
+            db::open(app_data_dir)?
+                .execute(
+                    "UPDATE game_icons SET bytes = ?2 WHERE universe_id = ?1 AND url = ?3",
+                    params![universe_id as i64, bytes, url],
+                )
+                .map_err(|e| e.to_string())?;
+            bytes
+        }
+    };
+
+    Ok(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
+/// Drop cached details and icons (place → universe mappings are kept)
+pub fn clear(app_data_dir: &Path) -> Result<(), String> {
+    db::open(app_data_dir)?
+        .execute_batch("DELETE FROM game_details; DELETE FROM game_icons;")
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn info(universe_id: u64, place_id: u64) -> GameInfo {
+        GameInfo {
+            universe_id,
+            place_id,
+            name: "Arsenal".to_string(),
+            description: String::new(),
+            creator_name: "ROLVe Community".to_string(),
+            playing: 10,
+            visits: 100,
+            thumbnail: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_rows_answer_without_network() {
+        let dir = temp_dir("game-cache");
+        {
+            let conn = db::open(&dir).unwrap();
+            store_details(&conn, &info(111958650, 286090429)).unwrap();
+        }
+
+        // Served from the cache, including the mapping stored with the details
+        assert_eq!(get_universe_id(&dir, 286090429).await.unwrap(), 111958650);
+        let cached = batch_get_game_info(&dir, &[111958650]).await.unwrap();
+        assert_eq!(cached[0].name, "Arsenal");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_expired_details_are_stale() {
+        let dir = temp_dir("game-cache");
+        let conn = db::open(&dir).unwrap();
+        store_details(&conn, &info(1, 2)).unwrap();
+        assert!(lookup_details(&conn, 1).unwrap().unwrap().1);
+
+        conn.execute(
+            "UPDATE game_details SET fetched_at = fetched_at - ?1",
+            params![DETAILS_TTL_SECS + 1],
+        )
+        .unwrap();
+        assert!(!lookup_details(&conn, 1).unwrap().unwrap().1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_icon_bytes_dropped_when_url_changes() {
+        let dir = temp_dir("game-cache");
+        let mut conn = db::open(&dir).unwrap();
+        let icons = HashMap::from([(1u64, "https://a".to_string())]);
+        store_icons(&mut conn, &icons).unwrap();
+        conn.execute("UPDATE game_icons SET bytes = x'00'", []).unwrap();
+
+        // Same URL keeps the bytes, a new URL clears them
+        store_icons(&mut conn, &icons).unwrap();
+        let bytes: Option<Vec<u8>> = conn
+            .query_row("SELECT bytes FROM game_icons", [], |r| r.get(0))
+            .unwrap();
+        assert!(bytes.is_some());
+
+        store_icons(&mut conn, &HashMap::from([(1u64, "https://b".to_string())])).unwrap();
+        let bytes: Option<Vec<u8>> = conn
+            .query_row("SELECT bytes FROM game_icons", [], |r| r.get(0))
+            .unwrap();
+        assert!(bytes.is_none());
+
+        let (fresh, stale) = lookup_icons(&conn, &[1, 2]).unwrap();
+        assert_eq!(fresh.get(&1).map(String::as_str), Some("https://b"));
+        assert!(stale.is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
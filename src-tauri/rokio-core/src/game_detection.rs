@@ -0,0 +1,423 @@
+//! Game Detection - Get game info from Roblox API
+
+use crate::regions::ServerRegion;
+use serde::{Deserialize, Serialize};
+
+/// Game info returned to frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameInfo {
+    pub universe_id: u64,
+    pub place_id: u64,
+    pub name: String,
+    pub description: String,
+    pub creator_name: String,
+    pub playing: u64,
+    pub visits: u64,
+    pub thumbnail: Option<String>,
+}
+
+/// Roblox API response for game details
+#[derive(Debug, Deserialize)]
+struct GameDetailsResponse {
+    data: Vec<GameDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GameDetail {
+    root_place_id: u64,
+    name: String,
+    description: Option<String>,
+    creator: GameCreator,
+    playing: u64,
+    visits: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GameCreator {
+    name: String,
+}
+
+/// Get universe ID from place ID
+pub async fn get_universe_id(place_id: u64) -> Result<u64, String> {
+    let url = format!(
+        "https://apis.roblox.com/universes/v1/places/{}/universe",
+        place_id
+    );
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch universe: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("API error: {}", response.status()));
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct UniverseResponse {
+        universe_id: u64,
+    }
+
+    let data: UniverseResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Parse error: {}", e))?;
+
+    Ok(data.universe_id)
+}
+
+/// Get game info from universe ID
+pub async fn get_game_info(universe_id: u64) -> Result<GameInfo, String> {
+    let url = format!(
+        "https://games.roblox.com/v1/games?universeIds={}",
+        universe_id
+    );
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch game: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("API error: {}", response.status()));
+    }
+
+    let data: GameDetailsResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Parse error: {}", e))?;
+
+    let game = data
+        .data
+        .into_iter()
+        .next()
+        .ok_or("Game not found")?;
+
+    // Get thumbnail
+    let thumbnail = get_game_thumbnail(universe_id).await.ok();
+
+    Ok(GameInfo {
+        universe_id,
+        place_id: game.root_place_id,
+        name: game.name,
+        description: game.description.unwrap_or_default(),
+        creator_name: game.creator.name,
+        playing: game.playing,
+        visits: game.visits,
+        thumbnail,
+    })
+}
+
+/// Get game thumbnail URL
+async fn get_game_thumbnail(universe_id: u64) -> Result<String, String> {
+    let url = format!(
+        "https://thumbnails.roblox.com/v1/games/icons?universeIds={}&size=150x150&format=Png",
+        universe_id
+    );
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    #[derive(Deserialize)]
+    struct ThumbnailResponse {
+        data: Vec<ThumbnailData>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ThumbnailData {
+        image_url: Option<String>,
+    }
+
+    let data: ThumbnailResponse = response.json().await.map_err(|e| e.to_string())?;
+
+    data.data
+        .into_iter()
+        .next()
+        .and_then(|d| d.image_url)
+        .ok_or("No thumbnail".to_string())
+}
+
+/// Universe IDs per games/thumbnails batch request
+const GAME_BATCH_SIZE: usize = 100;
+
+/// Get game icons for multiple universe IDs in batch
+pub async fn batch_get_game_icons(universe_ids: Vec<u64>) -> Result<std::collections::HashMap<u64, String>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    #[derive(serde::Deserialize)]
+    struct ThumbnailResponse {
+        data: Vec<ThumbnailData>,
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ThumbnailData {
+        target_id: u64,
+        image_url: Option<String>,
+    }
+
+    let mut result = std::collections::HashMap::new();
+    for chunk in universe_ids.chunks(GAME_BATCH_SIZE) {
+        let ids_str = chunk.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",");
+        let url = format!(
+            "https://thumbnails.roblox.com/v1/games/icons?universeIds={}&size=420x420&format=Png",
+            ids_str
+        );
+
+        let response = client.get(&url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let data: ThumbnailResponse = response.json().await.map_err(|e| e.to_string())?;
+
+        for item in data.data {
+            if let Some(url) = item.image_url {
+                result.insert(item.target_id, url);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Get live details for multiple universe IDs in batch (no thumbnails)
+pub async fn batch_get_game_info(universe_ids: &[u64]) -> Result<Vec<GameInfo>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct BatchGameDetail {
+        id: u64,
+        #[serde(flatten)]
+        detail: GameDetail,
+    }
+
+    #[derive(Deserialize)]
+    struct BatchResponse {
+        data: Vec<BatchGameDetail>,
+    }
+
+    let mut games = Vec::with_capacity(universe_ids.len());
+    for chunk in universe_ids.chunks(GAME_BATCH_SIZE) {
+        let ids = chunk.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",");
+        let url = format!("https://games.roblox.com/v1/games?universeIds={}", ids);
+
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch games: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("API error: {}", response.status()));
+        }
+
+        let data: BatchResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Parse error: {}", e))?;
+
+        games.extend(data.data.into_iter().map(|g| GameInfo {
+            universe_id: g.id,
+            place_id: g.detail.root_place_id,
+            name: g.detail.name,
+            description: g.detail.description.unwrap_or_default(),
+            creator_name: g.detail.creator.name,
+            playing: g.detail.playing,
+            visits: g.detail.visits,
+            thumbnail: None,
+        }));
+    }
+
+    Ok(games)
+}
+
+/// Server info returned from Roblox API
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerInfo {
+    pub id: String,
+    pub playing: u32,
+    pub max_players: u32,
+    pub ping: Option<u32>,
+    pub fps: Option<f32>,
+    /// Datacenter, once resolved (see `regions`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<ServerRegion>,
+    /// Opaque per-player tokens (resolve to headshots, see `server_details`)
+    #[serde(skip)]
+    pub player_tokens: Vec<String>,
+}
+
+/// Sort order for the public server list (by player count)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn as_str(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "Asc",
+            SortOrder::Desc => "Desc",
+        }
+    }
+}
+
+/// One page of public servers
+#[derive(Debug, Clone)]
+pub struct ServerPage {
+    pub servers: Vec<ServerInfo>,
+    pub next_page_cursor: Option<String>,
+}
+
+/// Attempts per page before giving up on HTTP 429
+const SERVER_PAGE_ATTEMPTS: u32 = 4;
+
+/// Fetch one page of public servers, waiting out rate limits (HTTP 429)
+pub async fn fetch_server_page(
+    client: &reqwest::Client,
+    place_id: u64,
+    order: SortOrder,
+    cursor: Option<&str>,
+) -> Result<ServerPage, String> {
+    let mut url = format!(
+        "https://games.roblox.com/v1/games/{}/servers/Public?sortOrder={}&excludeFullGames=false&limit=100",
+        place_id,
+        order.as_str()
+    );
+    if let Some(c) = cursor {
+        url.push_str("&cursor=");
+        url.push_str(&urlencoding::encode(c));
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ServersResponse {
+        data: Vec<ServerData>,
+        next_page_cursor: Option<String>,
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ServerData {
+        id: String,
+        playing: u32,
+        max_players: u32,
+        ping: Option<u32>,
+        fps: Option<f32>,
+        #[serde(default)]
+        player_tokens: Vec<String>,
+    }
+
+    let mut attempt = 1;
+    let response = loop {
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch servers: {}", e))?;
+
+        if response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS
+            || attempt == SERVER_PAGE_ATTEMPTS
+        {
+            break response;
+        }
+
+        // Honour Retry-After, otherwise back off a little more each time
+        let wait = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(2 * attempt as u64);
+        tokio::time::sleep(std::time::Duration::from_secs(wait)).await;
+        attempt += 1;
+    };
+
+    if !response.status().is_success() {
+        return Err(format!("API error: {}", response.status()));
+    }
+
+    let data: ServersResponse = response.json().await.map_err(|e| format!("Parse error: {}", e))?;
+
+    let servers = data.data.into_iter().map(|s| ServerInfo {
+        id: s.id,
+        playing: s.playing,
+        max_players: s.max_players,
+        ping: s.ping,
+        fps: s.fps,
+        region: None,
+        player_tokens: s.player_tokens,
+    }).collect();
+
+    Ok(ServerPage {
+        servers,
+        next_page_cursor: data.next_page_cursor,
+    })
+}
+
+/// Get public servers for a game
+pub async fn get_game_servers(place_id: u64, cursor: Option<String>) -> Result<(Vec<ServerInfo>, Option<String>), String> {
+    let client = reqwest::Client::new();
+    let page = fetch_server_page(&client, place_id, SortOrder::Asc, cursor.as_deref()).await?;
+    Ok((page.servers, page.next_page_cursor))
+}
+
+/// Popular game info
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PopularGame {
+    pub universe_id: u64,
+    pub place_id: u64,
+    pub name: String,
+    pub player_count: u64,
+    pub thumbnail: Option<String>,
+}
+
+/// Fallback list for when the discovery APIs can't be reached.
+/// (universe ID, root place ID, name) - player counts come from `batch_get_game_info`.
+const BUNDLED_POPULAR_GAMES: &[(u64, u64, &str)] = &[
+    (1686885941, 4924922222, "Brookhaven 🏡RP"),
+    (383310974, 920587237, "Adopt Me!"),
+    (994732206, 2753915549, "Blox Fruits"),
+    (5203828273, 15101393044, "Dress To Impress"),
+    (66654135, 142823291, "Murder Mystery 2"),
+    (3317771874, 8737899170, "Pet Simulator 99"),
+    (4777817887, 13772394625, "Blade Ball"),
+    (2619619496, 6872265039, "BedWars"),
+    (2440500124, 6516141723, "DOORS 👁️"),
+    (88070565, 185655149, "Bloxburg"),
+    (245683, 606849621, "Jailbreak"),
+    (703124385, 1962086868, "Tower of Hell"),
+    (111958650, 286090429, "Arsenal"),
+    (228181322, 735030788, "Royale High"),
+    (210851291, 537413528, "Build A Boat For Treasure"),
+    (65241, 189707, "Natural Disaster Survival"),
+    (47545, 192800, "Work at a Pizza Place"),
+];
+
+/// Bundled popular games (offline fallback, no player counts or icons)
+pub fn bundled_popular_games() -> Vec<PopularGame> {
+    BUNDLED_POPULAR_GAMES
+        .iter()
+        .map(|&(universe_id, place_id, name)| PopularGame {
+            universe_id,
+            place_id,
+            name: name.to_string(),
+            player_count: 0,
+            thumbnail: None,
+        })
+        .collect()
+}
@@ -0,0 +1,526 @@
+//! Launch History - Every launch and how it ended, plus playtime queries
+//!
+//! A row is written when a launch is finalized and closed when the client is
+//! killed through ROKIO or exits. The server the client joined is filled in
+//! from the account's presence once it is in game. Rows that are still open
+//! are the running instances shared by the app and the CLI (see `running`). Clients ROKIO spawned itself report an exit
+//! code, which decides between crashed and closed. Clients started through a
+//! hand-off (protocol handler, `open`, bootstrappers) don't; for those a client
+//! that exits within `CRASH_WINDOW_SECS` of starting is guessed to have crashed.
+
+use crate::db;
+use crate::launcher::{ActiveInstance, LaunchOptions};
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Exits without an exit code sooner than this after launch are guessed to be crashes
+pub const CRASH_WINDOW_SECS: i64 = 30;
+
+/// How a launched client ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExitReason {
+    /// Terminated by ROKIO (kill, server hop)
+    Killed,
+    /// Exited with an error, or right after launching
+    Crashed,
+    /// Closed normally
+    Closed,
+    /// Still running when ROKIO last quit; the end time is unknown
+    Unknown,
+}
+
+impl ExitReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            ExitReason::Killed => "killed",
+            ExitReason::Crashed => "crashed",
+            ExitReason::Closed => "closed",
+            ExitReason::Unknown => "unknown",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "killed" => ExitReason::Killed,
+            "crashed" => ExitReason::Crashed,
+            "closed" => ExitReason::Closed,
+            _ => ExitReason::Unknown,
+        }
+    }
+
+    /// Reason for a client that exited on its own, from its exit code when
+    /// known and otherwise from how long it ran
+    pub fn for_exit(started_at: i64, ended_at: i64, exit_code: Option<i32>) -> Self {
+        match exit_code {
+            Some(0) => ExitReason::Closed,
+            Some(_) => ExitReason::Crashed,
+            None if ended_at - started_at < CRASH_WINDOW_SECS => ExitReason::Crashed,
+            None => ExitReason::Closed,
+        }
+    }
+}
+
+/// One launch
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchRecord {
+    pub id: i64,
+    pub account_id: String,
+    pub username: String,
+    pub place_id: u64,
+    /// Server the launch asked for
+    pub job_id: Option<String>,
+    /// Server the client joined, from its presence
+    pub game_id: Option<String>,
+    pub target_type: String,
+    pub pid: u32,
+    pub started_at: i64,
+    pub ended_at: Option<i64>,
+    pub exit_reason: Option<ExitReason>,
+    /// Exit code of the client, when ROKIO spawned it and waited on it
+    pub exit_code: Option<i32>,
+    /// `exit_reason` is a guess from the run time (`CRASH_WINDOW_SECS`),
+    /// not from an exit code
+    pub exit_reason_guessed: bool,
+}
+
+impl LaunchRecord {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let exit_reason = row
+            .get::<_, Option<String>>(9)?
+            .map(|s| ExitReason::parse(&s));
+        let exit_code: Option<i32> = row.get(10)?;
+
+        Ok(LaunchRecord {
+            id: row.get(0)?,
+            account_id: row.get(1)?,
+            username: row.get(2)?,
+            place_id: row.get::<_, i64>(3)? as u64,
+            job_id: row.get(4)?,
+            game_id: row.get(11)?,
+            target_type: row.get(5)?,
+            pid: row.get(6)?,
+            started_at: row.get(7)?,
+            ended_at: row.get(8)?,
+            exit_reason,
+            exit_code,
+            exit_reason_guessed: exit_code.is_none()
+                && matches!(exit_reason, Some(ExitReason::Crashed | ExitReason::Closed)),
+        })
+    }
+}
+
+/// Total time an account spent in a game
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Playtime {
+    pub account_id: String,
+    pub username: String,
+    pub place_id: u64,
+    pub sessions: u32,
+    pub total_secs: i64,
+    pub last_played_at: i64,
+}
+
+/// A server an account was sent to, for rejoining
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentServer {
+    pub place_id: u64,
+    pub job_id: String,
+    pub account_id: String,
+    pub username: String,
+    pub joined_at: i64,
+}
+
+const RECORD_COLUMNS: &str = "id, account_id, username, place_id, job_id, target_type, pid, \
+     started_at, ended_at, exit_reason, exit_code, game_id";
+
+// ============================================================================
+// WRITES
+// ============================================================================
+
+/// Record a launch, returning its history ID
+pub fn record_launch(
+    app_data_dir: &Path,
+    instance: &ActiveInstance,
+    target_type: &str,
+) -> Result<i64, String> {
+    let options = serde_json::to_string(&instance.options).map_err(|e| e.to_string())?;
+    let conn = db::open(app_data_dir)?;
+    conn.execute(
+        "INSERT INTO launch_history
+             (account_id, username, place_id, job_id, target_type, pid, started_at, vault_id, options)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            instance.account_id,
+            instance.username,
+            instance.place_id as i64,
+            instance.job_id,
+            target_type,
+            instance.pid,
+            instance.started_at as i64,
+            instance.vault_id,
+            options,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn close(
+    conn: &Connection,
+    id: i64,
+    ended_at: Option<i64>,
+    reason: ExitReason,
+    exit_code: Option<i32>,
+) -> Result<(), String> {
+    conn.execute(
+        "UPDATE launch_history SET ended_at = ?2, exit_reason = ?3, exit_code = ?4
+         WHERE id = ?1 AND exit_reason IS NULL",
+        params![id, ended_at, reason.as_str(), exit_code],
+    )
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
+
+/// Record the server a launched client joined (`gameId` of its presence)
+pub fn record_joined(app_data_dir: &Path, launch_id: i64, game_id: &str) -> Result<(), String> {
+    db::open(app_data_dir)?
+        .execute(
+            "UPDATE launch_history SET game_id = ?2 WHERE id = ?1",
+            params![launch_id, game_id],
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Record that ROKIO terminated an instance
+pub fn record_killed(app_data_dir: &Path, instance: &ActiveInstance) -> Result<(), String> {
+    match instance.launch_id {
+        Some(id) => close(&db::open(app_data_dir)?, id, Some(db::now()), ExitReason::Killed, None),
+        None => Ok(()),
+    }
+}
+
+/// Record that an instance exited on its own just now. `exit_code` is the
+/// client's status when it was spawned and waited on by ROKIO.
+pub fn record_exited(
+    app_data_dir: &Path,
+    instance: &ActiveInstance,
+    exit_code: Option<i32>,
+) -> Result<(), String> {
+    let Some(id) = instance.launch_id else {
+        return Ok(());
+    };
+    let now = db::now();
+    let reason = ExitReason::for_exit(instance.started_at as i64, now, exit_code);
+    close(&db::open(app_data_dir)?, id, Some(now), reason, exit_code)
+}
+
+/// Record that an instance was found gone without anyone watching it exit,
+/// so neither the end time nor the reason is known
+pub fn record_lost(app_data_dir: &Path, instance: &ActiveInstance) -> Result<(), String> {
+    match instance.launch_id {
+        Some(id) => close(&db::open(app_data_dir)?, id, None, ExitReason::Unknown, None),
+        None => Ok(()),
+    }
+}
+
+/// Close launches left open by a previous run whose client is gone.
+/// Returns how many rows were closed.
+pub fn close_orphaned(app_data_dir: &Path, is_running: impl Fn(u32) -> bool) -> Result<usize, String> {
+    let conn = db::open(app_data_dir)?;
+    let open: Vec<(i64, u32)> = conn
+        .prepare("SELECT id, pid FROM launch_history WHERE exit_reason IS NULL")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect()
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut closed = 0;
+    for (id, pid) in open {
+        if !is_running(pid) {
+            close(&conn, id, None, ExitReason::Unknown, None)?;
+            closed += 1;
+        }
+    }
+    Ok(closed)
+}
+
+// ============================================================================
+// QUERIES
+// ============================================================================
+
+/// Launches that haven't ended yet, oldest first. Callers drop the ones whose
+/// client is gone with `close_orphaned` first.
+pub fn running(app_data_dir: &Path) -> Result<Vec<ActiveInstance>, String> {
+    let conn = db::open(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, pid, account_id, username, place_id, job_id, started_at, vault_id, options
+             FROM launch_history
+             WHERE exit_reason IS NULL
+             ORDER BY started_at, id",
+        )
+        .map_err(|e| e.to_string())?;
+    let instances = stmt
+        .query_map([], |row| {
+            let options: Option<String> = row.get(8)?;
+            Ok(ActiveInstance {
+                launch_id: Some(row.get(0)?),
+                pid: row.get(1)?,
+                account_id: row.get(2)?,
+                username: row.get(3)?,
+                place_id: row.get::<_, i64>(4)? as u64,
+                job_id: row.get(5)?,
+                started_at: row.get::<_, i64>(6)? as u64,
+                vault_id: row.get::<_, Option<String>>(7)?.unwrap_or_default(),
+                options: options
+                    .and_then(|o| serde_json::from_str::<LaunchOptions>(&o).ok())
+                    .unwrap_or_default(),
+            })
+        })
+        .and_then(|rows| rows.collect())
+        .map_err(|e| e.to_string())?;
+    Ok(instances)
+}
+
+/// Most recent launches first
+pub fn list_launches(app_data_dir: &Path, limit: u32) -> Result<Vec<LaunchRecord>, String> {
+    let conn = db::open(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM launch_history ORDER BY started_at DESC, id DESC LIMIT ?1",
+            RECORD_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let records = stmt
+        .query_map(params![limit], LaunchRecord::from_row)
+        .and_then(|rows| rows.collect())
+        .map_err(|e| e.to_string())?;
+    Ok(records)
+}
+
+/// Playtime per account and game, optionally for one account or one place.
+/// Only launches with a known end time count.
+pub fn playtime(
+    app_data_dir: &Path,
+    account_id: Option<&str>,
+    place_id: Option<u64>,
+) -> Result<Vec<Playtime>, String> {
+    let conn = db::open(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT account_id, MAX(username), place_id, COUNT(*),
+                    SUM(ended_at - started_at), MAX(started_at)
+             FROM launch_history
+             WHERE ended_at IS NOT NULL AND place_id > 0
+               AND (?1 IS NULL OR account_id = ?1)
+               AND (?2 IS NULL OR place_id = ?2)
+             GROUP BY account_id, place_id
+             ORDER BY SUM(ended_at - started_at) DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params![account_id, place_id.map(|id| id as i64)], |row| {
+            Ok(Playtime {
+                account_id: row.get(0)?,
+                username: row.get(1)?,
+                place_id: row.get::<_, i64>(2)? as u64,
+                sessions: row.get(3)?,
+                total_secs: row.get(4)?,
+                last_played_at: row.get(5)?,
+            })
+        })
+        .and_then(|rows| rows.collect())
+        .map_err(|e| e.to_string())?;
+    Ok(rows)
+}
+
+/// Servers joined, most recent first (one entry per server). Launches
+/// whose joined server was never seen fall back to the one they asked for.
+pub fn recent_servers(app_data_dir: &Path, limit: u32) -> Result<Vec<RecentServer>, String> {
+    let conn = db::open(app_data_dir)?;
+    // SQLite takes the bare columns from the row holding MAX(started_at)
+    let mut stmt = conn
+        .prepare(
+            "SELECT place_id, COALESCE(game_id, job_id) AS server, account_id, username,
+                    MAX(started_at)
+             FROM launch_history
+             WHERE server IS NOT NULL
+             GROUP BY place_id, server
+             ORDER BY MAX(started_at) DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params![limit], |row| {
+            Ok(RecentServer {
+                place_id: row.get::<_, i64>(0)? as u64,
+                job_id: row.get(1)?,
+                account_id: row.get(2)?,
+                username: row.get(3)?,
+                joined_at: row.get(4)?,
+            })
+        })
+        .and_then(|rows| rows.collect())
+        .map_err(|e| e.to_string())?;
+    Ok(rows)
+}
+
+/// Servers an account was sent to or joined in a place (for server hopping)
+pub fn visited_servers(
+    app_data_dir: &Path,
+    account_id: &str,
+    place_id: u64,
+) -> Result<HashSet<String>, String> {
+    let conn = db::open(app_data_dir)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT job_id FROM launch_history
+             WHERE account_id = ?1 AND place_id = ?2 AND job_id IS NOT NULL
+             UNION
+             SELECT game_id FROM launch_history
+             WHERE account_id = ?1 AND place_id = ?2 AND game_id IS NOT NULL",
+        )
+        .map_err(|e| e.to_string())?;
+    let servers = stmt
+        .query_map(params![account_id, place_id as i64], |row| row.get(0))
+        .and_then(|rows| rows.collect())
+        .map_err(|e| e.to_string())?;
+    Ok(servers)
+}
+
+/// Delete all launch history
+pub fn clear(app_data_dir: &Path) -> Result<(), String> {
+    db::open(app_data_dir)?
+        .execute("DELETE FROM launch_history", [])
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn instance(pid: u32, account: &str, place_id: u64, job_id: Option<&str>, started_at: u64) -> ActiveInstance {
+        ActiveInstance {
+            pid,
+            account_id: account.to_string(),
+            username: format!("{}-name", account),
+            place_id,
+            job_id: job_id.map(str::to_string),
+            started_at,
+            launch_id: None,
+            options: Default::default(),
+            vault_id: String::new(),
+        }
+    }
+
+    /// Record a launch that ran for `secs` and then closed
+    fn played(dir: &Path, mut inst: ActiveInstance, secs: i64, reason: ExitReason) {
+        let id = record_launch(dir, &inst, "place").unwrap();
+        inst.launch_id = Some(id);
+        let conn = db::open(dir).unwrap();
+        close(&conn, id, Some(inst.started_at as i64 + secs), reason, None).unwrap();
+    }
+
+    #[test]
+    fn test_exit_reason() {
+        assert_eq!(ExitReason::for_exit(1000, 1010, None), ExitReason::Crashed);
+        assert_eq!(
+            ExitReason::for_exit(1000, 1000 + CRASH_WINDOW_SECS, None),
+            ExitReason::Closed
+        );
+        // A known exit code wins over the run time
+        assert_eq!(ExitReason::for_exit(1000, 1010, Some(0)), ExitReason::Closed);
+        assert_eq!(ExitReason::for_exit(1000, 5000, Some(-1)), ExitReason::Crashed);
+    }
+
+    #[test]
+    fn test_playtime_per_account_and_game() {
+        let dir = temp_dir("history");
+        played(&dir, instance(1, "a", 100, None, 1_000), 600, ExitReason::Closed);
+        played(&dir, instance(2, "a", 100, None, 5_000), 300, ExitReason::Killed);
+        played(&dir, instance(3, "a", 200, None, 9_000), 60, ExitReason::Closed);
+        played(&dir, instance(4, "b", 100, None, 9_000), 120, ExitReason::Closed);
+
+        // Still running: not counted
+        record_launch(&dir, &instance(5, "a", 100, None, 20_000), "place").unwrap();
+
+        let all = playtime(&dir, Some("a"), None).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].place_id, 100);
+        assert_eq!(all[0].sessions, 2);
+        assert_eq!(all[0].total_secs, 900);
+        assert_eq!(all[0].last_played_at, 5_000);
+
+        let game = playtime(&dir, None, Some(100)).unwrap();
+        assert_eq!(game.len(), 2);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_recent_servers_and_exit_records() {
+        let dir = temp_dir("history");
+        let mut first = instance(1, "a", 100, Some("job-1"), 1_000);
+        first.launch_id = Some(record_launch(&dir, &first, "server").unwrap());
+        let mut second = instance(2, "b", 100, Some("job-1"), 2_000);
+        second.launch_id = Some(record_launch(&dir, &second, "server").unwrap());
+        let third = record_launch(&dir, &instance(3, "a", 100, None, 3_000), "place").unwrap();
+
+        let recent = recent_servers(&dir, 10).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].account_id, "b");
+        assert_eq!(recent[0].joined_at, 2_000);
+
+        // A place launch shows up once its server is known
+        record_joined(&dir, third, "job-2").unwrap();
+        let recent = recent_servers(&dir, 10).unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_eq!((recent[0].job_id.as_str(), recent[0].joined_at), ("job-2", 3_000));
+
+        let visited = visited_servers(&dir, "a", 100).unwrap();
+        assert_eq!(visited, HashSet::from(["job-1".to_string(), "job-2".to_string()]));
+        assert!(visited_servers(&dir, "a", 200).unwrap().is_empty());
+
+        record_killed(&dir, &first).unwrap();
+        // Already closed rows keep their first reason
+        record_exited(&dir, &first, Some(0)).unwrap();
+        // Handed-off clients report no exit code
+        record_exited(&dir, &second, None).unwrap();
+
+        // Only pid 3 is still open, and comes back as a trackable instance
+        let running_now = running(&dir).unwrap();
+        assert_eq!(running_now.len(), 1);
+        assert_eq!((running_now[0].pid, running_now[0].launch_id), (3, Some(third)));
+        assert_eq!(running_now[0].options, LaunchOptions::default());
+
+        let launches = list_launches(&dir, 10).unwrap();
+        assert_eq!(launches.len(), 3);
+        assert_eq!(launches[2].exit_reason, Some(ExitReason::Killed));
+        assert_eq!(launches[2].exit_code, None);
+        assert!(!launches[2].exit_reason_guessed);
+        assert_eq!(launches[1].exit_reason, Some(ExitReason::Closed));
+        assert!(launches[1].exit_reason_guessed);
+
+        // pid 3 is gone, its row is closed without an end time
+        assert_eq!(close_orphaned(&dir, |_| false).unwrap(), 1);
+        let orphan = &list_launches(&dir, 1).unwrap()[0];
+        assert_eq!(orphan.exit_reason, Some(ExitReason::Unknown));
+        assert_eq!(orphan.ended_at, None);
+        assert!(!orphan.exit_reason_guessed);
+        assert!(running(&dir).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
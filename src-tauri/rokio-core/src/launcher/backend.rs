@@ -0,0 +1,392 @@
+//! Launcher Backends
+//! A backend knows how to prepare, start, find and stop one kind of Roblox client.
+
+use crate::environment;
+use crate::process_utils::kill_process;
+use crate::roblox_link::RobloxLink;
+use crate::settings::AppSettings;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::custom::CustomCommandBackend;
+
+/// How long a client usually takes to show up in the process list
+const STARTUP_GRACE: Duration = Duration::from_millis(1500);
+/// Interval between process list scans while waiting for a new client
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Number of scans before falling back to the newest running client
+const POLL_ATTEMPTS: u32 = 10;
+
+/// Everything a backend needs to start one client
+#[derive(Debug, Clone)]
+pub struct LaunchSpec {
+    /// Deep link handed to the client (`None` opens the client to its menu)
+    pub deep_link: Option<String>,
+    pub place_id: u64,
+    pub job_id: Option<String>,
+    /// Isolated HOME/AppData root for multi-instance launches
+    pub home_dir: Option<PathBuf>,
+    /// Decrypted .ROBLOSECURITY cookie of the account being launched
+    pub cookie: String,
+}
+
+impl LaunchSpec {
+    /// Build the spec for a launch target (`None` opens the menu).
+    /// Every target type goes through here so they all get the same isolation.
+    pub fn for_target(
+        target: Option<&RobloxLink>,
+        home_dir: Option<PathBuf>,
+        cookie: &str,
+    ) -> Result<Self, String> {
+        let deep_link = match target {
+            Some(link) => Some(
+                link.to_deep_link()
+                    .ok_or("Share links must be resolved before launching")?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            deep_link,
+            place_id: target.and_then(RobloxLink::place_id).unwrap_or(0),
+            job_id: target.and_then(RobloxLink::job_id).map(str::to_string),
+            home_dir,
+            cookie: cookie.to_string(),
+        })
+    }
+}
+
+/// Environment variables that point a client at an isolated profile directory
+pub fn isolated_env(home_dir: &Path) -> Vec<(&'static str, PathBuf)> {
+    #[cfg(target_os = "windows")]
+    {
+        vec![
+            ("LOCALAPPDATA", home_dir.join("LocalAppData")),
+            ("APPDATA", home_dir.join("AppData")),
+        ]
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        vec![("HOME", home_dir.to_path_buf())]
+    }
+}
+
+/// A way of starting Roblox (platform client, bootstrapper, user wrapper, ...)
+pub trait LauncherBackend: Send + Sync {
+    /// Short identifier used in logs and error messages
+    fn name(&self) -> &str;
+
+    /// Create the isolated directory structure before the client starts
+    fn prepare_environment(&self, home_dir: &Path) -> Result<(), String> {
+        environment::prepare_home_dir(home_dir)
+    }
+
+    /// Make the account's session visible to the client
+    fn inject_session(&self, spec: &LaunchSpec) -> Result<(), String> {
+        match &spec.home_dir {
+            Some(home_dir) => environment::write_session_cookie(home_dir, &spec.cookie),
+            None => inject_system_cookie(&spec.cookie),
+        }
+    }
+
+    /// Start the client. Returns the PID of the spawned process when it is
+    /// the client itself, `None` when it only hands off to another process.
+    fn spawn(&self, spec: &LaunchSpec) -> Result<Option<u32>, String>;
+
+    /// PIDs of all client processes currently running
+    fn running_pids(&self) -> Vec<u32>;
+
+    /// Work out which PID belongs to the client that was just started
+    fn resolve_pid(&self, before_pids: &[u32], spawned: Option<u32>) -> Result<u32, String> {
+        if let Some(pid) = spawned {
+            return Ok(pid);
+        }
+
+        std::thread::sleep(STARTUP_GRACE);
+        wait_for_new_pid(|| self.running_pids(), before_pids).ok_or_else(|| {
+            format!(
+                "Failed to find the {} client process. Is Roblox installed?",
+                self.name()
+            )
+        })
+    }
+
+    /// Stop a client started by this backend
+    fn terminate(&self, pid: u32) -> Result<(), String> {
+        kill_process(pid)
+    }
+}
+
+/// Run the full launch sequence on a backend and return the client PID.
+/// Blocks while it waits for the client to show up.
+pub fn run_launch(backend: &dyn LauncherBackend, spec: &LaunchSpec) -> Result<u32, String> {
+    if let Some(home_dir) = &spec.home_dir {
+        backend.prepare_environment(home_dir)?;
+    }
+    backend.inject_session(spec)?;
+
+    let before_pids = backend.running_pids();
+    let spawned = backend.spawn(spec)?;
+
+    let pid = backend.resolve_pid(&before_pids, spawned)?;
+    log::info!("Launched {} client with PID {}", backend.name(), pid);
+    Ok(pid)
+}
+
+/// Pick the backend for the user's `launcher_preference`
+pub fn backend_for(settings: &AppSettings) -> Result<Box<dyn LauncherBackend>, String> {
+    match settings.launcher_preference.as_str() {
+        "custom" => Ok(Box::new(CustomCommandBackend::new(
+            &settings.custom_launch_command,
+        )?)),
+        #[cfg(target_os = "windows")]
+        "bloxstrap" | "fishstrap" | "froststrap" => Ok(Box::new(
+            super::platform::BootstrapperBackend::new(&settings.launcher_preference)?,
+        )),
+        _ => Ok(Box::new(super::platform::PlatformBackend)),
+    }
+}
+
+/// Poll `find_pids` until a PID that was not in `before_pids` shows up.
+/// Falls back to the newest running PID if none appears in time.
+pub fn wait_for_new_pid<F>(find_pids: F, before_pids: &[u32]) -> Option<u32>
+where
+    F: Fn() -> Vec<u32>,
+{
+    for _ in 0..POLL_ATTEMPTS {
+        if let Some(pid) = find_pids().into_iter().find(|pid| !before_pids.contains(pid)) {
+            return Some(pid);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    find_pids().into_iter().max()
+}
+
+/// Exit codes of watched clients that have exited, until they are taken
+static EXIT_CODES: Mutex<BTreeMap<u32, i32>> = Mutex::new(BTreeMap::new());
+
+/// Wait for a spawned process on a background thread so it never lingers as a
+/// zombie once it exits. Returns its PID.
+pub fn reap(mut child: Child) -> u32 {
+    let pid = child.id();
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    pid
+}
+
+/// `reap` for a spawned process that is the client itself: its exit code is
+/// kept for `take_exit_code`. Death by a signal is recorded as -1.
+pub fn watch(mut child: Child) -> u32 {
+    let pid = child.id();
+    std::thread::spawn(move || {
+        if let Ok(status) = child.wait() {
+            EXIT_CODES
+                .lock()
+                .unwrap()
+                .insert(pid, status.code().unwrap_or(-1));
+        }
+    });
+    pid
+}
+
+/// Exit code of a client started through `watch`, once it has exited.
+/// `None` for clients ROKIO did not spawn itself (they were handed off).
+pub fn take_exit_code(pid: u32) -> Option<i32> {
+    EXIT_CODES.lock().unwrap().remove(&pid)
+}
+
+/// Inject cookie into the SYSTEM Roblox cookie paths (real ~/Library/)
+/// so that single-instance launch uses the correct account.
+#[cfg(target_os = "macos")]
+pub fn inject_system_cookie(cookie_value: &str) -> Result<(), String> {
+    use crate::binarycookies::{BinaryCookies, Cookie as BinaryCookie, Page};
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::time::SystemTime;
+
+    let home = dirs::home_dir().ok_or("Cannot determine home directory")?;
+
+    let cookie = BinaryCookie {
+        domain: ".roblox.com".into(),
+        name: ".ROBLOSECURITY".into(),
+        path: Some("/".into()),
+        value: cookie_value.to_string(),
+        secure: Some(true),
+        http_only: Some(true),
+        expiration: Some(SystemTime::now() + Duration::from_secs(60 * 60 * 24 * 30)),
+        creation: Some(SystemTime::now()),
+    };
+
+    let page = Page::new(vec![cookie]);
+    let binary_cookies = BinaryCookies::new(vec![page]);
+    let bytes = binary_cookies.build();
+
+    // Write to ~/Library/HTTPStorages/
+    let http_storages = home.join("Library").join("HTTPStorages");
+    fs::create_dir_all(&http_storages).map_err(|e| e.to_string())?;
+    let f1 = http_storages.join("com.roblox.RobloxPlayer.binarycookies");
+    File::create(&f1)
+        .and_then(|mut f| f.write_all(&bytes))
+        .map_err(|e| format!("Failed to write system cookie (HTTPStorages): {}", e))?;
+
+    // Write to ~/Library/Cookies/
+    let cookies_dir = home.join("Library").join("Cookies");
+    fs::create_dir_all(&cookies_dir).map_err(|e| e.to_string())?;
+    let f2 = cookies_dir.join("com.roblox.RobloxPlayer.binarycookies");
+    File::create(&f2)
+        .and_then(|mut f| f.write_all(&bytes))
+        .map_err(|e| format!("Failed to write system cookie (Cookies): {}", e))?;
+
+    Ok(())
+}
+
+/// Only the macOS client reads its session from files we can write;
+/// single-instance launches elsewhere use the client's own login.
+#[cfg(not(target_os = "macos"))]
+pub fn inject_system_cookie(_cookie_value: &str) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::Instant;
+
+    /// Temp dir with a fake client that records its HOME and arguments
+    struct FakeClient {
+        root: PathBuf,
+        output: PathBuf,
+        script: PathBuf,
+    }
+
+    impl FakeClient {
+        fn new() -> Self {
+            let root = temp_dir("launch");
+            fs::create_dir_all(&root).unwrap();
+
+            let output = root.join("client.out");
+            let script = root.join("fake-client.sh");
+            fs::write(
+                &script,
+                format!(
+                    "#!/bin/sh\n{{ echo \"$HOME\"; for arg in \"$@\"; do echo \"$arg\"; done; }} > '{0}.tmp' && mv '{0}.tmp' '{0}'\n",
+                    output.display()
+                ),
+            )
+            .unwrap();
+            fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+            Self {
+                root,
+                output,
+                script,
+            }
+        }
+
+        fn backend(&self) -> CustomCommandBackend {
+            CustomCommandBackend::new(&format!(
+                "'{}' {{deeplink}} {{place_id}} {{job_id}}",
+                self.script.display()
+            ))
+            .unwrap()
+        }
+
+        /// Wait for the fake client to exit and return the recorded lines
+        fn recorded(&self) -> Vec<String> {
+            let started = Instant::now();
+            while !self.output.exists() {
+                assert!(
+                    started.elapsed() < Duration::from_secs(5),
+                    "fake client never ran"
+                );
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            let lines = fs::read_to_string(&self.output)
+                .unwrap()
+                .lines()
+                .map(str::to_string)
+                .collect();
+            fs::remove_file(&self.output).unwrap();
+            lines
+        }
+    }
+
+    impl Drop for FakeClient {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn test_every_target_runs_in_isolated_environment() {
+        let client = FakeClient::new();
+        let backend = client.backend();
+        let home_dir = client.root.join("environments").join("account-1");
+
+        let targets = vec![
+            (None, vec!["roblox://", "0", ""]),
+            (
+                Some(RobloxLink::from_parts(606849621, None)),
+                vec!["roblox://experiences/start?placeId=606849621", "606849621", ""],
+            ),
+            (
+                Some(RobloxLink::from_parts(606849621, Some("job-1".to_string()))),
+                vec![
+                    "roblox://experiences/start?placeId=606849621&gameInstanceId=job-1",
+                    "606849621",
+                    "job-1",
+                ],
+            ),
+            (
+                Some(RobloxLink::PrivateServer {
+                    place_id: 606849621,
+                    link_code: "4242".to_string(),
+                }),
+                vec![
+                    "roblox://experiences/start?placeId=606849621&linkCode=4242",
+                    "606849621",
+                    "",
+                ],
+            ),
+            (
+                Some(RobloxLink::FollowUser { user_id: 156 }),
+                vec!["roblox://experiences/start?userId=156", "0", ""],
+            ),
+        ];
+
+        for (target, expected_args) in targets {
+            let spec =
+                LaunchSpec::for_target(target.as_ref(), Some(home_dir.clone()), "COOKIE").unwrap();
+            run_launch(&backend, &spec).unwrap();
+
+            let recorded = client.recorded();
+            assert_eq!(recorded[0], home_dir.to_string_lossy(), "{:?}", target);
+            assert_eq!(&recorded[1..], expected_args.as_slice(), "{:?}", target);
+        }
+
+        // The session cookie was written into the isolated environment, not the real HOME
+        assert!(home_dir
+            .join("Library")
+            .join("HTTPStorages")
+            .join("com.roblox.RobloxPlayer.binarycookies")
+            .exists());
+    }
+
+    #[test]
+    fn test_unresolved_share_link_rejected() {
+        let share = RobloxLink::Share {
+            code: "abc".to_string(),
+            link_type: "Server".to_string(),
+        };
+        assert!(LaunchSpec::for_target(Some(&share), None, "COOKIE").is_err());
+    }
+}
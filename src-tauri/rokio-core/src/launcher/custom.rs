@@ -0,0 +1,181 @@
+//! Custom Command Backend
+//! Runs a user-defined command template, e.g.
+//! `my-wrapper --home {home} "{deeplink}"`.
+//!
+//! Placeholders:
+//! - `{deeplink}` - deep link for the launch (`roblox://` for the menu)
+//! - `{home}`     - isolated profile directory (empty when multi-instance is off)
+//! - `{place_id}` - target place ID (`0` for the menu)
+//! - `{job_id}`   - target server job ID (empty when not joining a server)
+//!
+//! Multi-instance launches also get the isolated HOME/AppData variables.
+
+use super::backend::{isolated_env, watch, LaunchSpec, LauncherBackend};
+use std::process::{Command, Stdio};
+
+/// Backend that spawns a user-supplied command for every launch
+pub struct CustomCommandBackend {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CustomCommandBackend {
+    /// Parse a command template into program + argument templates
+    pub fn new(template: &str) -> Result<Self, String> {
+        let mut parts = split_command_line(template);
+        if parts.is_empty() {
+            return Err("Custom launch command is empty".to_string());
+        }
+
+        let program = parts.remove(0);
+        Ok(Self {
+            program,
+            args: parts,
+        })
+    }
+
+    /// Program and arguments with all placeholders filled in
+    pub fn render(&self, spec: &LaunchSpec) -> (String, Vec<String>) {
+        let program = expand_placeholders(&self.program, spec);
+        let args = self
+            .args
+            .iter()
+            .map(|arg| expand_placeholders(arg, spec))
+            .collect();
+        (program, args)
+    }
+}
+
+impl LauncherBackend for CustomCommandBackend {
+    fn name(&self) -> &str {
+        "custom"
+    }
+
+    fn spawn(&self, spec: &LaunchSpec) -> Result<Option<u32>, String> {
+        let (program, args) = self.render(spec);
+
+        let mut cmd = Command::new(&program);
+        cmd.args(&args);
+        if let Some(home_dir) = &spec.home_dir {
+            cmd.envs(isolated_env(home_dir));
+        }
+
+        let child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to run custom launch command '{}': {}", program, e))?;
+
+        Ok(Some(watch(child)))
+    }
+
+    fn running_pids(&self) -> Vec<u32> {
+        // The spawned process is the one we track, nothing to scan for
+        vec![]
+    }
+}
+
+/// Replace `{deeplink}`, `{home}`, `{place_id}` and `{job_id}` in one argument
+fn expand_placeholders(template: &str, spec: &LaunchSpec) -> String {
+    let home = spec
+        .home_dir
+        .as_ref()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    template
+        .replace("{deeplink}", spec.deep_link.as_deref().unwrap_or("roblox://"))
+        .replace("{home}", &home)
+        .replace("{place_id}", &spec.place_id.to_string())
+        .replace("{job_id}", spec.job_id.as_deref().unwrap_or(""))
+}
+
+/// Split a command line on whitespace, keeping single/double quoted runs together.
+/// Backslashes are left alone so Windows paths survive.
+fn split_command_line(input: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_token = false;
+
+    for c in input.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_token = true;
+            }
+            None if c.is_whitespace() => {
+                if in_token {
+                    parts.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_token {
+        parts.push(current);
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn spec() -> LaunchSpec {
+        LaunchSpec {
+            deep_link: Some("roblox://experiences/start?placeId=1818".to_string()),
+            place_id: 1818,
+            job_id: Some("abc-def".to_string()),
+            home_dir: Some(PathBuf::from("/tmp/rokio env")),
+            cookie: "cookie".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line(r#"wrapper --home "{home}" '{deeplink}'  -v"#),
+            vec!["wrapper", "--home", "{home}", "{deeplink}", "-v"]
+        );
+        assert_eq!(
+            split_command_line(r#""C:\Program Files\Wrap\wrap.exe" """#),
+            vec![r"C:\Program Files\Wrap\wrap.exe", ""]
+        );
+        assert!(split_command_line("   ").is_empty());
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let backend =
+            CustomCommandBackend::new("wrap {home}/bin --place={place_id} {job_id} {deeplink}")
+                .unwrap();
+        let (program, args) = backend.render(&spec());
+
+        assert_eq!(program, "wrap");
+        assert_eq!(
+            args,
+            vec![
+                "/tmp/rokio env/bin",
+                "--place=1818",
+                "abc-def",
+                "roblox://experiences/start?placeId=1818",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_template_rejected() {
+        assert!(CustomCommandBackend::new("").is_err());
+    }
+}
@@ -0,0 +1,246 @@
+//! ROKIO Launcher Module
+//! Handles launching Roblox with deep links (cross-platform).
+//! The actual client handling lives in pluggable backends (see `backend`).
+
+mod backend;
+mod custom;
+mod platform;
+
+pub use backend::{backend_for, run_launch, take_exit_code, LaunchSpec, LauncherBackend};
+
+use crate::environment;
+use crate::history;
+use crate::process_utils::is_process_running;
+use crate::profiles::Profile;
+use crate::roblox::get_own_presence;
+use crate::roblox_link::RobloxLink;
+use crate::settings::{load_settings, AppSettings};
+use crate::share_links;
+use crate::vault::{load_accounts, update_accounts};
+use crate::vaults;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How often to look up which server a launched client joined
+const JOIN_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How long a launched client gets to join its game
+const JOIN_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Active Roblox instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveInstance {
+    pub pid: u32,
+    pub account_id: String,
+    pub username: String,
+    pub place_id: u64,
+    /// Server the client was sent to (when launched into a specific one)
+    #[serde(default)]
+    pub job_id: Option<String>,
+    pub started_at: u64,
+    /// Row in the launch history (closed when the instance exits)
+    #[serde(default)]
+    pub launch_id: Option<i64>,
+    /// Overrides it was launched with (kept when it is closed or relaunched)
+    #[serde(default)]
+    pub options: LaunchOptions,
+    /// Vault the account belongs to
+    #[serde(default)]
+    pub vault_id: String,
+}
+
+impl ActiveInstance {
+    /// Backend that started this instance
+    pub fn backend(&self, app_data_dir: &Path) -> Result<Box<dyn LauncherBackend>, String> {
+        backend_for(&self.options.settings(app_data_dir))
+    }
+}
+
+/// Per-launch overrides of the launch settings (`None` keeps the setting)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchOptions {
+    #[serde(default)]
+    pub multi_instance: Option<bool>,
+    /// Backend name, as in `AppSettings::launcher_preference`
+    #[serde(default)]
+    pub launcher_preference: Option<String>,
+}
+
+impl LaunchOptions {
+    /// Saved launch settings with these overrides applied
+    pub fn settings(&self, app_data_dir: &Path) -> AppSettings {
+        let mut settings = load_settings(app_data_dir).unwrap_or_default();
+        if let Some(multi_instance) = self.multi_instance {
+            settings.multi_instance = multi_instance;
+        }
+        if let Some(preference) = &self.launcher_preference {
+            settings.launcher_preference = preference.clone();
+        }
+        settings
+    }
+}
+
+// ============================================================================
+// LAUNCH PIPELINE
+// ============================================================================
+
+/// Shared launch preparation: load accounts, settings & timestamps
+struct LaunchContext {
+    account: Profile,
+    key: [u8; 32],
+    app_data_dir: PathBuf,
+    settings: AppSettings,
+    options: LaunchOptions,
+    now_ms: u64,
+    now_secs: u64,
+}
+
+impl LaunchContext {
+    fn load(
+        app_data_dir: &Path,
+        key: &[u8; 32],
+        account_id: &str,
+        options: &LaunchOptions,
+    ) -> Result<Self, String> {
+        let account = load_accounts(app_data_dir, key)?
+            .into_iter()
+            .find(|a| a.id == account_id)
+            .ok_or("Account not found")?;
+
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let now_secs = now_ms / 1000;
+
+        Ok(Self {
+            account,
+            key: *key,
+            app_data_dir: app_data_dir.to_path_buf(),
+            settings: options.settings(app_data_dir),
+            options: options.clone(),
+            now_ms,
+            now_secs,
+        })
+    }
+}
+
+/// Shared launch finalization: update timestamp, save accounts, describe instance
+/// and record it in the launch history
+fn finalize_launch(
+    ctx: LaunchContext,
+    pid: u32,
+    spec: &LaunchSpec,
+    target_type: &str,
+) -> Result<ActiveInstance, String> {
+    // Update last_played_at timestamp on a fresh copy of the accounts, so
+    // cookies rotated while launching are not overwritten with the old ones
+    let _ = update_accounts(&ctx.app_data_dir, &ctx.key, |accounts| {
+        if let Some(acc) = accounts.iter_mut().find(|a| a.id == ctx.account.id) {
+            acc.last_played_at = ctx.now_ms;
+        }
+        Ok(())
+    });
+
+    let mut instance = ActiveInstance {
+        pid,
+        account_id: ctx.account.id.clone(),
+        username: ctx.account.username.clone(),
+        place_id: spec.place_id,
+        job_id: spec.job_id.clone(),
+        started_at: ctx.now_secs,
+        launch_id: None,
+        options: ctx.options,
+        vault_id: vaults::active(&ctx.app_data_dir).id,
+    };
+
+    // History is best effort, the client is already running
+    match history::record_launch(&ctx.app_data_dir, &instance, target_type) {
+        Ok(id) => instance.launch_id = Some(id),
+        Err(e) => log::warn!("Failed to record launch: {}", e),
+    }
+    watch_join(ctx.app_data_dir, &ctx.account, &instance);
+
+    Ok(instance)
+}
+
+// Helper: In the background, record the server a launch into a place joins
+// once the account's presence shows it there. Gives up when the client exits.
+fn watch_join(app_data_dir: PathBuf, account: &Profile, instance: &ActiveInstance) {
+    let (Some(launch_id), place_id) = (instance.launch_id, instance.place_id as i64) else {
+        return;
+    };
+    if place_id == 0 {
+        return;
+    }
+    let (cookie, user_id, pid) = (account.cookie.clone(), account.user_id, instance.pid);
+
+    tokio::spawn(async move {
+        let deadline = Instant::now() + JOIN_TIMEOUT;
+        while Instant::now() < deadline && is_process_running(pid) {
+            tokio::time::sleep(JOIN_POLL_INTERVAL).await;
+            let Ok(presence) = get_own_presence(&cookie, user_id).await else {
+                continue;
+            };
+            if presence.place_id != Some(place_id) {
+                continue;
+            }
+            if let Some(game_id) = presence.game_id {
+                if let Err(e) = history::record_joined(&app_data_dir, launch_id, &game_id) {
+                    log::warn!("Failed to record joined server: {}", e);
+                }
+                return;
+            }
+        }
+    });
+}
+
+/// Single launch pipeline shared by every target type (menu, place, job,
+/// private server, share link, follow-user). The launch stays open in the
+/// history until it ends, which is how other processes see it (`history::running`).
+pub async fn launch_account(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    account_id: &str,
+    target: Option<RobloxLink>,
+) -> Result<ActiveInstance, String> {
+    launch_account_with(app_data_dir, key, account_id, target, &LaunchOptions::default()).await
+}
+
+/// `launch_account` with the launch settings overridden by `options`
+pub async fn launch_account_with(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    account_id: &str,
+    target: Option<RobloxLink>,
+    options: &LaunchOptions,
+) -> Result<ActiveInstance, String> {
+    let ctx = LaunchContext::load(app_data_dir, key, account_id, options)?;
+    let backend = backend_for(&ctx.settings)?;
+    let target_type = target.as_ref().map_or("menu", RobloxLink::kind);
+
+    // Share links only resolve to a server with a signed-in session
+    let target = match target {
+        Some(link) => Some(share_links::resolve_link(&ctx.account.cookie, link).await?),
+        None => None,
+    };
+
+    // Multi-instance launches run inside the account's isolated environment,
+    // otherwise the cookie is injected into the system Roblox paths
+    let home_dir = ctx
+        .settings
+        .multi_instance
+        .then(|| environment::profile_dir_in(app_data_dir, account_id));
+
+    let spec = LaunchSpec::for_target(target.as_ref(), home_dir, &ctx.account.cookie)?;
+    // Finding the new client sleeps and polls the process list for seconds,
+    // so it runs off the async workers
+    let launch_spec = spec.clone();
+    let pid = tokio::task::spawn_blocking(move || run_launch(backend.as_ref(), &launch_spec))
+        .await
+        .map_err(|e| format!("Launch failed: {}", e))??;
+
+    finalize_launch(ctx, pid, &spec, target_type)
+}
@@ -0,0 +1,305 @@
+//! Platform Launcher Backends
+//! The stock Roblox client on each OS: Roblox.app (macOS), the roblox:// protocol
+//! handler (Windows) and Sober via Flatpak (Linux).
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+use super::backend::reap;
+#[cfg(target_os = "macos")]
+use super::backend::watch;
+use super::backend::{isolated_env, LaunchSpec, LauncherBackend};
+use std::process::Command;
+#[cfg(not(target_os = "windows"))]
+use std::process::Stdio;
+
+/// The default client for the current platform
+pub struct PlatformBackend;
+
+// ============================================================================
+// MACOS - Roblox.app
+// ============================================================================
+
+/// Find all running RobloxPlayer PIDs (macOS)
+#[cfg(target_os = "macos")]
+fn find_roblox_pids() -> Vec<u32> {
+    Command::new("pgrep")
+        .arg("RobloxPlayer")
+        .output()
+        .ok()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.trim().parse::<u32>().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Find the Roblox.app path on macOS
+#[cfg(target_os = "macos")]
+fn find_roblox_app() -> Result<std::path::PathBuf, String> {
+    use std::path::PathBuf;
+
+    // Check common installation locations
+    let mut locations = vec![PathBuf::from("/Applications/Roblox.app")];
+
+    if let Some(home) = dirs::home_dir() {
+        locations.push(home.join("Applications/Roblox.app"));
+    }
+
+    for loc in locations {
+        if loc.exists() {
+            return Ok(loc);
+        }
+    }
+
+    Err("Roblox.app not found. Please install Roblox first.".to_string())
+}
+
+#[cfg(target_os = "macos")]
+impl LauncherBackend for PlatformBackend {
+    fn name(&self) -> &str {
+        "Roblox"
+    }
+
+    fn prepare_environment(&self, home_dir: &std::path::Path) -> Result<(), String> {
+        crate::environment::prepare_home_dir(home_dir)?;
+        crate::environment::create_keychain_at(home_dir)?;
+        crate::environment::unlock_keychain_at(home_dir)
+    }
+
+    fn spawn(&self, spec: &LaunchSpec) -> Result<Option<u32>, String> {
+        if let Some(home_dir) = &spec.home_dir {
+            // Launch RobloxPlayer directly with a custom HOME (like raptormanager)
+            // Roblox will read cookies from {HOME}/Library/HTTPStorages/
+            // `open` would hand the link to the already running client, so the
+            // deep link is passed straight to the new player instead
+            let player_path = find_roblox_app()?
+                .join("Contents")
+                .join("MacOS")
+                .join("RobloxPlayer");
+
+            if !player_path.exists() {
+                return Err(format!(
+                    "RobloxPlayer not found at: {}",
+                    player_path.display()
+                ));
+            }
+
+            let mut cmd = Command::new(&player_path);
+            cmd.envs(isolated_env(home_dir));
+            if let Some(link) = &spec.deep_link {
+                cmd.arg("-protocolString").arg(link);
+            }
+
+            let child = cmd
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| format!("Failed to launch Roblox: {}", e))?;
+
+            return Ok(Some(watch(child)));
+        }
+
+        let mut cmd = Command::new("open");
+        match &spec.deep_link {
+            Some(link) => cmd.arg(link),
+            None => cmd.arg("-a").arg("Roblox"),
+        };
+        reap(
+            cmd.spawn()
+                .map_err(|e| format!("Failed to open Roblox: {}", e))?,
+        );
+
+        Ok(None)
+    }
+
+    fn running_pids(&self) -> Vec<u32> {
+        find_roblox_pids()
+    }
+}
+
+// ============================================================================
+// WINDOWS - roblox:// protocol handler
+// ============================================================================
+
+/// Find all running RobloxPlayerBeta PIDs (Windows)
+#[cfg(target_os = "windows")]
+fn find_roblox_pids() -> Vec<u32> {
+    // Use wmic to get RobloxPlayerBeta process IDs
+    Command::new("wmic")
+        .args(["process", "where", "name='RobloxPlayerBeta.exe'", "get", "ProcessId"])
+        .output()
+        .ok()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.trim().parse::<u32>().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "windows")]
+impl LauncherBackend for PlatformBackend {
+    fn name(&self) -> &str {
+        "Roblox"
+    }
+
+    fn spawn(&self, spec: &LaunchSpec) -> Result<Option<u32>, String> {
+        let deep_link = spec.deep_link.as_deref().unwrap_or("roblox://");
+
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", "", deep_link]);
+        if let Some(home_dir) = &spec.home_dir {
+            cmd.envs(isolated_env(home_dir));
+        }
+        reap(
+            cmd.spawn()
+                .map_err(|e| format!("Failed to open Roblox: {}", e))?,
+        );
+
+        Ok(None)
+    }
+
+    fn running_pids(&self) -> Vec<u32> {
+        find_roblox_pids()
+    }
+}
+
+/// Third-party bootstrappers (Bloxstrap and its forks) installed per user
+#[cfg(target_os = "windows")]
+pub struct BootstrapperBackend {
+    name: String,
+    executable: std::path::PathBuf,
+}
+
+#[cfg(target_os = "windows")]
+impl BootstrapperBackend {
+    /// Locate `%LOCALAPPDATA%\<Name>\<Name>.exe` for a launcher preference
+    pub fn new(preference: &str) -> Result<Self, String> {
+        let name = match preference {
+            "bloxstrap" => "Bloxstrap",
+            "fishstrap" => "Fishstrap",
+            "froststrap" => "Froststrap",
+            other => return Err(format!("Unknown bootstrapper: {}", other)),
+        };
+
+        let local_appdata = dirs::data_local_dir().ok_or("Cannot determine LocalAppData")?;
+        let executable = local_appdata.join(name).join(format!("{}.exe", name));
+        if !executable.exists() {
+            return Err(format!(
+                "{} not found at: {}",
+                name,
+                executable.display()
+            ));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            executable,
+        })
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl LauncherBackend for BootstrapperBackend {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn spawn(&self, spec: &LaunchSpec) -> Result<Option<u32>, String> {
+        let deep_link = spec.deep_link.as_deref().unwrap_or("roblox://");
+
+        let mut cmd = Command::new(&self.executable);
+        cmd.args(["-player", deep_link]);
+        if let Some(home_dir) = &spec.home_dir {
+            cmd.envs(isolated_env(home_dir));
+        }
+        reap(
+            cmd.spawn()
+                .map_err(|e| format!("Failed to launch {}: {}", self.name, e))?,
+        );
+
+        Ok(None)
+    }
+
+    fn running_pids(&self) -> Vec<u32> {
+        find_roblox_pids()
+    }
+}
+
+// ============================================================================
+// LINUX - Sober (Roblox via Flatpak)
+// ============================================================================
+
+/// Find all running Sober PIDs (Linux)
+#[cfg(target_os = "linux")]
+fn find_sober_pids() -> Vec<u32> {
+    Command::new("pgrep")
+        .args(["-f", "org.vinegarhq.Sober"])
+        .output()
+        .ok()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.trim().parse::<u32>().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+impl LauncherBackend for PlatformBackend {
+    fn name(&self) -> &str {
+        "Sober"
+    }
+
+    fn spawn(&self, spec: &LaunchSpec) -> Result<Option<u32>, String> {
+        let mut cmd = Command::new("flatpak");
+        cmd.arg("run");
+        if let Some(home_dir) = &spec.home_dir {
+            // Flatpak does not pass our environment into the sandbox
+            for (key, value) in isolated_env(home_dir) {
+                cmd.arg(format!("--env={}={}", key, value.display()));
+            }
+        }
+        cmd.arg("org.vinegarhq.Sober");
+        if let Some(link) = &spec.deep_link {
+            cmd.arg(link);
+        }
+
+        let child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to launch Sober: {}", e))?;
+        reap(child);
+
+        Ok(None)
+    }
+
+    fn running_pids(&self) -> Vec<u32> {
+        find_sober_pids()
+    }
+}
+
+// ============================================================================
+// UNSUPPORTED PLATFORMS
+// ============================================================================
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+impl LauncherBackend for PlatformBackend {
+    fn name(&self) -> &str {
+        "Roblox"
+    }
+
+    fn spawn(&self, _spec: &LaunchSpec) -> Result<Option<u32>, String> {
+        Err("Roblox launch is not supported on this platform".to_string())
+    }
+
+    fn running_pids(&self) -> Vec<u32> {
+        vec![]
+    }
+}
@@ -0,0 +1,38 @@
+//! ROKIO Core - Everything that does not need a window.
+//!
+//! Vault, crypto, isolated environments, settings, the launch pipeline and the
+//! Roblox web client. Functions take the app data directory explicitly instead
+//! of a `tauri::AppHandle`, so the GUI, the CLI and the tests share one code path.
+
+pub mod audit;
+pub mod binarycookies;
+pub mod bulk_import;
+pub mod cookie_files;
+pub mod crypto;
+pub mod db;
+pub mod discovery;
+pub mod environment;
+pub mod game_cache;
+pub mod game_detection;
+pub mod history;
+pub mod launcher;
+pub mod organize;
+pub mod presets;
+pub mod process_utils;
+pub mod profiles;
+pub mod regions;
+pub mod roblox;
+pub mod roblox_link;
+pub mod security;
+pub mod server_details;
+pub mod server_finder;
+pub mod server_hop;
+pub mod session;
+pub mod settings;
+pub mod share_links;
+pub mod trash;
+pub mod vault;
+pub mod vaults;
+
+#[cfg(test)]
+mod test_support;
@@ -0,0 +1,366 @@
+//! ROKIO Account Organization - tags, folders and custom order
+//!
+//! Folders are `/`-separated paths ("Alts/Farming"), so a folder query can
+//! include everything nested below it. All writes go through the vault.
+
+use crate::profiles::Profile;
+use crate::vault::update_accounts;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Filter for `query_accounts` (all set fields must match)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountQuery {
+    /// Tag, case-insensitive
+    pub tag: Option<String>,
+    /// Folder path; an empty string selects ungrouped accounts
+    pub folder: Option<String>,
+    /// Also match accounts in folders nested below `folder`
+    #[serde(default)]
+    pub include_subfolders: bool,
+}
+
+/// A tag or folder with the number of accounts in it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelCount {
+    pub name: String,
+    pub count: usize,
+}
+
+/// Every tag and folder in use
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountLabels {
+    pub tags: Vec<LabelCount>,
+    /// Each folder path, parents included; counts cover nested folders
+    pub folders: Vec<LabelCount>,
+}
+
+/// Change applied to a selection of accounts (`Delete` moves them to the trash)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum BulkAction {
+    AddTags { tags: Vec<String> },
+    RemoveTags { tags: Vec<String> },
+    SetTags { tags: Vec<String> },
+    MoveToFolder { folder: String },
+    SetFavorite { favorite: bool },
+    Delete,
+}
+
+// ============================================================================
+// NORMALIZATION
+// ============================================================================
+
+/// Trim tags and drop empty or duplicate (case-insensitive) ones
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !out.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            out.push(tag.to_string());
+        }
+    }
+    out
+}
+
+/// Trim each segment of a folder path and drop empty ones ("/a//b " -> "a/b")
+pub fn normalize_folder(folder: &str) -> String {
+    folder
+        .split('/')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn in_folder(account_folder: &str, folder: &str, include_subfolders: bool) -> bool {
+    if account_folder == folder {
+        return true;
+    }
+    include_subfolders
+        && (folder.is_empty()
+            || account_folder
+                .strip_prefix(folder)
+                .is_some_and(|rest| rest.starts_with('/')))
+}
+
+// ============================================================================
+// QUERIES
+// ============================================================================
+
+/// Accounts matching `query`, in custom order
+pub fn query_accounts(accounts: Vec<Profile>, query: &AccountQuery) -> Vec<Profile> {
+    let folder = query.folder.as_deref().map(normalize_folder);
+
+    accounts
+        .into_iter()
+        .filter(|a| {
+            query
+                .tag
+                .as_deref()
+                .is_none_or(|tag| a.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim())))
+        })
+        .filter(|a| {
+            folder
+                .as_deref()
+                .is_none_or(|f| in_folder(&a.folder, f, query.include_subfolders))
+        })
+        .collect()
+}
+
+/// Tags and folders in use, sorted by name
+pub fn account_labels(accounts: &[Profile]) -> AccountLabels {
+    let mut tags: BTreeMap<String, LabelCount> = BTreeMap::new();
+    let mut folders: BTreeMap<String, usize> = BTreeMap::new();
+
+    for account in accounts {
+        for tag in &account.tags {
+            tags.entry(tag.to_lowercase())
+                .or_insert_with(|| LabelCount {
+                    name: tag.clone(),
+                    count: 0,
+                })
+                .count += 1;
+        }
+
+        // Count the account in its folder and every parent
+        let mut path = String::new();
+        for segment in account.folder.split('/').filter(|s| !s.is_empty()) {
+            if !path.is_empty() {
+                path.push('/');
+            }
+            path.push_str(segment);
+            *folders.entry(path.clone()).or_insert(0) += 1;
+        }
+    }
+
+    AccountLabels {
+        tags: tags.into_values().collect(),
+        folders: folders
+            .into_iter()
+            .map(|(name, count)| LabelCount { name, count })
+            .collect(),
+    }
+}
+
+// ============================================================================
+// WRITES
+// ============================================================================
+
+/// Apply `action` to the given accounts, returning how many were changed
+pub fn apply_bulk(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    account_ids: &[String],
+    action: &BulkAction,
+) -> Result<usize, String> {
+    if let BulkAction::Delete = action {
+        return crate::trash::move_to_trash(app_data_dir, account_ids);
+    }
+
+    update_accounts(app_data_dir, key, |accounts| {
+        let mut count = 0;
+        for account in accounts.iter_mut().filter(|a| account_ids.contains(&a.id)) {
+            apply_to(account, action);
+            count += 1;
+        }
+        Ok(count)
+    })
+}
+
+fn apply_to(account: &mut Profile, action: &BulkAction) {
+    match action {
+        BulkAction::AddTags { tags } => {
+            let mut all = account.tags.clone();
+            all.extend(tags.iter().cloned());
+            account.tags = normalize_tags(&all);
+        }
+        BulkAction::RemoveTags { tags } => {
+            account
+                .tags
+                .retain(|t| !tags.iter().any(|r| r.trim().eq_ignore_ascii_case(t)));
+        }
+        BulkAction::SetTags { tags } => account.tags = normalize_tags(tags),
+        BulkAction::MoveToFolder { folder } => account.folder = normalize_folder(folder),
+        BulkAction::SetFavorite { favorite } => account.is_favorite = *favorite,
+        BulkAction::Delete => {}
+    }
+}
+
+/// Persist a custom order. Listed accounts come first in the given order,
+/// the rest keep their relative order after them.
+pub fn reorder_accounts(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    ordered_ids: &[String],
+) -> Result<(), String> {
+    update_accounts(app_data_dir, key, |accounts| {
+        accounts.sort_by_key(|a| {
+            ordered_ids
+                .iter()
+                .position(|id| *id == a.id)
+                .unwrap_or(ordered_ids.len())
+        });
+        for (i, account) in accounts.iter_mut().enumerate() {
+            account.sort_order = i as u32;
+        }
+        Ok(())
+    })
+}
+
+/// Rename or move a folder together with everything nested below it.
+/// Returns the number of accounts moved.
+pub fn rename_folder(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    from: &str,
+    to: &str,
+) -> Result<usize, String> {
+    let from = normalize_folder(from);
+    let to = normalize_folder(to);
+    if from.is_empty() {
+        return Err("Folder name cannot be empty".to_string());
+    }
+
+    update_accounts(app_data_dir, key, |accounts| {
+        let mut moved = 0;
+        for account in accounts
+            .iter_mut()
+            .filter(|a| in_folder(&a.folder, &from, true))
+        {
+            let rest = &account.folder[from.len()..];
+            account.folder = normalize_folder(&format!("{}{}", to, rest));
+            moved += 1;
+        }
+        Ok(moved)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{derive_key, write_empty_vault};
+    use crate::test_support::{profile, temp_dir};
+    use crate::vault::{load_accounts, save_accounts};
+
+    fn account(id: &str, folder: &str, tags: &[&str]) -> Profile {
+        Profile {
+            id: id.to_string(),
+            cookie: format!("COOKIE_{}", id),
+            username: id.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            folder: folder.to_string(),
+            created_at: Some(1),
+            ..profile(id.len() as i64)
+        }
+    }
+
+    fn ids(accounts: &[Profile]) -> Vec<&str> {
+        accounts.iter().map(|a| a.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_query_by_tag_and_folder() {
+        let accounts = vec![
+            account("main", "", &["Main"]),
+            account("alt1", "Alts", &["farm"]),
+            account("alt2", "Alts/Farming", &["FARM", "trade"]),
+            account("alt3", "Alts2", &[]),
+        ];
+
+        let by_tag = AccountQuery {
+            tag: Some("farm".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(&query_accounts(accounts.clone(), &by_tag)),
+            ["alt1", "alt2"]
+        );
+
+        let mut by_folder = AccountQuery {
+            folder: Some(" Alts/ ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&query_accounts(accounts.clone(), &by_folder)), ["alt1"]);
+        by_folder.include_subfolders = true;
+        assert_eq!(
+            ids(&query_accounts(accounts.clone(), &by_folder)),
+            ["alt1", "alt2"]
+        );
+
+        let ungrouped = AccountQuery {
+            folder: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&query_accounts(accounts.clone(), &ungrouped)), ["main"]);
+
+        let labels = account_labels(&accounts);
+        assert_eq!(labels.tags.len(), 3);
+        assert_eq!(
+            labels.folders,
+            vec![
+                LabelCount {
+                    name: "Alts".to_string(),
+                    count: 2
+                },
+                LabelCount {
+                    name: "Alts/Farming".to_string(),
+                    count: 1
+                },
+                LabelCount {
+                    name: "Alts2".to_string(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bulk_reorder_and_rename() {
+        let dir = temp_dir("organize");
+        let key = derive_key("pw");
+        write_empty_vault(&dir, &key).unwrap();
+        save_accounts(
+            &dir,
+            &key,
+            &[
+                account("a", "Alts/Farming", &["farm"]),
+                account("bb", "", &[]),
+                account("ccc", "", &[]),
+            ],
+        )
+        .unwrap();
+
+        let selection = vec!["bb".to_string(), "ccc".to_string(), "gone".to_string()];
+        let add = BulkAction::AddTags {
+            tags: vec![" trade ".to_string(), "Trade".to_string()],
+        };
+        assert_eq!(apply_bulk(&dir, &key, &selection, &add).unwrap(), 2);
+        let moved = BulkAction::MoveToFolder {
+            folder: "Alts".to_string(),
+        };
+        apply_bulk(&dir, &key, &selection[..1], &moved).unwrap();
+
+        reorder_accounts(&dir, &key, &["ccc".to_string(), "a".to_string()]).unwrap();
+        let accounts = load_accounts(&dir, &key).unwrap();
+        assert_eq!(ids(&accounts), ["ccc", "a", "bb"]);
+        assert_eq!(accounts[0].tags, vec!["trade".to_string()]);
+        assert_eq!(accounts[1].created_at, Some(1));
+
+        assert_eq!(rename_folder(&dir, &key, "Alts", "Old/Alts").unwrap(), 2);
+        let accounts = load_accounts(&dir, &key).unwrap();
+        assert_eq!(accounts[1].folder, "Old/Alts/Farming");
+        assert_eq!(accounts[2].folder, "Old/Alts");
+
+        assert_eq!(
+            apply_bulk(&dir, &key, &selection, &BulkAction::Delete).unwrap(),
+            2
+        );
+        assert_eq!(ids(&load_accounts(&dir, &key).unwrap()), ["a"]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
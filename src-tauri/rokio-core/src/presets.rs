@@ -0,0 +1,291 @@
+//! ROKIO Launch Presets - `presets.json` in the active vault's directory
+//!
+//! A preset is a named destination, a set of accounts and launch options.
+//! Running it launches every account through the normal launch pipeline,
+//! one after another with an optional stagger delay.
+
+use crate::launcher::{launch_account_with, ActiveInstance, LaunchOptions};
+use crate::roblox_link::RobloxLink;
+use crate::vaults;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Longest stagger delay accepted between two launches
+const MAX_STAGGER_MS: u64 = 5 * 60 * 1000;
+
+/// Where a preset sends its accounts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PresetDestination {
+    /// Open the client to its menu
+    Menu,
+    #[serde(rename_all = "camelCase")]
+    Place { place_id: u64 },
+    #[serde(rename_all = "camelCase")]
+    Server { place_id: u64, job_id: String },
+    #[serde(rename_all = "camelCase")]
+    PrivateServer { place_id: u64, link_code: String },
+    /// Any link `RobloxLink` understands (share links resolve per account)
+    Link { link: String },
+}
+
+impl PresetDestination {
+    /// Launch target, `None` for the menu
+    pub fn to_link(&self) -> Result<Option<RobloxLink>, String> {
+        Ok(match self {
+            PresetDestination::Menu => None,
+            PresetDestination::Place { place_id } => Some(RobloxLink::from_parts(*place_id, None)),
+            PresetDestination::Server { place_id, job_id } => {
+                Some(RobloxLink::from_parts(*place_id, Some(job_id.clone())))
+            }
+            PresetDestination::PrivateServer {
+                place_id,
+                link_code,
+            } => Some(RobloxLink::PrivateServer {
+                place_id: *place_id,
+                link_code: link_code.clone(),
+            }),
+            PresetDestination::Link { link } => Some(RobloxLink::parse(link)?),
+        })
+    }
+}
+
+/// Launch options of a preset
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetOptions {
+    /// Multi-instance and backend overrides (`None` uses the settings)
+    #[serde(flatten)]
+    pub launch: LaunchOptions,
+    /// Delay between two launches in milliseconds
+    #[serde(default)]
+    pub stagger_ms: u64,
+}
+
+/// A saved launch preset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchPreset {
+    pub id: String,
+    pub name: String,
+    pub destination: PresetDestination,
+    /// Accounts in launch order
+    pub account_ids: Vec<String>,
+    #[serde(default)]
+    pub options: PresetOptions,
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+/// An account the preset could not launch
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetFailure {
+    pub account_id: String,
+    pub error: String,
+}
+
+/// Outcome of running a preset
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetRunResult {
+    pub launched: Vec<ActiveInstance>,
+    pub failed: Vec<PresetFailure>,
+}
+
+// Presets name accounts of one vault, so each vault keeps its own
+fn presets_path(app_data_dir: &Path) -> PathBuf {
+    vaults::active_dir(app_data_dir).join("presets.json")
+}
+
+/// Load all presets (empty when the file does not exist yet)
+pub fn load_presets(app_data_dir: &Path) -> Result<Vec<LaunchPreset>, String> {
+    let path = presets_path(app_data_dir);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read presets: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse presets: {}", e))
+}
+
+fn save_presets(app_data_dir: &Path, presets: &[LaunchPreset]) -> Result<(), String> {
+    fs::create_dir_all(app_data_dir)
+        .map_err(|e| format!("Failed to create presets directory: {}", e))?;
+    let json = serde_json::to_string_pretty(presets)
+        .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+    fs::write(presets_path(app_data_dir), json).map_err(|e| format!("Failed to write presets: {}", e))
+}
+
+fn validate(preset: &LaunchPreset) -> Result<(), String> {
+    if preset.name.trim().is_empty() {
+        return Err("Preset name cannot be empty".to_string());
+    }
+    if preset.account_ids.is_empty() {
+        return Err("Preset needs at least one account".to_string());
+    }
+    if preset.options.stagger_ms > MAX_STAGGER_MS {
+        return Err("Stagger delay is too long (max 5 minutes)".to_string());
+    }
+    preset.destination.to_link().map(|_| ())
+}
+
+/// Create or replace a preset (a new ID is assigned when `id` is empty)
+pub fn upsert_preset(app_data_dir: &Path, mut preset: LaunchPreset) -> Result<LaunchPreset, String> {
+    validate(&preset)?;
+    let mut presets = load_presets(app_data_dir)?;
+
+    match presets.iter_mut().find(|p| !preset.id.is_empty() && p.id == preset.id) {
+        Some(existing) => {
+            preset.created_at = existing.created_at;
+            *existing = preset.clone();
+        }
+        None => {
+            if preset.id.is_empty() {
+                preset.id = uuid::Uuid::new_v4().to_string();
+            }
+            preset.created_at = chrono::Utc::now().timestamp() as u64;
+            presets.push(preset.clone());
+        }
+    }
+
+    save_presets(app_data_dir, &presets)?;
+    Ok(preset)
+}
+
+/// Delete a preset, returning whether it existed
+pub fn delete_preset(app_data_dir: &Path, preset_id: &str) -> Result<bool, String> {
+    let mut presets = load_presets(app_data_dir)?;
+    let before = presets.len();
+    presets.retain(|p| p.id != preset_id);
+    if presets.len() == before {
+        return Ok(false);
+    }
+    save_presets(app_data_dir, &presets)?;
+    Ok(true)
+}
+
+/// Find a preset by ID or (case-insensitive) name
+pub fn find_preset(app_data_dir: &Path, query: &str) -> Result<LaunchPreset, String> {
+    load_presets(app_data_dir)?
+        .into_iter()
+        .find(|p| p.id == query || p.name.eq_ignore_ascii_case(query))
+        .ok_or_else(|| "Preset not found".to_string())
+}
+
+/// Launch every account of a preset. One failing account does not stop the
+/// others; `on_launch` sees each instance as soon as it is running.
+pub async fn run_preset(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    preset: &LaunchPreset,
+    on_launch: &(dyn Fn(&ActiveInstance) + Sync),
+) -> Result<PresetRunResult, String> {
+    validate(preset)?;
+
+    let mut result = PresetRunResult {
+        launched: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for (i, account_id) in preset.account_ids.iter().enumerate() {
+        if i > 0 && preset.options.stagger_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(preset.options.stagger_ms)).await;
+        }
+
+        let target = preset.destination.to_link()?;
+        match launch_account_with(app_data_dir, key, account_id, target, &preset.options.launch).await {
+            Ok(instance) => {
+                on_launch(&instance);
+                result.launched.push(instance);
+            }
+            Err(error) => {
+                log::warn!("Preset {} failed for {}: {}", preset.name, account_id, error);
+                result.failed.push(PresetFailure {
+                    account_id: account_id.clone(),
+                    error,
+                });
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn preset(name: &str) -> LaunchPreset {
+        LaunchPreset {
+            id: String::new(),
+            name: name.to_string(),
+            destination: PresetDestination::PrivateServer {
+                place_id: 920587237,
+                link_code: "12345".to_string(),
+            },
+            account_ids: vec!["a".to_string(), "b".to_string()],
+            options: PresetOptions {
+                launch: LaunchOptions {
+                    multi_instance: Some(true),
+                    launcher_preference: None,
+                },
+                stagger_ms: 2000,
+            },
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_upsert_find_and_delete() {
+        let dir = temp_dir("presets");
+
+        let created = upsert_preset(&dir, preset("Testing alts")).unwrap();
+        assert!(!created.id.is_empty());
+        assert!(created.created_at > 0);
+
+        let mut renamed = created.clone();
+        renamed.name = "Private server".to_string();
+        renamed.created_at = 0;
+        upsert_preset(&dir, renamed).unwrap();
+
+        let presets = load_presets(&dir).unwrap();
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].created_at, created.created_at);
+        assert_eq!(find_preset(&dir, "private SERVER").unwrap().id, created.id);
+
+        assert!(delete_preset(&dir, &created.id).unwrap());
+        assert!(!delete_preset(&dir, &created.id).unwrap());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_invalid_presets_rejected() {
+        let dir = temp_dir("presets");
+
+        let mut empty = preset("No accounts");
+        empty.account_ids.clear();
+        assert!(upsert_preset(&dir, empty).is_err());
+
+        let mut bad_link = preset("Bad link");
+        bad_link.destination = PresetDestination::Link {
+            link: "not a link".to_string(),
+        };
+        assert!(upsert_preset(&dir, bad_link).is_err());
+
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_json_shape() {
+        let json = serde_json::to_value(preset("x")).unwrap();
+        assert_eq!(json["destination"]["type"], "privateServer");
+        assert_eq!(json["destination"]["linkCode"], "12345");
+        assert_eq!(json["options"]["multiInstance"], true);
+        assert_eq!(json["options"]["staggerMs"], 2000);
+    }
+}
@@ -0,0 +1,99 @@
+//! ROKIO Profiles Module
+//! Profile data structure for Roblox accounts.
+
+use serde::{Deserialize, Serialize};
+
+/// A Roblox account profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    /// Unique identifier (UUID v4)
+    pub id: String,
+    /// Decrypted .ROBLOSECURITY cookie
+    pub cookie: String,
+    /// Roblox user ID
+    pub user_id: i64,
+    /// Roblox username
+    pub username: String,
+    /// Roblox display name
+    pub display_name: String,
+    /// Avatar thumbnail URL
+    pub thumbnail: Option<String>,
+    /// User-defined alias (custom name)
+    #[serde(default)]
+    pub alias: String,
+    /// User-defined description/notes
+    #[serde(default)]
+    pub description: String,
+    /// User-defined tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Folder path, `/`-separated for nesting (empty = ungrouped)
+    #[serde(default)]
+    pub folder: String,
+    /// Position in the user's custom order
+    #[serde(default)]
+    pub sort_order: u32,
+    /// Is favorite account
+    pub is_favorite: bool,
+    /// Last played timestamp (Unix epoch)
+    pub last_played_at: u64,
+    /// Optional password for the account
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Account creation timestamp (Unix epoch)
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    /// Premium membership status
+    #[serde(default)]
+    pub is_premium: Option<bool>,
+}
+
+/// Account as sent to the webview: everything but the cookie and password.
+/// Commands that need the cookie take the account ID and read it from the vault.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSummary {
+    pub id: String,
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: String,
+    pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub alias: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub folder: String,
+    #[serde(default)]
+    pub sort_order: u32,
+    pub is_favorite: bool,
+    pub last_played_at: u64,
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    #[serde(default)]
+    pub is_premium: Option<bool>,
+}
+
+impl From<&Profile> for AccountSummary {
+    fn from(p: &Profile) -> Self {
+        Self {
+            id: p.id.clone(),
+            user_id: p.user_id,
+            username: p.username.clone(),
+            display_name: p.display_name.clone(),
+            thumbnail: p.thumbnail.clone(),
+            alias: p.alias.clone(),
+            description: p.description.clone(),
+            tags: p.tags.clone(),
+            folder: p.folder.clone(),
+            sort_order: p.sort_order,
+            is_favorite: p.is_favorite,
+            last_played_at: p.last_played_at,
+            created_at: p.created_at,
+            is_premium: p.is_premium,
+        }
+    }
+}
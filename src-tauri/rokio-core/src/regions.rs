@@ -0,0 +1,439 @@
+//! Server Regions - Map a server (job ID) to the datacenter it runs in
+//!
+//! The server list has no location, but the game-join API hands a signed-in
+//! user the server's address. That address is looked up in an IP-range table
+//! the user imports (`server_regions.json` in the app data directory). Roblox
+//! publishes no such table, so none ships with the app: without one, or when
+//! no range matches, the region is "Unknown" and only the address is known.
+
+use crate::game_detection::ServerInfo;
+use crate::roblox::csrf_post;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const GAME_JOIN_API: &str = "https://gamejoin.roblox.com/v1/join-game-instance";
+/// User-provided IP-range table
+const TABLE_FILE: &str = "server_regions.json";
+/// Join requests in flight at once
+const MAX_CONCURRENT_LOOKUPS: usize = 4;
+/// Job IDs kept in the cache; the oldest make room beyond that
+const MAX_CACHED_SERVERS: usize = 5000;
+/// How long a resolved region is kept (servers rarely run longer)
+const REGION_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Datacenter a server runs in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerRegion {
+    /// Short code used for filtering (e.g. "US-LAX"), "??" when unknown
+    pub code: String,
+    pub name: String,
+    /// Public server address returned by the join API
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TableFile {
+    ranges: Vec<TableEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TableEntry {
+    cidr: String,
+    code: String,
+    name: String,
+}
+
+#[derive(Debug, Clone)]
+struct RegionRange {
+    network: u32,
+    prefix: u8,
+    code: String,
+    name: String,
+}
+
+impl RegionRange {
+    fn contains(&self, ip: Ipv4Addr) -> bool {
+        let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+        u32::from(ip) & mask == self.network & mask
+    }
+}
+
+/// IP-range → region lookup table
+#[derive(Debug, Clone, Default)]
+pub struct RegionTable {
+    ranges: Vec<RegionRange>,
+}
+
+impl RegionTable {
+    /// Parse a table in the `regions.json` format
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let file: TableFile =
+            serde_json::from_str(json).map_err(|e| format!("Invalid region table: {}", e))?;
+
+        let mut ranges = file
+            .ranges
+            .into_iter()
+            .map(|entry| {
+                let (addr, prefix) = entry.cidr.split_once('/').ok_or_else(|| {
+                    format!("Invalid CIDR '{}' in region table", entry.cidr)
+                })?;
+                let network: Ipv4Addr = addr
+                    .parse()
+                    .map_err(|_| format!("Invalid CIDR '{}' in region table", entry.cidr))?;
+                let prefix: u8 = prefix
+                    .parse()
+                    .ok()
+                    .filter(|p| *p <= 32)
+                    .ok_or_else(|| format!("Invalid CIDR '{}' in region table", entry.cidr))?;
+
+                Ok(RegionRange {
+                    network: u32::from(network),
+                    prefix,
+                    code: entry.code,
+                    name: entry.name,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        // Most specific range wins
+        ranges.sort_by_key(|r| std::cmp::Reverse(r.prefix));
+        Ok(Self { ranges })
+    }
+
+    /// The user's table from the app data directory (empty when none was imported)
+    pub fn load(app_data_dir: &Path) -> Self {
+        fs::read_to_string(table_path(app_data_dir))
+            .ok()
+            .and_then(|json| Self::parse(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Region for a server address ("??"/"Unknown" when no range matches)
+    pub fn lookup(&self, ip: Ipv4Addr) -> ServerRegion {
+        match self.ranges.iter().find(|r| r.contains(ip)) {
+            Some(range) => ServerRegion {
+                code: range.code.clone(),
+                name: range.name.clone(),
+                address: ip.to_string(),
+            },
+            None => ServerRegion {
+                code: "??".to_string(),
+                name: "Unknown".to_string(),
+                address: ip.to_string(),
+            },
+        }
+    }
+}
+
+fn table_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(TABLE_FILE)
+}
+
+/// Validate and install a region table. Returns the number of ranges.
+pub fn install_table(app_data_dir: &Path, json: &str) -> Result<usize, String> {
+    let table = RegionTable::parse(json)?;
+    fs::create_dir_all(app_data_dir).map_err(|e| e.to_string())?;
+    fs::write(table_path(app_data_dir), json).map_err(|e| e.to_string())?;
+    Ok(table.len())
+}
+
+/// Remove the imported table (every region is unknown again)
+pub fn reset_table(app_data_dir: &Path) -> Result<(), String> {
+    let path = table_path(app_data_dir);
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// CACHE
+// ============================================================================
+
+/// Regions already resolved, keyed by job ID (a server never moves). Entries
+/// expire after `REGION_TTL`; when full, the oldest make room for new ones.
+#[derive(Debug, Default)]
+pub struct RegionCache {
+    entries: HashMap<String, CachedRegion>,
+    /// Insertion counter, orders entries by age
+    inserted: u64,
+}
+
+#[derive(Debug)]
+struct CachedRegion {
+    region: ServerRegion,
+    resolved_at: Instant,
+    order: u64,
+}
+
+impl RegionCache {
+    pub fn get(&self, job_id: &str) -> Option<&ServerRegion> {
+        self.entries
+            .get(job_id)
+            .filter(|entry| entry.resolved_at.elapsed() < REGION_TTL)
+            .map(|entry| &entry.region)
+    }
+
+    pub fn insert(&mut self, job_id: String, region: ServerRegion) {
+        if self.entries.len() >= MAX_CACHED_SERVERS && !self.entries.contains_key(&job_id) {
+            self.entries
+                .retain(|_, entry| entry.resolved_at.elapsed() < REGION_TTL);
+            if self.entries.len() >= MAX_CACHED_SERVERS {
+                let oldest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.order)
+                    .map(|(id, _)| id.clone());
+                if let Some(oldest) = oldest {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+
+        self.inserted += 1;
+        let entry = CachedRegion {
+            region,
+            resolved_at: Instant::now(),
+            order: self.inserted,
+        };
+        self.entries.insert(job_id, entry);
+    }
+
+    /// Job IDs from `job_ids` that still need a lookup
+    pub fn missing(&self, job_ids: &[String]) -> Vec<String> {
+        job_ids
+            .iter()
+            .filter(|id| self.get(id).is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Fill in `region` on servers that were already resolved
+    pub fn annotate(&self, servers: &mut [ServerInfo]) {
+        for server in servers {
+            if server.region.is_none() {
+                server.region = self.get(&server.id).cloned();
+            }
+        }
+    }
+}
+
+// ============================================================================
+// GAME JOIN LOOKUP
+// ============================================================================
+
+/// Ask the join API (as the account behind `cookie`) where a server lives
+pub async fn fetch_server_address(
+    client: &reqwest::Client,
+    cookie: &str,
+    place_id: u64,
+    job_id: &str,
+) -> Result<Ipv4Addr, String> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct JoinResponse {
+        status: i32,
+        message: Option<String>,
+        join_script: Option<JoinScript>,
+    }
+
+    #[derive(Deserialize)]
+    struct JoinScript {
+        #[serde(rename = "UdmuxEndpoints", default)]
+        udmux_endpoints: Option<Vec<Endpoint>>,
+        #[serde(rename = "MachineAddress")]
+        machine_address: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct Endpoint {
+        #[serde(rename = "Address")]
+        address: String,
+    }
+
+    let body = serde_json::json!({
+        "placeId": place_id,
+        "gameId": job_id,
+        "gameJoinAttemptId": uuid::Uuid::new_v4().to_string(),
+        "isTeleport": false,
+    });
+
+    let response = csrf_post(client, GAME_JOIN_API, cookie, &body.to_string()).await?;
+    if !response.status().is_success() {
+        return Err(format!("Game join API error: {}", response.status()));
+    }
+
+    let join: JoinResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Game join parse error: {}", e))?;
+
+    // 2 = server found and joinable
+    if join.status != 2 {
+        return Err(join
+            .message
+            .unwrap_or_else(|| format!("Server not joinable (status {})", join.status)));
+    }
+
+    // The proxy (UDMUX) address is the public one; MachineAddress may be internal
+    let script = join.join_script.ok_or("Game join returned no join script")?;
+    let address = script
+        .udmux_endpoints
+        .and_then(|endpoints| endpoints.into_iter().next())
+        .map(|e| e.address)
+        .or(script.machine_address)
+        .ok_or("Game join returned no server address")?;
+
+    address
+        .parse()
+        .map_err(|_| format!("Unexpected server address '{}'", address))
+}
+
+/// Resolve the region of every job ID (a few at a time). Servers that can't
+/// be looked up (full, closed, private) are left out of the result.
+pub async fn resolve_regions(
+    table: &RegionTable,
+    cookie: &str,
+    place_id: u64,
+    job_ids: Vec<String>,
+) -> HashMap<String, ServerRegion> {
+    let client = reqwest::Client::builder()
+        .user_agent("Roblox/WinInet")
+        .build()
+        .unwrap_or_default();
+    let limit = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_LOOKUPS));
+    let mut lookups = tokio::task::JoinSet::new();
+
+    for job_id in job_ids {
+        let client = client.clone();
+        let cookie = cookie.to_string();
+        let limit = limit.clone();
+        lookups.spawn(async move {
+            let _permit = limit.acquire_owned().await.ok()?;
+            match fetch_server_address(&client, &cookie, place_id, &job_id).await {
+                Ok(ip) => Some((job_id, ip)),
+                Err(e) => {
+                    log::debug!("Region lookup for {} failed: {}", job_id, e);
+                    None
+                }
+            }
+        });
+    }
+
+    let mut regions = HashMap::new();
+    while let Some(result) = lookups.join_next().await {
+        if let Ok(Some((job_id, ip))) = result {
+            regions.insert(job_id, table.lookup(ip));
+        }
+    }
+    regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{server, temp_dir};
+
+    #[test]
+    fn test_no_table_is_unknown() {
+        let table = RegionTable::default();
+        assert!(table.is_empty());
+
+        let region = table.lookup("128.116.5.20".parse().unwrap());
+        assert_eq!((region.code.as_str(), region.name.as_str()), ("??", "Unknown"));
+        assert_eq!(region.address, "128.116.5.20");
+    }
+
+    #[test]
+    fn test_most_specific_range_wins() {
+        let table = RegionTable::parse(
+            r#"{ "ranges": [
+                { "cidr": "10.0.0.0/8", "code": "WIDE", "name": "Wide" },
+                { "cidr": "10.1.2.0/24", "code": "NARROW", "name": "Narrow" }
+            ] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(table.lookup("10.1.2.77".parse().unwrap()).code, "NARROW");
+        assert_eq!(table.lookup("10.9.9.9".parse().unwrap()).code, "WIDE");
+
+        let unknown = table.lookup("192.0.2.1".parse().unwrap());
+        assert_eq!(unknown.code, "??");
+        assert_eq!(unknown.address, "192.0.2.1");
+    }
+
+    #[test]
+    fn test_invalid_tables_rejected() {
+        assert!(RegionTable::parse("{}").is_err());
+        assert!(RegionTable::parse(
+            r#"{ "ranges": [{ "cidr": "10.0.0.0/40", "code": "X", "name": "X" }] }"#
+        )
+        .is_err());
+        assert!(RegionTable::parse(
+            r#"{ "ranges": [{ "cidr": "not-an-ip", "code": "X", "name": "X" }] }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_install_and_reset_override() {
+        let dir = temp_dir("regions");
+        let custom = r#"{ "ranges": [{ "cidr": "0.0.0.0/0", "code": "ANY", "name": "Anywhere" }] }"#;
+
+        assert!(install_table(&dir, "{ broken").is_err());
+        assert_eq!(install_table(&dir, custom).unwrap(), 1);
+        let ip = "203.0.113.9".parse().unwrap();
+        assert_eq!(RegionTable::load(&dir).lookup(ip).code, "ANY");
+
+        reset_table(&dir).unwrap();
+        assert!(RegionTable::load(&dir).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cache_annotates_servers() {
+        let mut cache = RegionCache::default();
+        let region = RegionTable::default().lookup("203.0.113.9".parse().unwrap());
+        cache.insert("job-a".to_string(), region.clone());
+
+        let mut servers = vec![server("job-a", 1, 10), server("job-b", 1, 10)];
+        cache.annotate(&mut servers);
+
+        assert_eq!(servers[0].region, Some(region));
+        assert_eq!(servers[1].region, None);
+        assert_eq!(
+            cache.missing(&["job-a".to_string(), "job-b".to_string()]),
+            vec!["job-b".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_full_cache_drops_oldest_only() {
+        let mut cache = RegionCache::default();
+        let region = RegionTable::default().lookup("203.0.113.9".parse().unwrap());
+        for i in 0..MAX_CACHED_SERVERS {
+            cache.insert(format!("job-{}", i), region.clone());
+        }
+        cache.insert("job-new".to_string(), region.clone());
+
+        assert!(cache.get("job-0").is_none());
+        assert!(cache.get("job-1").is_some());
+        assert!(cache.get("job-new").is_some());
+        assert_eq!(cache.entries.len(), MAX_CACHED_SERVERS);
+    }
+}
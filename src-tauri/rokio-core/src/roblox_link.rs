@@ -0,0 +1,597 @@
+//! ROKIO Roblox Link Module
+//! Parses and builds every join link format we deal with.
+//!
+//! Supported inputs:
+//! - `123456789` (raw place ID)
+//! - `https://www.roblox.com/games/123456789/Game-Name`
+//! - `https://www.roblox.com/games/123456789?privateServerLinkCode=...`
+//! - `https://www.roblox.com/games/start?placeId=...&gameInstanceId=...`
+//! - `https://www.roblox.com/share?code=...&type=Server`
+//! - `roblox://experiences/start?placeId=...&gameInstanceId=|accessCode=|linkCode=|launchData=`
+//! - `roblox://experiences/start?userId=...` (follow user)
+//! - `roblox://placeId=...&linkCode=...` (legacy form)
+//! - `roblox-player:1+launchmode:play+...+placelauncherurl:...`
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const ROBLOX_WEB: &str = "https://www.roblox.com";
+const PLACE_LAUNCHER_URL: &str = "https://assetgame.roblox.com/game/PlaceLauncher.ashx";
+
+/// A parsed Roblox join target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum RobloxLink {
+    /// Join any public server of a place
+    Place {
+        place_id: u64,
+        launch_data: Option<String>,
+    },
+    /// Join a specific public server (job ID)
+    Server { place_id: u64, job_id: String },
+    /// Join a private server through its link code
+    PrivateServer { place_id: u64, link_code: String },
+    /// Join a reserved/private server through its access code
+    ReservedServer { place_id: u64, access_code: String },
+    /// Unresolved `roblox.com/share` link
+    Share { code: String, link_type: String },
+    /// Join whatever server a user is currently in
+    FollowUser { user_id: u64 },
+}
+
+impl RobloxLink {
+    /// Parse any supported link (or a raw place ID)
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+
+        if let Ok(place_id) = input.parse::<u64>() {
+            return Ok(RobloxLink::Place {
+                place_id,
+                launch_data: None,
+            });
+        }
+
+        let lower = input.to_ascii_lowercase();
+        if lower.starts_with("roblox-player:") {
+            return parse_player_uri(input);
+        }
+        if lower.starts_with("roblox://") {
+            return parse_deep_link(&input["roblox://".len()..]);
+        }
+        if let Some(rest) = roblox_web_path(input) {
+            return parse_web_url(input, rest);
+        }
+
+        Err(format!("Unrecognized Roblox link: {}", input))
+    }
+
+    /// Place launch, or a specific server when a job ID is given
+    pub fn from_parts(place_id: u64, job_id: Option<String>) -> Self {
+        match job_id {
+            Some(job_id) => RobloxLink::Server { place_id, job_id },
+            None => RobloxLink::Place {
+                place_id,
+                launch_data: None,
+            },
+        }
+    }
+
+    /// Place the link points at (not known for share links and follow-user links)
+    pub fn place_id(&self) -> Option<u64> {
+        match self {
+            RobloxLink::Place { place_id, .. }
+            | RobloxLink::Server { place_id, .. }
+            | RobloxLink::PrivateServer { place_id, .. }
+            | RobloxLink::ReservedServer { place_id, .. } => Some(*place_id),
+            RobloxLink::Share { .. } | RobloxLink::FollowUser { .. } => None,
+        }
+    }
+
+    /// Specific server job ID, if the link targets one
+    pub fn job_id(&self) -> Option<&str> {
+        match self {
+            RobloxLink::Server { job_id, .. } => Some(job_id),
+            _ => None,
+        }
+    }
+
+    /// Short name of the target type (recorded in launch history)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RobloxLink::Place { .. } => "place",
+            RobloxLink::Server { .. } => "server",
+            RobloxLink::PrivateServer { .. } => "privateServer",
+            RobloxLink::ReservedServer { .. } => "reservedServer",
+            RobloxLink::Share { .. } => "share",
+            RobloxLink::FollowUser { .. } => "followUser",
+        }
+    }
+
+    /// `roblox://experiences/start?...` deep link.
+    /// Share links have no deep link until they are resolved.
+    pub fn to_deep_link(&self) -> Option<String> {
+        let query = match self {
+            RobloxLink::Place {
+                place_id,
+                launch_data,
+            } => with_launch_data(format!("placeId={}", place_id), launch_data),
+            RobloxLink::Server { place_id, job_id } => {
+                format!("placeId={}&gameInstanceId={}", place_id, encode(job_id))
+            }
+            RobloxLink::PrivateServer {
+                place_id,
+                link_code,
+            } => format!("placeId={}&linkCode={}", place_id, encode(link_code)),
+            RobloxLink::ReservedServer {
+                place_id,
+                access_code,
+            } => format!("placeId={}&accessCode={}", place_id, encode(access_code)),
+            RobloxLink::FollowUser { user_id } => format!("userId={}", user_id),
+            RobloxLink::Share { .. } => return None,
+        };
+
+        Some(format!("roblox://experiences/start?{}", query))
+    }
+
+    /// `https://www.roblox.com/...` web URL
+    pub fn to_web_url(&self) -> String {
+        match self {
+            RobloxLink::Place {
+                place_id,
+                launch_data: None,
+            } => format!("{}/games/{}", ROBLOX_WEB, place_id),
+            RobloxLink::Place {
+                place_id,
+                launch_data,
+            } => format!(
+                "{}/games/start?{}",
+                ROBLOX_WEB,
+                with_launch_data(format!("placeId={}", place_id), launch_data)
+            ),
+            RobloxLink::Server { place_id, job_id } => format!(
+                "{}/games/start?placeId={}&gameInstanceId={}",
+                ROBLOX_WEB,
+                place_id,
+                encode(job_id)
+            ),
+            RobloxLink::PrivateServer {
+                place_id,
+                link_code,
+            } => format!(
+                "{}/games/{}?privateServerLinkCode={}",
+                ROBLOX_WEB,
+                place_id,
+                encode(link_code)
+            ),
+            RobloxLink::ReservedServer {
+                place_id,
+                access_code,
+            } => format!(
+                "{}/games/start?placeId={}&accessCode={}",
+                ROBLOX_WEB,
+                place_id,
+                encode(access_code)
+            ),
+            RobloxLink::Share { code, link_type } => format!(
+                "{}/share?code={}&type={}",
+                ROBLOX_WEB,
+                encode(code),
+                encode(link_type)
+            ),
+            RobloxLink::FollowUser { user_id } => {
+                format!("{}/games/start?userId={}", ROBLOX_WEB, user_id)
+            }
+        }
+    }
+
+    /// `PlaceLauncher.ashx` request URL used inside `roblox-player:` URIs
+    pub fn to_place_launcher_url(&self) -> Option<String> {
+        let query = match self {
+            RobloxLink::Place {
+                place_id,
+                launch_data,
+            } => with_launch_data(
+                format!("request=RequestGame&placeId={}", place_id),
+                launch_data,
+            ),
+            RobloxLink::Server { place_id, job_id } => format!(
+                "request=RequestGameJob&placeId={}&gameId={}",
+                place_id,
+                encode(job_id)
+            ),
+            RobloxLink::PrivateServer {
+                place_id,
+                link_code,
+            } => format!(
+                "request=RequestPrivateGame&placeId={}&linkCode={}",
+                place_id,
+                encode(link_code)
+            ),
+            RobloxLink::ReservedServer {
+                place_id,
+                access_code,
+            } => format!(
+                "request=RequestPrivateGame&placeId={}&accessCode={}",
+                place_id,
+                encode(access_code)
+            ),
+            RobloxLink::FollowUser { user_id } => {
+                format!("request=RequestFollowUser&userId={}", user_id)
+            }
+            RobloxLink::Share { .. } => return None,
+        };
+
+        Some(format!("{}?{}", PLACE_LAUNCHER_URL, query))
+    }
+
+    /// `roblox-player:` launch URI carrying an authentication ticket
+    pub fn to_player_uri(&self, auth_ticket: &str, launch_time_ms: u64) -> Option<String> {
+        let launcher_url = self.to_place_launcher_url()?;
+        Some(format!(
+            "roblox-player:1+launchmode:play+gameinfo:{}+launchtime:{}+placelauncherurl:{}",
+            auth_ticket,
+            launch_time_ms,
+            encode(&launcher_url)
+        ))
+    }
+}
+
+// ============================================================================
+// PARSERS
+// ============================================================================
+
+/// Parse the part after `roblox://`
+fn parse_deep_link(rest: &str) -> Result<RobloxLink, String> {
+    // roblox://experiences/start?placeId=... or legacy roblox://placeId=...
+    let query = match rest.split_once('?') {
+        Some((path, query)) if path.eq_ignore_ascii_case("experiences/start") => query,
+        Some(_) => return Err(format!("Unsupported deep link: roblox://{}", rest)),
+        None => rest,
+    };
+
+    from_query(&parse_query(query))
+        .ok_or_else(|| format!("Deep link has no place or user: roblox://{}", rest))
+}
+
+/// Parse a `roblox-player:` URI by decoding its `placelauncherurl` component
+fn parse_player_uri(uri: &str) -> Result<RobloxLink, String> {
+    let launcher_url = uri
+        .split('+')
+        .find_map(|part| {
+            let (key, value) = part.split_once(':')?;
+            key.eq_ignore_ascii_case("placelauncherurl").then_some(value)
+        })
+        .ok_or("roblox-player URI has no placelauncherurl")?;
+
+    let launcher_url = decode(launcher_url);
+    let query = launcher_url
+        .split_once('?')
+        .map(|(_, q)| q)
+        .ok_or("placelauncherurl has no query string")?;
+    let params = parse_query(query);
+
+    let place_id = params.get("placeid").and_then(|v| v.parse::<u64>().ok());
+    let request = params.get("request").map(|r| r.to_ascii_lowercase());
+
+    let link = match (request.as_deref(), place_id) {
+        (Some("requestfollowuser"), _) => params
+            .get("userid")
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|user_id| RobloxLink::FollowUser { user_id }),
+        (Some("requestgamejob"), Some(place_id)) => {
+            params.get("gameid").map(|job_id| RobloxLink::Server {
+                place_id,
+                job_id: job_id.clone(),
+            })
+        }
+        (Some("requestprivategame"), Some(place_id)) => {
+            if let Some(link_code) = params.get("linkcode") {
+                Some(RobloxLink::PrivateServer {
+                    place_id,
+                    link_code: link_code.clone(),
+                })
+            } else {
+                params
+                    .get("accesscode")
+                    .map(|access_code| RobloxLink::ReservedServer {
+                        place_id,
+                        access_code: access_code.clone(),
+                    })
+            }
+        }
+        (_, Some(place_id)) => Some(RobloxLink::Place {
+            place_id,
+            launch_data: params.get("launchdata").cloned(),
+        }),
+        _ => None,
+    };
+
+    link.ok_or_else(|| "Unsupported roblox-player launch request".to_string())
+}
+
+/// Whether a host or cookie domain belongs to Roblox (`roblox.com` or a subdomain)
+pub(crate) fn is_roblox_domain(domain: &str) -> bool {
+    let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+    domain == "roblox.com" || domain.ends_with(".roblox.com")
+}
+
+// Helper: Path and query of a web URL (scheme optional) whose host is a Roblox
+// domain, `None` for any other host
+fn roblox_web_path(input: &str) -> Option<&str> {
+    let lower = input.to_ascii_lowercase();
+    let scheme_len = ["https://", "http://"]
+        .iter()
+        .find(|scheme| lower.starts_with(*scheme))
+        .map_or(0, |scheme| scheme.len());
+    let rest = &input[scheme_len..];
+
+    let authority_len = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let authority = &rest[..authority_len];
+    // Drop user info and port: "user@host:443"
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let host = host.split(':').next().unwrap_or(host);
+
+    is_roblox_domain(host).then_some(&rest[authority_len..])
+}
+
+/// Parse a `roblox.com` web URL, given its path and query
+fn parse_web_url(url: &str, path_and_query: &str) -> Result<RobloxLink, String> {
+    let without_fragment = path_and_query.split('#').next().unwrap_or(path_and_query);
+    let (path, query) = without_fragment
+        .split_once('?')
+        .unwrap_or((without_fragment, ""));
+    let params = parse_query(query);
+
+    let path = path.trim_end_matches('/');
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match segments.as_slice() {
+        // roblox.com/share?code=...&type=Server (also /share-links)
+        [share] if share.eq_ignore_ascii_case("share") || share.eq_ignore_ascii_case("share-links") => {
+            let code = params.get("code").ok_or("Share link has no code")?;
+            Ok(RobloxLink::Share {
+                code: code.clone(),
+                link_type: params
+                    .get("type")
+                    .cloned()
+                    .unwrap_or_else(|| "Server".to_string()),
+            })
+        }
+        // roblox.com/games/start?placeId=...
+        [games, start, ..]
+            if games.eq_ignore_ascii_case("games") && start.eq_ignore_ascii_case("start") =>
+        {
+            from_query(&params).ok_or_else(|| format!("Game link has no place or user: {}", url))
+        }
+        // roblox.com/games/123456789/Game-Name?privateServerLinkCode=...
+        [games, id, ..] if games.eq_ignore_ascii_case("games") => {
+            let place_id = id
+                .parse::<u64>()
+                .map_err(|_| format!("Invalid place ID in link: {}", id))?;
+
+            if let Some(link_code) = params.get("privateserverlinkcode") {
+                return Ok(RobloxLink::PrivateServer {
+                    place_id,
+                    link_code: link_code.clone(),
+                });
+            }
+            if let Some(job_id) = params.get("gameinstanceid") {
+                return Ok(RobloxLink::Server {
+                    place_id,
+                    job_id: job_id.clone(),
+                });
+            }
+            Ok(RobloxLink::Place {
+                place_id,
+                launch_data: params.get("launchdata").cloned(),
+            })
+        }
+        _ => Err(format!("Unsupported Roblox URL: {}", url)),
+    }
+}
+
+/// Build a link from `placeId`/`gameInstanceId`/`linkCode`/`accessCode`/`userId` params
+fn from_query(params: &HashMap<String, String>) -> Option<RobloxLink> {
+    let place_id = params.get("placeid").and_then(|v| v.parse::<u64>().ok());
+
+    let Some(place_id) = place_id else {
+        return params
+            .get("userid")
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|user_id| RobloxLink::FollowUser { user_id });
+    };
+
+    let link = if let Some(job_id) = params.get("gameinstanceid") {
+        RobloxLink::Server {
+            place_id,
+            job_id: job_id.clone(),
+        }
+    } else if let Some(link_code) = params
+        .get("linkcode")
+        .or_else(|| params.get("privateserverlinkcode"))
+    {
+        RobloxLink::PrivateServer {
+            place_id,
+            link_code: link_code.clone(),
+        }
+    } else if let Some(access_code) = params.get("accesscode") {
+        RobloxLink::ReservedServer {
+            place_id,
+            access_code: access_code.clone(),
+        }
+    } else {
+        RobloxLink::Place {
+            place_id,
+            launch_data: params.get("launchdata").cloned(),
+        }
+    };
+
+    Some(link)
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Parse `a=1&b=2` into a map with lowercased keys and decoded values
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let value = decode(value);
+            (!value.is_empty()).then(|| (key.to_ascii_lowercase(), value))
+        })
+        .collect()
+}
+
+fn with_launch_data(query: String, launch_data: &Option<String>) -> String {
+    match launch_data {
+        Some(data) => format!("{}&launchData={}", query, encode(data)),
+        None => query,
+    }
+}
+
+fn encode(value: &str) -> String {
+    urlencoding::encode(value).into_owned()
+}
+
+fn decode(value: &str) -> String {
+    urlencoding::decode(value)
+        .map(|v| v.into_owned())
+        .unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_forms() -> Vec<RobloxLink> {
+        vec![
+            RobloxLink::Place {
+                place_id: 606849621,
+                launch_data: None,
+            },
+            RobloxLink::Place {
+                place_id: 606849621,
+                launch_data: Some("{\"team\":\"red\"}".to_string()),
+            },
+            RobloxLink::Server {
+                place_id: 606849621,
+                job_id: "0a1b2c3d-4e5f-6789-abcd-ef0123456789".to_string(),
+            },
+            RobloxLink::PrivateServer {
+                place_id: 606849621,
+                link_code: "12345678901234567890".to_string(),
+            },
+            RobloxLink::ReservedServer {
+                place_id: 606849621,
+                access_code: "c0ffee00-aaaa-bbbb-cccc-123456789abc".to_string(),
+            },
+            RobloxLink::FollowUser { user_id: 156 },
+        ]
+    }
+
+    #[test]
+    fn test_parse_web_urls() {
+        assert_eq!(
+            RobloxLink::parse("https://www.roblox.com/games/606849621/Jailbreak").unwrap(),
+            RobloxLink::Place {
+                place_id: 606849621,
+                launch_data: None
+            }
+        );
+        assert_eq!(
+            RobloxLink::parse(
+                "https://www.roblox.com/games/606849621/Jailbreak?privateServerLinkCode=4242"
+            )
+            .unwrap(),
+            RobloxLink::PrivateServer {
+                place_id: 606849621,
+                link_code: "4242".to_string()
+            }
+        );
+        assert_eq!(
+            RobloxLink::parse("https://www.roblox.com/share?code=abcdef&type=Server").unwrap(),
+            RobloxLink::Share {
+                code: "abcdef".to_string(),
+                link_type: "Server".to_string()
+            }
+        );
+        assert!(RobloxLink::parse("roblox.com/games/606849621").is_ok());
+    }
+
+    #[test]
+    fn test_rejects_other_hosts() {
+        for url in [
+            "https://evilroblox.com/games/606849621",
+            "https://x.com/?r=roblox.com/games/606849621",
+            "https://www.roblox.com@evil.com/games/606849621",
+            "https://roblox.com.evil.com/games/606849621",
+        ] {
+            assert!(RobloxLink::parse(url).is_err(), "{}", url);
+        }
+        assert!(RobloxLink::parse("https://web.roblox.com:443/games/606849621").is_ok());
+    }
+
+    #[test]
+    fn test_parse_deep_links() {
+        assert_eq!(
+            RobloxLink::parse("roblox://placeId=123&linkCode=999").unwrap(),
+            RobloxLink::PrivateServer {
+                place_id: 123,
+                link_code: "999".to_string()
+            }
+        );
+        assert_eq!(
+            RobloxLink::parse("roblox://experiences/start?placeId=123&gameInstanceId=abc")
+                .unwrap(),
+            RobloxLink::Server {
+                place_id: 123,
+                job_id: "abc".to_string()
+            }
+        );
+        assert_eq!(
+            RobloxLink::parse("roblox://experiences/start?userId=156").unwrap(),
+            RobloxLink::FollowUser { user_id: 156 }
+        );
+        assert!(RobloxLink::parse("roblox://navigation/home").is_err());
+        assert!(RobloxLink::parse("not a link").is_err());
+    }
+
+    #[test]
+    fn test_parse_player_uri() {
+        let uri = "roblox-player:1+launchmode:play+gameinfo:TICKET+launchtime:1700000000000\
+                   +placelauncherurl:https%3A%2F%2Fassetgame.roblox.com%2Fgame%2FPlaceLauncher.ashx\
+                   %3Frequest%3DRequestGameJob%26browserTrackerId%3D1%26placeId%3D123%26gameId%3Dabc\
+                   +browsertrackerid:1+robloxLocale:en_us+gameLocale:en_us";
+        assert_eq!(
+            RobloxLink::parse(uri).unwrap(),
+            RobloxLink::Server {
+                place_id: 123,
+                job_id: "abc".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_round_trips() {
+        for link in all_forms() {
+            let deep_link = link.to_deep_link().unwrap();
+            assert_eq!(RobloxLink::parse(&deep_link).unwrap(), link, "{}", deep_link);
+
+            let web_url = link.to_web_url();
+            assert_eq!(RobloxLink::parse(&web_url).unwrap(), link, "{}", web_url);
+
+            let player_uri = link.to_player_uri("TICKET", 1_700_000_000_000).unwrap();
+            assert_eq!(RobloxLink::parse(&player_uri).unwrap(), link, "{}", player_uri);
+        }
+
+        let share = RobloxLink::Share {
+            code: "abc".to_string(),
+            link_type: "Server".to_string(),
+        };
+        assert_eq!(RobloxLink::parse(&share.to_web_url()).unwrap(), share);
+        assert_eq!(share.to_deep_link(), None);
+    }
+}
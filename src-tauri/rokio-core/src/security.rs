@@ -0,0 +1,303 @@
+//! ROKIO Security Audit - How well each account is protected
+//!
+//! Checks email, 2-step verification, phone, account PIN and age bracket
+//! through the account's own session, then flags what is missing so weakly
+//! protected alts stand out. Reports export to CSV.
+
+use crate::profiles::Profile;
+use crate::roblox::send_authenticated;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const ROBLOX_EMAIL_API: &str = "https://accountsettings.roblox.com/v1/email";
+const ROBLOX_PHONE_API: &str = "https://accountinformation.roblox.com/v1/phone";
+const ROBLOX_PIN_API: &str = "https://auth.roblox.com/v1/account/pin";
+const ROBLOX_AGE_BRACKET_API: &str = "https://users.roblox.com/v1/users/authenticated/age-bracket";
+const ROBLOX_TWO_STEP_API: &str = "https://twostepverification.roblox.com/v1/users";
+
+/// Accounts checked at the same time (each check makes 5 requests)
+const MAX_CONCURRENT_AUDITS: usize = 4;
+
+/// Security posture of one account. `None` means the check failed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityReport {
+    pub account_id: String,
+    pub user_id: i64,
+    pub username: String,
+    pub email_set: Option<bool>,
+    pub email_verified: Option<bool>,
+    pub two_step_enabled: Option<bool>,
+    /// Primary 2SV method ("Email", "Authenticator", "SecurityKey", ...)
+    pub two_step_type: Option<String>,
+    pub phone_verified: Option<bool>,
+    pub pin_enabled: Option<bool>,
+    /// "13+" or "Under 13"
+    pub age_bracket: Option<String>,
+    /// Missing protections, empty when the account looks well protected
+    #[serde(default)]
+    pub issues: Vec<String>,
+    /// Checks that could not be made
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EmailResponse {
+    email_address: Option<String>,
+    #[serde(default)]
+    verified: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PhoneResponse {
+    phone: Option<String>,
+    #[serde(default)]
+    is_verified: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PinResponse {
+    #[serde(default)]
+    is_enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AgeBracketResponse {
+    age_bracket: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TwoStepResponse {
+    primary_media_type: Option<String>,
+    #[serde(default)]
+    methods: Vec<TwoStepMethod>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TwoStepMethod {
+    media_type: String,
+    #[serde(default)]
+    enabled: bool,
+}
+
+// Helper: Authenticated GET returning JSON
+async fn get_json<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+    cookie: &str,
+) -> Result<T, String> {
+    let response = send_authenticated(client.get(url), cookie)
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Roblox API error: {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+impl SecurityReport {
+    /// What a careful owner would want turned on
+    fn find_issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        if self.email_set == Some(false) {
+            issues.push("No email".to_string());
+        } else if self.email_verified == Some(false) {
+            issues.push("Email not verified".to_string());
+        }
+        if self.two_step_enabled == Some(false) {
+            issues.push("2-step verification off".to_string());
+        }
+        if self.phone_verified == Some(false) {
+            issues.push("No verified phone".to_string());
+        }
+        if self.pin_enabled == Some(false) {
+            issues.push("No account PIN".to_string());
+        }
+        issues
+    }
+}
+
+/// Check one account through its session
+pub async fn audit_account(account: &Profile) -> SecurityReport {
+    let client = reqwest::Client::new();
+    let cookie = account.cookie.as_str();
+    let two_step_url = format!("{}/{}/configuration", ROBLOX_TWO_STEP_API, account.user_id);
+
+    let (email, phone, pin, age, two_step) = tokio::join!(
+        get_json::<EmailResponse>(&client, ROBLOX_EMAIL_API, cookie),
+        get_json::<PhoneResponse>(&client, ROBLOX_PHONE_API, cookie),
+        get_json::<PinResponse>(&client, ROBLOX_PIN_API, cookie),
+        get_json::<AgeBracketResponse>(&client, ROBLOX_AGE_BRACKET_API, cookie),
+        get_json::<TwoStepResponse>(&client, &two_step_url, cookie),
+    );
+
+    let mut failed = Vec::new();
+    let mut report = SecurityReport {
+        account_id: account.id.clone(),
+        user_id: account.user_id,
+        username: account.username.clone(),
+        ..Default::default()
+    };
+
+    match email {
+        Ok(e) => {
+            report.email_set = Some(e.email_address.is_some_and(|a| !a.is_empty()));
+            report.email_verified = Some(e.verified);
+        }
+        Err(_) => failed.push("email"),
+    }
+    match phone {
+        Ok(p) => report.phone_verified = Some(p.phone.is_some() && p.is_verified),
+        Err(_) => failed.push("phone"),
+    }
+    match pin {
+        Ok(p) => report.pin_enabled = Some(p.is_enabled),
+        Err(_) => failed.push("PIN"),
+    }
+    match age {
+        Ok(a) => {
+            report.age_bracket = Some(match a.age_bracket {
+                1 => "Under 13".to_string(),
+                _ => "13+".to_string(),
+            })
+        }
+        Err(_) => failed.push("age bracket"),
+    }
+    match two_step {
+        Ok(t) => {
+            let enabled: Vec<String> = t
+                .methods
+                .into_iter()
+                .filter(|m| m.enabled)
+                .map(|m| m.media_type)
+                .collect();
+            report.two_step_enabled = Some(!enabled.is_empty());
+            report.two_step_type = t
+                .primary_media_type
+                .filter(|_| !enabled.is_empty())
+                .or_else(|| enabled.first().cloned());
+        }
+        Err(_) => failed.push("2-step verification"),
+    }
+
+    if !failed.is_empty() {
+        report.error = Some(format!("Could not check {}", failed.join(", ")));
+    }
+    report.issues = report.find_issues();
+    report
+}
+
+/// Check every account (a few at a time), in the order given
+pub async fn audit_accounts(accounts: Vec<Profile>) -> Vec<SecurityReport> {
+    let limit = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_AUDITS));
+    let mut audits = tokio::task::JoinSet::new();
+
+    for (index, account) in accounts.into_iter().enumerate() {
+        let limit = limit.clone();
+        audits.spawn(async move {
+            let _permit = limit.acquire_owned().await.ok();
+            (index, audit_account(&account).await)
+        });
+    }
+
+    let mut reports = Vec::new();
+    while let Some(result) = audits.join_next().await {
+        if let Ok(report) = result {
+            reports.push(report);
+        }
+    }
+    reports.sort_by_key(|(index, _)| *index);
+    reports.into_iter().map(|(_, report)| report).collect()
+}
+
+// Helper: Quote a CSV field when needed
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Helper: "yes"/"no", empty when unknown
+fn yes_no(value: Option<bool>) -> &'static str {
+    match value {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "",
+    }
+}
+
+/// Reports as CSV, one row per account
+pub fn reports_to_csv(reports: &[SecurityReport]) -> String {
+    let mut csv = String::from(
+        "Account ID,User ID,Username,Email set,Email verified,2-step verification,\
+         2-step type,Phone verified,PIN,Age bracket,Issues,Error\n",
+    );
+    for r in reports {
+        let row = [
+            csv_field(&r.account_id),
+            r.user_id.to_string(),
+            csv_field(&r.username),
+            yes_no(r.email_set).to_string(),
+            yes_no(r.email_verified).to_string(),
+            yes_no(r.two_step_enabled).to_string(),
+            csv_field(r.two_step_type.as_deref().unwrap_or_default()),
+            yes_no(r.phone_verified).to_string(),
+            yes_no(r.pin_enabled).to_string(),
+            csv_field(r.age_bracket.as_deref().unwrap_or_default()),
+            csv_field(&r.issues.join("; ")),
+            csv_field(r.error.as_deref().unwrap_or_default()),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issues_and_csv() {
+        let mut report = SecurityReport {
+            account_id: "acc-1".to_string(),
+            user_id: 1,
+            username: "user1".to_string(),
+            email_set: Some(true),
+            email_verified: Some(false),
+            two_step_enabled: Some(true),
+            two_step_type: Some("Authenticator".to_string()),
+            phone_verified: Some(false),
+            pin_enabled: None,
+            age_bracket: Some("13+".to_string()),
+            error: Some("Could not check PIN".to_string()),
+            ..Default::default()
+        };
+        report.issues = report.find_issues();
+        assert_eq!(
+            report.issues,
+            vec!["Email not verified", "No verified phone"]
+        );
+
+        let csv = reports_to_csv(&[report]);
+        let row = csv.lines().nth(1).unwrap();
+        assert_eq!(
+            row,
+            "acc-1,1,user1,yes,no,yes,Authenticator,no,,13+,Email not verified; No verified phone,Could not check PIN"
+        );
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
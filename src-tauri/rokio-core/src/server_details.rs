@@ -0,0 +1,246 @@
+//! Server Details - Who is in a specific server
+//!
+//! The public server list only carries opaque player tokens. They are turned
+//! into avatar headshots through the thumbnails batch endpoint, and vault
+//! accounts are matched against the server through their own presence.
+
+use crate::game_detection::{fetch_server_page, ServerInfo, SortOrder};
+use crate::profiles::Profile;
+use crate::roblox::get_own_presence;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const THUMBNAIL_BATCH_API: &str = "https://thumbnails.roblox.com/v1/batch";
+/// Requests per thumbnails batch call (API limit)
+const THUMBNAIL_BATCH_SIZE: usize = 100;
+/// Server list pages to search for the job before giving up
+const DETAILS_MAX_PAGES: usize = 10;
+/// Presence lookups in flight at once
+const MAX_CONCURRENT_PRESENCE: usize = 4;
+
+/// A player in the server (the API does not reveal who)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerPlayer {
+    pub token: String,
+    pub headshot: Option<String>,
+}
+
+/// A vault account that is currently in the server
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnAccountInServer {
+    pub account_id: String,
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: String,
+}
+
+/// Everything known about one server
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerDetails {
+    pub place_id: u64,
+    pub server: ServerInfo,
+    pub players: Vec<ServerPlayer>,
+    pub own_accounts: Vec<OwnAccountInServer>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HeadshotRequest<'a> {
+    request_id: &'a str,
+    token: &'a str,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    size: &'static str,
+    format: &'static str,
+    is_circular: bool,
+}
+
+fn headshot_requests(tokens: &[String]) -> Vec<HeadshotRequest<'_>> {
+    tokens
+        .iter()
+        .map(|token| HeadshotRequest {
+            request_id: token,
+            token,
+            kind: "AvatarHeadShot",
+            size: "150x150",
+            format: "png",
+            is_circular: false,
+        })
+        .collect()
+}
+
+/// Resolve player tokens to headshot URLs (in the order of `tokens`)
+pub async fn get_token_headshots(
+    client: &reqwest::Client,
+    tokens: &[String],
+) -> Result<Vec<ServerPlayer>, String> {
+    #[derive(Deserialize)]
+    struct BatchResponse {
+        data: Vec<BatchItem>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct BatchItem {
+        request_id: String,
+        image_url: Option<String>,
+    }
+
+    let mut urls = std::collections::HashMap::new();
+    for chunk in tokens.chunks(THUMBNAIL_BATCH_SIZE) {
+        let response = client
+            .post(THUMBNAIL_BATCH_API)
+            .json(&headshot_requests(chunk))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch headshots: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("API error: {}", response.status()));
+        }
+
+        let data: BatchResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Parse error: {}", e))?;
+        for item in data.data {
+            if let Some(url) = item.image_url {
+                urls.insert(item.request_id, url);
+            }
+        }
+    }
+
+    Ok(tokens
+        .iter()
+        .map(|token| ServerPlayer {
+            token: token.clone(),
+            headshot: urls.remove(token),
+        })
+        .collect())
+}
+
+/// Find a server by job ID in the public list
+async fn find_server(
+    client: &reqwest::Client,
+    place_id: u64,
+    job_id: &str,
+) -> Result<ServerInfo, String> {
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..DETAILS_MAX_PAGES {
+        let page = fetch_server_page(client, place_id, SortOrder::Desc, cursor.as_deref()).await?;
+        if let Some(server) = page.servers.into_iter().find(|s| s.id == job_id) {
+            return Ok(server);
+        }
+        match page.next_page_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    Err("Server not found (it may have closed or be private)".to_string())
+}
+
+/// Vault accounts whose presence puts them in `job_id`
+pub async fn accounts_in_server(accounts: &[Profile], job_id: &str) -> Vec<OwnAccountInServer> {
+    let limit = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_PRESENCE));
+    let mut lookups = tokio::task::JoinSet::new();
+
+    for account in accounts {
+        let account = account.clone();
+        let limit = limit.clone();
+        lookups.spawn(async move {
+            let _permit = limit.acquire_owned().await.ok()?;
+            match get_own_presence(&account.cookie, account.user_id).await {
+                Ok(presence) => Some((account, presence.game_id)),
+                Err(e) => {
+                    log::debug!("Presence for {} failed: {}", account.username, e);
+                    None
+                }
+            }
+        });
+    }
+
+    let mut found = Vec::new();
+    while let Some(result) = lookups.join_next().await {
+        if let Ok(Some((account, Some(game_id)))) = result {
+            if game_id == job_id {
+                found.push(OwnAccountInServer {
+                    account_id: account.id,
+                    user_id: account.user_id,
+                    username: account.username,
+                    display_name: account.display_name,
+                });
+            }
+        }
+    }
+    found.sort_by(|a, b| a.username.cmp(&b.username));
+    found
+}
+
+/// Look up a server, its players' headshots and which of `accounts` are in it
+pub async fn get_server_details(
+    place_id: u64,
+    job_id: &str,
+    accounts: &[Profile],
+) -> Result<ServerDetails, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let (server, own_accounts) = tokio::join!(
+        find_server(&client, place_id, job_id),
+        accounts_in_server(accounts, job_id)
+    );
+    let server = server?;
+
+    // Headshots are cosmetic; keep the tokens if the batch call fails
+    let players = match get_token_headshots(&client, &server.player_tokens).await {
+        Ok(players) => players,
+        Err(e) => {
+            log::warn!("Headshot lookup failed: {}", e);
+            server
+                .player_tokens
+                .iter()
+                .map(|token| ServerPlayer {
+                    token: token.clone(),
+                    headshot: None,
+                })
+                .collect()
+        }
+    };
+
+    Ok(ServerDetails {
+        place_id,
+        server,
+        players,
+        own_accounts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headshot_request_shape() {
+        let tokens = vec!["ABC".to_string()];
+        let body = serde_json::to_value(headshot_requests(&tokens)).unwrap();
+
+        assert_eq!(
+            body,
+            serde_json::json!([{
+                "requestId": "ABC",
+                "token": "ABC",
+                "type": "AvatarHeadShot",
+                "size": "150x150",
+                "format": "png",
+                "isCircular": false
+            }])
+        );
+    }
+}
@@ -0,0 +1,385 @@
+//! Server Finder - Scan every public server of a place with filters and sorting
+//!
+//! Public servers are paged with an opaque cursor, so pages can't be requested
+//! in parallel. Instead two walkers run at once from both ends of the list
+//! (ascending and descending player count) and stop when they meet, which
+//! halves the time of a full scan. Every request goes through one rate limiter.
+
+use crate::game_detection::{fetch_server_page, ServerInfo, ServerPage, SortOrder};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Minimum delay between two server list requests (shared by both walkers)
+const REQUEST_INTERVAL: Duration = Duration::from_millis(350);
+
+/// Field to sort results by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ServerSortField {
+    #[default]
+    Playing,
+    FreeSlots,
+    Ping,
+    Fps,
+}
+
+/// Filters and sorting for a server search
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerQuery {
+    /// Only servers with at least this many open slots (e.g. party size)
+    pub min_free_slots: Option<u32>,
+    /// Only servers reporting a ping at or below this (ms)
+    pub max_ping: Option<u32>,
+    /// Only servers reporting at least this server FPS
+    pub min_fps: Option<f32>,
+    pub sort_by: ServerSortField,
+    pub descending: bool,
+    /// Stop after this many pages (both walkers combined); `None` scans everything
+    pub max_pages: Option<u32>,
+}
+
+impl ServerQuery {
+    /// Does a server pass every filter?
+    pub fn matches(&self, server: &ServerInfo) -> bool {
+        let free = server.max_players.saturating_sub(server.playing);
+
+        self.min_free_slots.is_none_or(|min| free >= min)
+            && self
+                .max_ping
+                .is_none_or(|max| server.ping.is_some_and(|ping| ping <= max))
+            && self
+                .min_fps
+                .is_none_or(|min| server.fps.is_some_and(|fps| fps >= min))
+    }
+
+    fn sort_key(&self, server: &ServerInfo) -> Option<f64> {
+        match self.sort_by {
+            ServerSortField::Playing => Some(server.playing as f64),
+            ServerSortField::FreeSlots => {
+                Some(server.max_players.saturating_sub(server.playing) as f64)
+            }
+            ServerSortField::Ping => server.ping.map(f64::from),
+            ServerSortField::Fps => server.fps.map(f64::from),
+        }
+    }
+
+    /// Sort servers by the chosen field; servers without a value go last
+    pub fn sort(&self, servers: &mut [ServerInfo]) {
+        servers.sort_by(|a, b| match (self.sort_key(a), self.sort_key(b)) {
+            (Some(x), Some(y)) if self.descending => y.total_cmp(&x),
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (Some(_), None) => CmpOrdering::Less,
+            (None, Some(_)) => CmpOrdering::Greater,
+            (None, None) => CmpOrdering::Equal,
+        });
+    }
+}
+
+/// Outcome of a finished (or cancelled) search
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerSearchResult {
+    /// Matching servers, sorted
+    pub servers: Vec<ServerInfo>,
+    /// Distinct servers looked at
+    pub scanned: usize,
+    /// Pages requested
+    pub pages: u32,
+    pub cancelled: bool,
+}
+
+/// Spaces out requests so concurrent walkers stay under Roblox's rate limit
+struct RateLimiter {
+    interval: Duration,
+    next: tokio::sync::Mutex<tokio::time::Instant>,
+}
+
+impl RateLimiter {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next: tokio::sync::Mutex::new(tokio::time::Instant::now()),
+        }
+    }
+
+    async fn wait(&self) {
+        let mut next = self.next.lock().await;
+        tokio::time::sleep_until(*next).await;
+        *next = tokio::time::Instant::now() + self.interval;
+    }
+}
+
+/// Progress shared by both walkers
+#[derive(Default)]
+struct ScanState {
+    seen: HashSet<String>,
+    matched: Vec<ServerInfo>,
+    pages: u32,
+}
+
+// ============================================================================
+// SEARCH
+// ============================================================================
+
+/// Scan all public servers of `place_id`.
+/// `on_batch` gets each page's new matching servers as soon as they arrive;
+/// setting `cancel` stops the scan and returns what was found so far.
+pub async fn find_servers(
+    place_id: u64,
+    query: &ServerQuery,
+    cancel: &AtomicBool,
+    on_batch: &(dyn Fn(&[ServerInfo]) + Sync),
+) -> Result<ServerSearchResult, String> {
+    let client = reqwest::Client::new();
+    let limiter = RateLimiter::new(REQUEST_INTERVAL);
+
+    search_pages(query, cancel, on_batch, |order, cursor| {
+        let client = &client;
+        let limiter = &limiter;
+        async move {
+            limiter.wait().await;
+            fetch_server_page(client, place_id, order, cursor.as_deref()).await
+        }
+    })
+    .await
+}
+
+/// Run both walkers over an arbitrary page source
+async fn search_pages<F, Fut>(
+    query: &ServerQuery,
+    cancel: &AtomicBool,
+    on_batch: &(dyn Fn(&[ServerInfo]) + Sync),
+    fetch: F,
+) -> Result<ServerSearchResult, String>
+where
+    F: Fn(SortOrder, Option<String>) -> Fut,
+    Fut: Future<Output = Result<ServerPage, String>>,
+{
+    let state = Mutex::new(ScanState::default());
+
+    let (asc, desc) = tokio::join!(
+        walk(SortOrder::Asc, query, cancel, on_batch, &fetch, &state),
+        walk(SortOrder::Desc, query, cancel, on_batch, &fetch, &state),
+    );
+
+    // One failed walker is fine: the other keeps going to the far end
+    if let (Err(e), Err(_)) = (&asc, &desc) {
+        return Err(e.clone());
+    }
+
+    let state = state.into_inner().unwrap();
+    let mut servers = state.matched;
+    query.sort(&mut servers);
+
+    Ok(ServerSearchResult {
+        servers,
+        scanned: state.seen.len(),
+        pages: state.pages,
+        cancelled: cancel.load(Ordering::Relaxed),
+    })
+}
+
+/// Follow the cursor in one direction until the end, the other walker, or cancellation
+async fn walk<F, Fut>(
+    order: SortOrder,
+    query: &ServerQuery,
+    cancel: &AtomicBool,
+    on_batch: &(dyn Fn(&[ServerInfo]) + Sync),
+    fetch: &F,
+    state: &Mutex<ScanState>,
+) -> Result<(), String>
+where
+    F: Fn(SortOrder, Option<String>) -> Fut,
+    Fut: Future<Output = Result<ServerPage, String>>,
+{
+    let mut cursor = None;
+
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        {
+            let mut state = state.lock().unwrap();
+            if query.max_pages.is_some_and(|max| state.pages >= max) {
+                return Ok(());
+            }
+            state.pages += 1;
+        }
+
+        let page = fetch(order, cursor.take()).await?;
+
+        let (fresh, batch) = {
+            let mut state = state.lock().unwrap();
+            let new: Vec<ServerInfo> = page
+                .servers
+                .into_iter()
+                .filter(|s| state.seen.insert(s.id.clone()))
+                .collect();
+            let fresh = new.len();
+            let batch: Vec<ServerInfo> = new.into_iter().filter(|s| query.matches(s)).collect();
+            state.matched.extend(batch.iter().cloned());
+            (fresh, batch)
+        };
+
+        if !batch.is_empty() {
+            on_batch(&batch);
+        }
+
+        // A page with nothing new means the other walker already covered it
+        match page.next_page_cursor {
+            Some(next) if fresh > 0 => cursor = Some(next),
+            _ => return Ok(()),
+        }
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn server(n: u32) -> ServerInfo {
+        ServerInfo {
+            ping: (!n.is_multiple_of(3)).then_some(40 + n),
+            fps: Some(60.0 - (n % 5) as f32),
+            ..test_support::server(&format!("job-{}", n), n % 12, 12)
+        }
+    }
+
+    /// `count` servers paged `per_page` at a time from either end
+    async fn fake_page(
+        count: u32,
+        per_page: usize,
+        order: SortOrder,
+        cursor: Option<String>,
+    ) -> Result<ServerPage, String> {
+        // Let the other walker run, like a real request would
+        tokio::task::yield_now().await;
+
+        let mut all: Vec<ServerInfo> = (1..=count).map(server).collect();
+        if order == SortOrder::Desc {
+            all.reverse();
+        }
+        let start: usize = cursor.map(|c| c.parse().unwrap()).unwrap_or(0);
+        let end = (start + per_page).min(all.len());
+        Ok(ServerPage {
+            servers: all[start..end].to_vec(),
+            next_page_cursor: (end < all.len()).then(|| end.to_string()),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_walkers_cover_every_server_once() {
+        let cancel = AtomicBool::new(false);
+        let streamed = Mutex::new(Vec::new());
+        let on_batch = |batch: &[ServerInfo]| streamed.lock().unwrap().extend_from_slice(batch);
+
+        let result = search_pages(&ServerQuery::default(), &cancel, &on_batch, |order, cursor| {
+            fake_page(95, 10, order, cursor)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result.scanned, 95);
+        assert_eq!(result.servers.len(), 95);
+        assert_eq!(streamed.lock().unwrap().len(), 95);
+        // Meeting in the middle needs far fewer than 2 × 10 pages
+        assert!(result.pages <= 12, "{} pages", result.pages);
+        assert!(!result.cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_filters_and_sorting() {
+        let cancel = AtomicBool::new(false);
+        let query = ServerQuery {
+            min_free_slots: Some(4),
+            max_ping: Some(100),
+            sort_by: ServerSortField::Ping,
+            descending: true,
+            ..Default::default()
+        };
+
+        let result = search_pages(&query, &cancel, &|_| {}, |order, cursor| {
+            fake_page(60, 10, order, cursor)
+        })
+        .await
+        .unwrap();
+
+        assert!(!result.servers.is_empty());
+        assert!(result.servers.iter().all(|s| query.matches(s)));
+        assert!(result
+            .servers
+            .windows(2)
+            .all(|w| w[0].ping.unwrap() >= w[1].ping.unwrap()));
+        let expected = (1..=60).map(server).filter(|s| query.matches(s)).count();
+        assert_eq!(result.servers.len(), expected);
+    }
+
+    #[test]
+    fn test_missing_values_sort_last() {
+        let query = ServerQuery {
+            sort_by: ServerSortField::Ping,
+            ..Default::default()
+        };
+        let mut servers: Vec<ServerInfo> = (1..=6).map(server).collect();
+        query.sort(&mut servers);
+
+        assert_eq!(servers[0].id, "job-1");
+        assert!(servers[4].ping.is_none() && servers[5].ping.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_page_limit() {
+        let cancel = AtomicBool::new(true);
+        let result = search_pages(&ServerQuery::default(), &cancel, &|_| {}, |order, cursor| {
+            fake_page(50, 10, order, cursor)
+        })
+        .await
+        .unwrap();
+        assert!(result.cancelled);
+        assert_eq!(result.pages, 0);
+
+        let cancel = AtomicBool::new(false);
+        let query = ServerQuery {
+            max_pages: Some(3),
+            ..Default::default()
+        };
+        let result = search_pages(&query, &cancel, &|_| {}, |order, cursor| {
+            fake_page(500, 10, order, cursor)
+        })
+        .await
+        .unwrap();
+        assert_eq!(result.pages, 3);
+        assert_eq!(result.scanned, 30);
+    }
+
+    #[tokio::test]
+    async fn test_one_failing_direction_is_tolerated() {
+        let cancel = AtomicBool::new(false);
+        let result = search_pages(&ServerQuery::default(), &cancel, &|_| {}, |order, cursor| async move {
+            match order {
+                SortOrder::Asc => Err("API error: 500".to_string()),
+                SortOrder::Desc => fake_page(25, 10, order, cursor).await,
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(result.scanned, 25);
+
+        let failed = search_pages(&ServerQuery::default(), &cancel, &|_| {}, |_, _| async {
+            Err::<ServerPage, _>("API error: 500".to_string())
+        })
+        .await;
+        assert!(failed.is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! Server Hop - Relaunch a running account into another server of the same place
+
+use crate::game_detection::{fetch_server_page, ServerInfo, SortOrder};
+use crate::history;
+use crate::launcher::{launch_account_with, ActiveInstance};
+use crate::process_utils::is_process_running;
+use crate::roblox::get_own_presence;
+use crate::roblox_link::RobloxLink;
+use crate::vault::load_accounts;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+/// Server list pages to look through before giving up
+const HOP_MAX_PAGES: usize = 3;
+/// How long to wait for the old client to exit before relaunching
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Start of the error of a hop that closed the client but couldn't relaunch it
+pub const CLIENT_CLOSED_ERROR: &str = "The client was closed but could not be relaunched";
+
+/// How to choose the next server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HopStrategy {
+    LeastPlayers,
+    MostPlayers,
+    Random,
+    /// Random among servers this account hasn't been in yet (per the launch history)
+    NotVisited,
+}
+
+/// Outcome of a hop
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HopResult {
+    /// The relaunched client
+    pub instance: ActiveInstance,
+    pub from_job_id: Option<String>,
+    pub to_job_id: String,
+}
+
+/// Pick a joinable server other than `current_job`
+pub fn pick_server<'a, R: Rng>(
+    servers: &'a [ServerInfo],
+    current_job: Option<&str>,
+    visited: &HashSet<String>,
+    strategy: HopStrategy,
+    rng: &mut R,
+) -> Option<&'a ServerInfo> {
+    let candidates: Vec<&ServerInfo> = servers
+        .iter()
+        .filter(|s| Some(s.id.as_str()) != current_job && s.playing < s.max_players)
+        .filter(|s| strategy != HopStrategy::NotVisited || !visited.contains(&s.id))
+        .collect();
+
+    match strategy {
+        HopStrategy::LeastPlayers => candidates.into_iter().min_by_key(|s| s.playing),
+        HopStrategy::MostPlayers => candidates.into_iter().max_by_key(|s| s.playing),
+        HopStrategy::Random | HopStrategy::NotVisited => candidates.choose(rng).copied(),
+    }
+}
+
+/// Find another server for the account behind `instance`, close its client
+/// and launch it again into that server through the normal launch pipeline,
+/// with the same launch options it was started with.
+/// Once the client is closed, errors start with `CLIENT_CLOSED_ERROR`.
+pub async fn server_hop(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    instance: &ActiveInstance,
+    strategy: HopStrategy,
+) -> Result<HopResult, String> {
+    let accounts = load_accounts(app_data_dir, key)?;
+    let account = accounts
+        .iter()
+        .find(|a| a.id == instance.account_id)
+        .ok_or("Account not found")?;
+
+    // Presence knows where the client really is (it may have teleported);
+    // fall back to what it was launched into
+    let presence = get_own_presence(&account.cookie, account.user_id).await?;
+    let place_id = presence
+        .place_id
+        .filter(|id| *id > 0)
+        .map(|id| id as u64)
+        .or((instance.place_id > 0).then_some(instance.place_id))
+        .ok_or("Account is not in a game")?;
+    let from_job_id = presence.game_id.or_else(|| instance.job_id.clone());
+
+    let visited = match strategy {
+        HopStrategy::NotVisited => {
+            history::visited_servers(app_data_dir, &instance.account_id, place_id)?
+        }
+        _ => HashSet::new(),
+    };
+
+    // Emptiest servers come first in ascending order, fullest in descending
+    let order = match strategy {
+        HopStrategy::MostPlayers => SortOrder::Desc,
+        _ => SortOrder::Asc,
+    };
+
+    let client = reqwest::Client::new();
+    let mut cursor: Option<String> = None;
+    let mut to_job_id = None;
+
+    for _ in 0..HOP_MAX_PAGES {
+        let page = fetch_server_page(&client, place_id, order, cursor.as_deref()).await?;
+        let picked = pick_server(
+            &page.servers,
+            from_job_id.as_deref(),
+            &visited,
+            strategy,
+            &mut rand::thread_rng(),
+        )
+        .map(|s| s.id.clone());
+
+        if picked.is_some() {
+            to_job_id = picked;
+            break;
+        }
+        match page.next_page_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    let to_job_id = to_job_id.ok_or("No other server with free slots found")?;
+
+    // Close the current client so the relaunch isn't blocked by it
+    instance.backend(app_data_dir)?.terminate(instance.pid)?;
+    if let Err(e) = history::record_killed(app_data_dir, instance) {
+        log::warn!("Failed to record hop exit: {}", e);
+    }
+    // Counts as visited even if the join watcher hadn't seen it yet
+    if let (Some(launch_id), Some(job_id)) = (instance.launch_id, &from_job_id) {
+        if place_id == instance.place_id {
+            if let Err(e) = history::record_joined(app_data_dir, launch_id, job_id) {
+                log::warn!("Failed to record joined server: {}", e);
+            }
+        }
+    }
+    let deadline = std::time::Instant::now() + EXIT_TIMEOUT;
+    while is_process_running(instance.pid) && std::time::Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    let target = RobloxLink::Server {
+        place_id,
+        job_id: to_job_id.clone(),
+    };
+    let instance = launch_account_with(
+        app_data_dir,
+        key,
+        &instance.account_id,
+        Some(target),
+        &instance.options,
+    )
+    .await
+    .map_err(|e| format!("{}: {}", CLIENT_CLOSED_ERROR, e))?;
+
+    Ok(HopResult {
+        instance,
+        from_job_id,
+        to_job_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::server;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn servers() -> Vec<ServerInfo> {
+        [("a", 3), ("b", 12), ("c", 7), ("d", 1), ("e", 10)]
+            .into_iter()
+            .map(|(id, playing)| server(id, playing, 12))
+            .collect()
+    }
+
+    #[test]
+    fn test_least_and_most_players_skip_current_and_full() {
+        let servers = servers();
+        let visited = HashSet::new();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let least = pick_server(&servers, Some("d"), &visited, HopStrategy::LeastPlayers, &mut rng);
+        assert_eq!(least.unwrap().id, "a");
+
+        // "b" is full
+        let most = pick_server(&servers, None, &visited, HopStrategy::MostPlayers, &mut rng);
+        assert_eq!(most.unwrap().id, "e");
+    }
+
+    #[test]
+    fn test_not_visited() {
+        let servers = servers();
+        let visited: HashSet<String> = ["a", "c", "e"].iter().map(|s| s.to_string()).collect();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for _ in 0..20 {
+            let pick = pick_server(&servers, None, &visited, HopStrategy::NotVisited, &mut rng);
+            assert_eq!(pick.unwrap().id, "d");
+        }
+
+        let pick = pick_server(&servers, Some("d"), &visited, HopStrategy::NotVisited, &mut rng);
+        assert!(pick.is_none());
+    }
+
+    #[test]
+    fn test_random_never_picks_current() {
+        let servers = servers();
+        let visited = HashSet::new();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..50 {
+            let pick = pick_server(&servers, Some("a"), &visited, HopStrategy::Random, &mut rng);
+            let id = &pick.unwrap().id;
+            assert!(id != "a" && id != "b");
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//! ROKIO Sessions - Keep stored `.ROBLOSECURITY` cookies current
+//!
+//! Roblox rotates session cookies and sends the new value in `Set-Cookie` on
+//! authenticated responses. `roblox::send_authenticated`, which every
+//! authenticated request goes through, reports each rotation here; the app
+//! registers a handler that writes it to the vault with `apply_rotation`, since
+//! only the app knows the data directory and whether the vault is unlocked.
+//!
+//! Also manages sessions on Roblox's side: listing where an account is logged
+//! in, signing out every other session, and signing out for good
+//! (`sign_out_and_remove`) so a deleted account's cookie stops working.
+
+use crate::audit;
+use crate::environment::{profile_dir_in, write_session_cookie};
+use crate::roblox::{csrf_post, send_authenticated};
+use crate::trash;
+use crate::vault::{find_account, remove_account, update_accounts};
+use reqwest::header::{HeaderMap, SET_COOKIE};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Called with `(old_cookie, new_cookie)` whenever Roblox rotates a session
+pub type RotationHandler = Box<dyn Fn(&str, &str) + Send + Sync>;
+
+const ROBLOX_LOGOUT_API: &str = "https://auth.roblox.com/v2/logout";
+const ROBLOX_SESSIONS_API: &str = "https://apis.roblox.com/token-metadata-service/v1/sessions";
+const ROBLOX_SIGN_OUT_OTHERS_API: &str =
+    "https://www.roblox.com/authentication/signoutfromallsessionsandreauthenticate";
+/// Page limit when listing sessions (25 sessions per page)
+const SESSIONS_MAX_PAGES: usize = 8;
+
+static ROTATION_HANDLER: OnceLock<RotationHandler> = OnceLock::new();
+
+/// Register the handler for rotated cookies (only the first call takes effect)
+pub fn set_rotation_handler(handler: impl Fn(&str, &str) + Send + Sync + 'static) {
+    if ROTATION_HANDLER.set(Box::new(handler)).is_err() {
+        log::warn!("Cookie rotation handler is already set");
+    }
+}
+
+/// The `.ROBLOSECURITY` value set by a response, if any (empty = cleared)
+pub fn set_cookie_value(headers: &HeaderMap) -> Option<String> {
+    headers.get_all(SET_COOKIE).iter().find_map(|v| {
+        let value = v.to_str().ok()?.trim().strip_prefix(".ROBLOSECURITY=")?;
+        Some(value.split(';').next().unwrap_or_default().to_string())
+    })
+}
+
+/// Report a rotation if `headers` replace the cookie the request was sent with
+pub(crate) fn check_rotation(sent_cookie: &str, headers: &HeaderMap) {
+    let sent = sent_cookie
+        .strip_prefix(".ROBLOSECURITY=")
+        .unwrap_or(sent_cookie);
+    let Some(new) = set_cookie_value(headers) else {
+        return;
+    };
+    if new.is_empty() || new == sent {
+        return;
+    }
+
+    match ROTATION_HANDLER.get() {
+        Some(handler) => handler(sent, &new),
+        None => log::warn!("Roblox rotated a session cookie but no handler is set"),
+    }
+}
+
+/// Store a rotated cookie: update the account holding `old_cookie`, rewrite
+/// it in the account's environment and record it in the audit log.
+/// Returns the account ID (`None` when no account uses `old_cookie`).
+pub fn apply_rotation(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    old_cookie: &str,
+    new_cookie: &str,
+) -> Result<Option<String>, String> {
+    let account_id = update_accounts(app_data_dir, key, |accounts| {
+        let account = accounts.iter_mut().find(|a| a.cookie == old_cookie);
+        Ok(account.map(|account| {
+            account.cookie = new_cookie.to_string();
+            account.id.clone()
+        }))
+    })?;
+    let Some(account_id) = account_id else {
+        return Ok(None);
+    };
+
+    // Only environments that were already set up (launched before)
+    let profile_dir = profile_dir_in(app_data_dir, &account_id);
+    if profile_dir.exists() {
+        write_session_cookie(&profile_dir, new_cookie)?;
+    }
+
+    audit::record(app_data_dir, "cookie_rotated", Some(&account_id), true)?;
+    Ok(Some(account_id))
+}
+
+/// End a session on Roblox's side. A cookie Roblox already rejects counts as signed out.
+pub async fn sign_out(cookie: &str) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let response = csrf_post(&client, ROBLOX_LOGOUT_API, cookie, "{}").await?;
+
+    if response.status().is_success() || response.status() == 401 {
+        Ok(())
+    } else {
+        Err(format!("Roblox sign-out failed: {}", response.status()))
+    }
+}
+
+/// Sign an account out on Roblox, then delete it for good (skipping the
+/// recycle bin, its cookie is dead) along with its environment directory.
+/// Nothing is deleted if the sign-out fails.
+pub async fn sign_out_and_remove(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    account_id: &str,
+) -> Result<(), String> {
+    let account = find_account(app_data_dir, key, account_id)?;
+    sign_out(&account.cookie).await?;
+    audit::record(app_data_dir, "signed_out", Some(account_id), true)?;
+
+    // Purging removes the environment too
+    remove_account(app_data_dir, account_id)?;
+    trash::purge(app_data_dir, Some(&[account_id.to_string()]))?;
+    Ok(())
+}
+
+// ============================================================================
+// ACTIVE SESSIONS
+// ============================================================================
+
+/// One place an account is logged in
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveSession {
+    /// e.g. "Chrome on Windows", "App on iOS"
+    pub device: String,
+    /// "City, Region, Country" as far as Roblox knows it
+    pub location: Option<String>,
+    pub ip: Option<String>,
+    /// Unix time in seconds
+    pub last_active: Option<i64>,
+    /// The session ROKIO itself uses
+    pub is_current: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionsResponse {
+    #[serde(default)]
+    sessions: Vec<RawSession>,
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSession {
+    #[serde(default)]
+    agent: Option<RawAgent>,
+    #[serde(default)]
+    location: Option<RawLocation>,
+    last_accessed_ip: Option<String>,
+    /// Sent as a string or a number depending on the endpoint version
+    #[serde(default)]
+    last_accessed_timestamp_epoch_milliseconds: Option<serde_json::Value>,
+    #[serde(default)]
+    is_current_session: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAgent {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    value: Option<String>,
+    os: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawLocation {
+    city: Option<String>,
+    subdivision: Option<String>,
+    country: Option<String>,
+}
+
+impl From<RawSession> for ActiveSession {
+    fn from(raw: RawSession) -> Self {
+        let agent = raw.agent.unwrap_or_default();
+        let app = agent
+            .value
+            .or(agent.kind)
+            .unwrap_or_else(|| "Unknown device".to_string());
+        let device = match agent.os {
+            Some(os) if !os.is_empty() => format!("{} on {}", app, os),
+            _ => app,
+        };
+
+        let location = raw.location.map(|l| {
+            [l.city, l.subdivision, l.country]
+                .into_iter()
+                .flatten()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+
+        let last_active = raw
+            .last_accessed_timestamp_epoch_milliseconds
+            .and_then(|v| match v {
+                serde_json::Value::String(s) => s.parse::<i64>().ok(),
+                other => other.as_i64(),
+            })
+            .map(|ms| ms / 1000);
+
+        ActiveSession {
+            device,
+            location: location.filter(|l| !l.is_empty()),
+            ip: raw.last_accessed_ip,
+            last_active,
+            is_current: raw.is_current_session,
+        }
+    }
+}
+
+/// Sessions an account is logged in with, most recently active first
+pub async fn list_sessions(cookie: &str) -> Result<Vec<ActiveSession>, String> {
+    let client = reqwest::Client::new();
+    let mut sessions = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..SESSIONS_MAX_PAGES {
+        let mut url = format!("{}?desiredLimit=25", ROBLOX_SESSIONS_API);
+        if let Some(c) = &cursor {
+            url.push_str(&format!("&nextCursor={}", urlencoding::encode(c)));
+        }
+
+        let response = send_authenticated(client.get(&url), cookie)
+            .await
+            .map_err(|e| format!("Sessions fetch error: {}", e))?;
+
+        if response.status() == 401 {
+            return Err("Invalid or expired cookie".to_string());
+        }
+        if !response.status().is_success() {
+            return Err(format!("Sessions API error: {}", response.status()));
+        }
+
+        let page: SessionsResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Sessions parse error: {}", e))?;
+        sessions.extend(page.sessions.into_iter().map(ActiveSession::from));
+
+        match page.next_cursor.filter(|c| !c.is_empty()) {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.last_active));
+    Ok(sessions)
+}
+
+/// Sessions of a stored account
+pub async fn list_account_sessions(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    account_id: &str,
+) -> Result<Vec<ActiveSession>, String> {
+    let account = find_account(app_data_dir, key, account_id)?;
+    list_sessions(&account.cookie).await
+}
+
+/// Sign a stored account out of every other session. Roblox answers with a
+/// fresh cookie for ROKIO's own session, which replaces the old one in the
+/// vault and the account's environment.
+pub async fn sign_out_other_sessions(
+    app_data_dir: &Path,
+    key: &[u8; 32],
+    account_id: &str,
+) -> Result<(), String> {
+    let account = find_account(app_data_dir, key, account_id)?;
+    let client = reqwest::Client::new();
+    let response = csrf_post(&client, ROBLOX_SIGN_OUT_OTHERS_API, &account.cookie, "{}").await?;
+
+    if !response.status().is_success() {
+        return Err(format!("Roblox sign-out failed: {}", response.status()));
+    }
+
+    let new_cookie = set_cookie_value(response.headers())
+        .filter(|c| !c.is_empty())
+        .ok_or("Roblox did not return a new session cookie")?;
+    audit::record(
+        app_data_dir,
+        "signed_out_other_sessions",
+        Some(account_id),
+        true,
+    )?;
+
+    // The rotation handler may already have stored it; then this finds nothing
+    apply_rotation(app_data_dir, key, &account.cookie, &new_cookie)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{derive_key, write_empty_vault};
+    use crate::profiles::Profile;
+    use crate::test_support::{profile, temp_dir};
+    use crate::vault::{load_accounts, save_accounts};
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_set_cookie_value() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            SET_COOKIE,
+            HeaderValue::from_static("RBXEventTrackerV2=x; path=/"),
+        );
+        assert_eq!(set_cookie_value(&headers), None);
+
+        headers.append(
+            SET_COOKIE,
+            HeaderValue::from_static(".ROBLOSECURITY=_|WARNING|_NEW; domain=.roblox.com; HttpOnly"),
+        );
+        assert_eq!(
+            set_cookie_value(&headers).as_deref(),
+            Some("_|WARNING|_NEW")
+        );
+    }
+
+    #[test]
+    fn test_active_session_from_raw() {
+        let raw: RawSession = serde_json::from_value(serde_json::json!({
+            "agent": { "type": "Browser", "value": "Chrome", "os": "Windows" },
+            "location": { "city": "Hanoi", "subdivision": "", "country": "Vietnam" },
+            "lastAccessedIp": "203.0.113.7",
+            "lastAccessedTimestampEpochMilliseconds": "1700000000123",
+            "isCurrentSession": true
+        }))
+        .unwrap();
+        let session = ActiveSession::from(raw);
+        assert_eq!(session.device, "Chrome on Windows");
+        assert_eq!(session.location.as_deref(), Some("Hanoi, Vietnam"));
+        assert_eq!(session.last_active, Some(1_700_000_000));
+        assert!(session.is_current);
+
+        let session = ActiveSession::from(RawSession::default());
+        assert_eq!(session.device, "Unknown device");
+        assert_eq!(session.location, None);
+    }
+
+    #[test]
+    fn test_apply_rotation() {
+        let dir = temp_dir("session");
+        let key = derive_key("pw");
+        write_empty_vault(&dir, &key).unwrap();
+
+        let account = Profile {
+            cookie: "OLD".to_string(),
+            ..profile(1)
+        };
+        save_accounts(&dir, &key, &[account]).unwrap();
+
+        assert_eq!(apply_rotation(&dir, &key, "UNKNOWN", "NEW").unwrap(), None);
+        assert_eq!(
+            apply_rotation(&dir, &key, "OLD", "NEW").unwrap().as_deref(),
+            Some("acc-1")
+        );
+        assert_eq!(load_accounts(&dir, &key).unwrap()[0].cookie, "NEW");
+
+        let log = audit::list(&dir, 10).unwrap();
+        assert_eq!(log[0].action, "cookie_rotated");
+        assert_eq!(log[0].account_id.as_deref(), Some("acc-1"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,188 @@
+//! ROKIO Settings - `settings.json` in the app data directory
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Favorite game data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoriteGame {
+    pub id: String,
+    pub place_id: u64,
+    pub name: String,
+    pub thumbnail: Option<String>,
+    pub added_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    // General
+    pub auto_lock_timeout: String, // "never", "1min", "5min", "15min"
+    pub launch_on_startup: bool,
+    pub always_on_top: bool,
+
+    // Appearance
+    pub theme: String, // "dark", "light", "system"
+    pub compact_mode: bool,
+    #[serde(default = "default_accent")]
+    pub accent_color: String, // "red", "orange", "yellow", "green", "teal", "blue", "indigo", "purple", "pink"
+
+    // Launch Options
+    #[serde(default)]
+    pub multi_instance: bool, // Enable multi-instance launching with isolated environments
+    #[serde(default)]
+    pub launcher_preference: String, // "default", "bloxstrap", "fishstrap", "froststrap", "client", "custom"
+    #[serde(default)]
+    pub custom_launch_command: String, // Command template for the "custom" launcher, e.g. "wrapper {deeplink}"
+    #[serde(default)]
+    pub quarantine_installers: bool, // Move RobloxPlayerInstaller.exe to prevent update popups (Windows)
+    #[serde(default)]
+    pub save_logs: bool, // Save session logs
+    #[serde(default)]
+    pub force_handle_closure: bool, // Aggressive handle resolution for multi-instance
+    #[serde(default)]
+    pub low_cpu_mode: bool, // Lower CPU usage by reducing scan frequency
+
+    // Recycle Bin
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32, // Days deleted accounts are kept, 0 = forever
+
+    // Automation API
+    #[serde(default)]
+    pub automation_api_enabled: bool, // Serve the local automation API on 127.0.0.1
+    #[serde(default = "default_automation_api_port")]
+    pub automation_api_port: u16,
+
+    // Favorite Games
+    #[serde(default)]
+    pub favorite_games: Vec<FavoriteGame>,
+}
+
+fn default_accent() -> String {
+    "red".to_string()
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_automation_api_port() -> u16 {
+    7963
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            auto_lock_timeout: "never".to_string(),
+            launch_on_startup: false,
+            always_on_top: false,
+            theme: "dark".to_string(),
+            compact_mode: false,
+            accent_color: "red".to_string(),
+            multi_instance: false, // Disabled by default
+            launcher_preference: "default".to_string(),
+            custom_launch_command: String::new(),
+            quarantine_installers: false,
+            save_logs: false,
+            force_handle_closure: false,
+            low_cpu_mode: false,
+            trash_retention_days: default_trash_retention_days(),
+            automation_api_enabled: false, // Opt-in
+            automation_api_port: default_automation_api_port(),
+            favorite_games: vec![],
+        }
+    }
+}
+
+fn settings_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("settings.json")
+}
+
+/// Load settings (defaults when the file does not exist yet)
+pub fn load_settings(app_data_dir: &Path) -> Result<AppSettings, String> {
+    let path = settings_path(app_data_dir);
+
+    if !path.exists() {
+        return Ok(AppSettings::default());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read settings: {}", e))?;
+
+    let settings: AppSettings =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse settings: {}", e))?;
+
+    Ok(settings)
+}
+
+/// Write settings to `settings.json`
+pub fn save_settings(app_data_dir: &Path, settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path(app_data_dir);
+
+    // Ensure parent directory exists
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+    }
+
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write settings: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_missing_file_gives_defaults() {
+        let dir = temp_dir("settings");
+        let settings = load_settings(&dir).unwrap();
+        assert_eq!(settings.launcher_preference, "default");
+        assert!(!settings.automation_api_enabled);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = temp_dir("settings");
+        let settings = AppSettings {
+            multi_instance: true,
+            launcher_preference: "custom".to_string(),
+            custom_launch_command: "wrap {deeplink}".to_string(),
+            ..Default::default()
+        };
+
+        save_settings(&dir, &settings).unwrap();
+        let loaded = load_settings(&dir).unwrap();
+
+        assert!(loaded.multi_instance);
+        assert_eq!(loaded.launcher_preference, "custom");
+        assert_eq!(loaded.custom_launch_command, "wrap {deeplink}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_old_settings_files_get_new_defaults() {
+        let dir = temp_dir("settings");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            settings_path(&dir),
+            r#"{"autoLockTimeout":"5min","launchOnStartup":false,"alwaysOnTop":false,"theme":"light","compactMode":false}"#,
+        )
+        .unwrap();
+
+        let settings = load_settings(&dir).unwrap();
+        assert_eq!(settings.theme, "light");
+        assert_eq!(settings.accent_color, "red");
+        assert_eq!(settings.automation_api_port, 7963);
+        assert!(settings.favorite_games.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+//! Share Links - Resolve `roblox.com/share?code=...&type=Server` invites
+//!
+//! Roblox hides the private server link code behind a share code. The share-link
+//! API turns it back into a place ID + link code, but only for a signed-in user.
+
+use crate::roblox::csrf_post;
+use crate::roblox_link::RobloxLink;
+use serde::{Deserialize, Serialize};
+
+const RESOLVE_SHARE_LINK_API: &str = "https://apis.roblox.com/sharelinks/v1/resolve-link";
+
+/// Outcome of resolving a share link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShareLinkStatus {
+    Valid,
+    Expired,
+    Invalid,
+}
+
+/// Resolved share link returned to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedShareLink {
+    pub status: ShareLinkStatus,
+    pub place_id: Option<u64>,
+    pub universe_id: Option<u64>,
+    pub link_code: Option<String>,
+}
+
+impl ResolvedShareLink {
+    fn invalid() -> Self {
+        Self {
+            status: ShareLinkStatus::Invalid,
+            place_id: None,
+            universe_id: None,
+            link_code: None,
+        }
+    }
+
+    /// Turn a resolved link into a launchable private-server target
+    pub fn into_link(self) -> Result<RobloxLink, String> {
+        match (self.status, self.place_id, self.link_code) {
+            (ShareLinkStatus::Valid, Some(place_id), Some(link_code)) => {
+                Ok(RobloxLink::PrivateServer {
+                    place_id,
+                    link_code,
+                })
+            }
+            (ShareLinkStatus::Expired, _, _) => Err("This share link has expired".to_string()),
+            _ => Err("This share link is invalid or no longer points to a server".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResolveLinkResponse {
+    private_server_invite_data: Option<PrivateServerInviteData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrivateServerInviteData {
+    status: Option<String>,
+    place_id: Option<u64>,
+    universe_id: Option<u64>,
+    link_code: Option<String>,
+}
+
+impl From<ResolveLinkResponse> for ResolvedShareLink {
+    fn from(data: ResolveLinkResponse) -> Self {
+        let Some(invite) = data.private_server_invite_data else {
+            return Self::invalid();
+        };
+
+        let status = match invite.status.as_deref() {
+            Some(s) if s.eq_ignore_ascii_case("valid") => ShareLinkStatus::Valid,
+            Some(s) if s.eq_ignore_ascii_case("expired") => ShareLinkStatus::Expired,
+            _ => ShareLinkStatus::Invalid,
+        };
+
+        Self {
+            status,
+            place_id: invite.place_id,
+            universe_id: invite.universe_id,
+            link_code: invite.link_code,
+        }
+    }
+}
+
+/// Resolve a share code through Roblox's share-link API using an account's session
+pub async fn resolve_share_code(
+    cookie: &str,
+    code: &str,
+    link_type: &str,
+) -> Result<ResolvedShareLink, String> {
+    let client = reqwest::Client::new();
+    let body = serde_json::json!({
+        "linkId": code,
+        "linkType": link_type,
+    });
+
+    let response = csrf_post(&client, RESOLVE_SHARE_LINK_API, cookie, &body.to_string()).await?;
+    let status = response.status();
+
+    // Unknown or malformed codes come back as 400/404
+    if status == 400 || status == 404 {
+        return Ok(ResolvedShareLink::invalid());
+    }
+    if status == 401 {
+        return Err("Account session is invalid or expired".to_string());
+    }
+    if !status.is_success() {
+        return Err(format!("Share link API error: {}", status));
+    }
+
+    let data: ResolveLinkResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse share link: {}", e))?;
+
+    Ok(data.into())
+}
+
+/// Resolve any share link into a launchable target (non-share links pass through)
+pub async fn resolve_link(cookie: &str, link: RobloxLink) -> Result<RobloxLink, String> {
+    match link {
+        RobloxLink::Share { code, link_type } => {
+            if !link_type.eq_ignore_ascii_case("server") {
+                return Err(format!("Unsupported share link type: {}", link_type));
+            }
+            resolve_share_code(cookie, &code, &link_type)
+                .await?
+                .into_link()
+        }
+        other => Ok(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(json: &str) -> ResolvedShareLink {
+        serde_json::from_str::<ResolveLinkResponse>(json).unwrap().into()
+    }
+
+    #[test]
+    fn test_parse_resolve_response() {
+        let link = resolve(
+            r#"{"privateServerInviteData":{"status":"Valid","placeId":606849621,"universeId":245662005,"linkCode":"abc123","ownerUserId":1}}"#,
+        );
+        assert_eq!(link.status, ShareLinkStatus::Valid);
+        assert_eq!(link.universe_id, Some(245662005));
+        assert_eq!(
+            link.into_link().unwrap(),
+            RobloxLink::PrivateServer {
+                place_id: 606849621,
+                link_code: "abc123".to_string(),
+            }
+        );
+
+        let expired = resolve(r#"{"privateServerInviteData":{"status":"Expired","placeId":1}}"#);
+        assert_eq!(expired.status, ShareLinkStatus::Expired);
+        assert_eq!(expired.into_link().unwrap_err(), "This share link has expired");
+
+        // Other share types (profiles, experiences) carry no server invite
+        let profile = resolve(r#"{"privateServerInviteData":null,"profileLinkResolutionResponseData":{"userId":1}}"#);
+        assert_eq!(profile.status, ShareLinkStatus::Invalid);
+        assert!(profile.into_link().is_err());
+
+        // A valid status without a link code is not launchable
+        let partial = resolve(r#"{"privateServerInviteData":{"status":"valid","placeId":1}}"#);
+        assert!(partial.into_link().is_err());
+    }
+}
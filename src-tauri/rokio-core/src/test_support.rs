@@ -0,0 +1,45 @@
+//! Fixtures shared by the unit tests
+
+use crate::game_detection::ServerInfo;
+use crate::profiles::Profile;
+use std::path::PathBuf;
+
+/// Unique directory under the system temp dir (not created)
+pub fn temp_dir(tag: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rokio-{}-{}", tag, uuid::Uuid::new_v4()))
+}
+
+/// Account "acc-<id>" of Roblox user `id` ("user<id>"), with cookie "COOKIE_<id>"
+pub fn profile(id: i64) -> Profile {
+    Profile {
+        id: format!("acc-{}", id),
+        cookie: format!("COOKIE_{}", id),
+        user_id: id,
+        username: format!("user{}", id),
+        display_name: format!("User {}", id),
+        thumbnail: None,
+        alias: String::new(),
+        description: String::new(),
+        tags: Vec::new(),
+        folder: String::new(),
+        sort_order: 0,
+        is_favorite: false,
+        last_played_at: 0,
+        password: None,
+        created_at: None,
+        is_premium: None,
+    }
+}
+
+/// Public server `id` with `playing` of `max` players
+pub fn server(id: &str, playing: u32, max: u32) -> ServerInfo {
+    ServerInfo {
+        id: id.to_string(),
+        playing,
+        max_players: max,
+        ping: None,
+        fps: None,
+        region: None,
+        player_tokens: Vec::new(),
+    }
+}
@@ -0,0 +1,260 @@
+//! ROKIO Recycle Bin - deleted accounts kept inside the encrypted vault
+//!
+//! Deleting an account moves its record (cookie still encrypted) from
+//! `accounts` to `trash` in `vault.dat`. Entries older than the retention
+//! period (`trashRetentionDays` in the settings) are purged for good, together
+//! with their isolated environment.
+
+use crate::crypto::vault_path;
+use crate::environment::remove_profile;
+use crate::vault::{read_vault, update_vault, TrashedAccount, VaultData};
+use serde::Serialize;
+use std::path::Path;
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// Trashed account as shown to the user (no cookie)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub id: String,
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: String,
+    pub thumbnail: Option<String>,
+    pub alias: String,
+    pub deleted_at: u64,
+    /// When the entry gets purged (`None` when kept forever)
+    pub expires_at: Option<u64>,
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// Move accounts to the recycle bin, returning how many were moved
+pub fn move_to_trash(app_data_dir: &Path, account_ids: &[String]) -> Result<usize, String> {
+    update_vault(app_data_dir, |vault| Ok(trash_in(vault, account_ids)))
+}
+
+/// Move every account to the recycle bin
+pub fn move_all_to_trash(app_data_dir: &Path) -> Result<usize, String> {
+    update_vault(app_data_dir, |vault| {
+        let ids: Vec<String> = vault.accounts.iter().map(|a| a.id.clone()).collect();
+        Ok(trash_in(vault, &ids))
+    })
+}
+
+/// `move_to_trash` on a vault being updated
+pub(crate) fn trash_in(vault: &mut VaultData, account_ids: &[String]) -> usize {
+    let (trashed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut vault.accounts)
+        .into_iter()
+        .partition(|a| account_ids.contains(&a.id));
+    vault.accounts = kept;
+
+    let count = trashed.len();
+    let deleted_at = now();
+    vault
+        .trash
+        .extend(trashed.into_iter().map(|account| TrashedAccount {
+            account,
+            deleted_at,
+        }));
+    count
+}
+
+/// Accounts in the recycle bin, most recently deleted first
+pub fn list_trash(app_data_dir: &Path, retention_days: u32) -> Result<Vec<TrashEntry>, String> {
+    if !vault_path(app_data_dir).exists() {
+        return Ok(vec![]);
+    }
+
+    let mut entries: Vec<TrashEntry> = read_vault(app_data_dir)?
+        .trash
+        .into_iter()
+        .map(|item| TrashEntry {
+            id: item.account.id,
+            user_id: item.account.user_id,
+            username: item.account.username,
+            display_name: item.account.display_name,
+            thumbnail: item.account.thumbnail,
+            alias: item.account.alias,
+            deleted_at: item.deleted_at,
+            expires_at: (retention_days > 0)
+                .then(|| item.deleted_at + retention_days as u64 * DAY_SECS),
+        })
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+    Ok(entries)
+}
+
+/// Move accounts back from the recycle bin, returning how many were restored.
+/// An account whose Roblox user is already in the vault stays in the trash.
+pub fn restore(app_data_dir: &Path, account_ids: &[String]) -> Result<usize, String> {
+    update_vault(app_data_dir, |vault| restore_in(vault, account_ids))
+}
+
+// Helper: `restore` on a vault being updated
+fn restore_in(vault: &mut VaultData, account_ids: &[String]) -> Result<usize, String> {
+    let mut next_order = vault
+        .accounts
+        .iter()
+        .map(|a| a.sort_order + 1)
+        .max()
+        .unwrap_or(0);
+
+    let mut restored = 0;
+    let mut conflict = None;
+    let mut remaining = Vec::new();
+
+    for item in std::mem::take(&mut vault.trash) {
+        if !account_ids.contains(&item.account.id) {
+            remaining.push(item);
+        } else if vault
+            .accounts
+            .iter()
+            .any(|a| a.user_id == item.account.user_id)
+        {
+            conflict = Some(item.account.username.clone());
+            remaining.push(item);
+        } else {
+            let mut account = item.account;
+            account.sort_order = next_order;
+            next_order += 1;
+            vault.accounts.push(account);
+            restored += 1;
+        }
+    }
+
+    if restored == 0 {
+        return Err(match conflict {
+            Some(username) => format!("Account {} is already in the vault", username),
+            None => "Account not found in the recycle bin".to_string(),
+        });
+    }
+
+    vault.trash = remaining;
+    Ok(restored)
+}
+
+// Helper: Drop trash entries not kept by `keep`, then their environments
+fn purge_where(
+    app_data_dir: &Path,
+    keep: impl Fn(&TrashedAccount) -> bool,
+) -> Result<usize, String> {
+    let purged = update_vault(app_data_dir, |vault| {
+        let (kept, purged): (Vec<_>, Vec<_>) = std::mem::take(&mut vault.trash)
+            .into_iter()
+            .partition(|item| keep(item));
+        vault.trash = kept;
+        Ok(purged)
+    })?;
+
+    // Environments go after the vault lock is released
+    for item in &purged {
+        if let Err(e) = remove_profile(app_data_dir, &item.account.id) {
+            log::warn!("Failed to remove environment of {}: {}", item.account.id, e);
+        }
+    }
+    Ok(purged.len())
+}
+
+/// Permanently delete accounts from the recycle bin (`None` empties it).
+/// Returns how many were purged.
+pub fn purge(app_data_dir: &Path, account_ids: Option<&[String]>) -> Result<usize, String> {
+    purge_where(app_data_dir, |item| match account_ids {
+        Some(ids) => !ids.contains(&item.account.id),
+        None => false,
+    })
+}
+
+/// Purge entries older than `retention_days` (0 keeps them forever)
+pub fn purge_expired(app_data_dir: &Path, retention_days: u32) -> Result<usize, String> {
+    if retention_days == 0 || !vault_path(app_data_dir).exists() {
+        return Ok(0);
+    }
+
+    let cutoff = now().saturating_sub(retention_days as u64 * DAY_SECS);
+    let purged = purge_where(app_data_dir, |item| item.deleted_at > cutoff)?;
+    if purged > 0 {
+        log::info!("Purged {} expired account(s) from the recycle bin", purged);
+    }
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{derive_key, write_empty_vault};
+    use crate::profiles::Profile;
+    use crate::test_support::{profile, temp_dir};
+    use crate::vault::{load_accounts, save_accounts};
+    use std::path::PathBuf;
+
+    fn temp_vault() -> (PathBuf, [u8; 32]) {
+        let dir = temp_dir("trash");
+        let key = derive_key("pw");
+        write_empty_vault(&dir, &key).unwrap();
+        (dir, key)
+    }
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_trash_restore_and_purge() {
+        let (dir, key) = temp_vault();
+        let first = Profile {
+            created_at: Some(1),
+            ..profile(1)
+        };
+        save_accounts(&dir, &key, &[first, profile(2), profile(3)]).unwrap();
+
+        assert_eq!(move_to_trash(&dir, &ids(&["acc-1", "acc-2"])).unwrap(), 2);
+        assert_eq!(load_accounts(&dir, &key).unwrap().len(), 1);
+        let trash = list_trash(&dir, 30).unwrap();
+        assert_eq!(trash.len(), 2);
+        assert!(trash[0].expires_at.unwrap() > trash[0].deleted_at);
+
+        // Restored accounts come back with their cookie, after the others
+        assert_eq!(restore(&dir, &ids(&["acc-1"])).unwrap(), 1);
+        let accounts = load_accounts(&dir, &key).unwrap();
+        assert_eq!(accounts[1].id, "acc-1");
+        assert_eq!(accounts[1].cookie, "COOKIE_1");
+        assert_eq!(accounts[1].created_at, Some(1));
+
+        // The same Roblox user cannot be restored twice
+        move_all_to_trash(&dir).unwrap();
+        save_accounts(&dir, &key, &[profile(2)]).unwrap();
+        assert_eq!(
+            restore(&dir, &ids(&["acc-2"])),
+            Err("Account user2 is already in the vault".to_string())
+        );
+
+        assert_eq!(purge(&dir, Some(&ids(&["acc-2"]))).unwrap(), 1);
+        assert_eq!(purge(&dir, None).unwrap(), 2);
+        assert!(list_trash(&dir, 30).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_purge_expired() {
+        let (dir, key) = temp_vault();
+        save_accounts(&dir, &key, &[profile(1), profile(2)]).unwrap();
+        move_all_to_trash(&dir).unwrap();
+
+        update_vault(&dir, |vault| {
+            vault.trash[0].deleted_at -= 31 * DAY_SECS;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(purge_expired(&dir, 0).unwrap(), 0);
+        assert_eq!(purge_expired(&dir, 30).unwrap(), 1);
+        assert_eq!(list_trash(&dir, 30).unwrap()[0].id, "acc-2");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
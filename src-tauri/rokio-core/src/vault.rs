@@ -0,0 +1,545 @@
+//! ROKIO Vault Module
+//! Handles encrypted account storage (load/save operations).
+
+use crate::crypto::{
+    decrypt_string, derive_key, encrypt_string, vault_path, verify_password, VAULT_VERIFICATION,
+};
+use crate::profiles::Profile;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::Mutex;
+
+/// Held for every change to a vault file, so read-modify-write sequences in
+/// this process don't overwrite each other
+static VAULT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Vault data structure (stored encrypted on disk)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultData {
+    pub version: u32,
+    pub verification: String,
+    pub accounts: Vec<EncryptedAccount>,
+    /// Deleted accounts, still encrypted (see `trash`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trash: Vec<TrashedAccount>,
+}
+
+/// Encrypted account (stored in vault)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedAccount {
+    pub id: String,
+    pub encrypted_cookie: String,
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: String,
+    pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub alias: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub folder: String,
+    #[serde(default)]
+    pub sort_order: u32,
+    pub is_favorite: bool,
+    pub last_played_at: u64,
+    pub created_at: u64,
+}
+
+/// Account in the recycle bin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedAccount {
+    #[serde(flatten)]
+    pub account: EncryptedAccount,
+    /// Unix time the account was deleted
+    pub deleted_at: u64,
+}
+
+// ============================================================================
+// VAULT OPERATIONS
+// ============================================================================
+
+/// Read and parse the vault file
+pub(crate) fn read_vault(app_data_dir: &std::path::Path) -> Result<VaultData, String> {
+    let content = fs::read_to_string(vault_path(app_data_dir)).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+// Helper: Write the vault file back. Only called under `VAULT_LOCK`.
+fn write_vault(app_data_dir: &std::path::Path, vault: &VaultData) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(vault).map_err(|e| e.to_string())?;
+    write_atomic(&vault_path(app_data_dir), json.as_bytes())
+}
+
+/// Replace `path` with `contents` through a temporary file in the same
+/// directory, so a crash mid-write leaves either the old or the new file
+pub(crate) fn write_atomic(path: &std::path::Path, contents: &[u8]) -> Result<(), String> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+    let tmp = std::path::PathBuf::from(tmp);
+
+    fs::write(&tmp, contents).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        e.to_string()
+    })
+}
+
+/// Read the vault, change it with `f` and write it back, all under the vault
+/// lock. Nothing is written when `f` fails.
+pub(crate) fn update_vault<T>(
+    app_data_dir: &std::path::Path,
+    f: impl FnOnce(&mut VaultData) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = VAULT_LOCK.lock().unwrap();
+    let mut vault = read_vault(app_data_dir)?;
+    let result = f(&mut vault)?;
+    write_vault(app_data_dir, &vault)?;
+    Ok(result)
+}
+
+/// Load the accounts, change them with `f` and save them, all under the vault
+/// lock. Every load-modify-save of the accounts goes through here so a
+/// concurrent change (e.g. a rotated cookie) is never lost. Nothing is saved
+/// when `f` fails.
+pub fn update_accounts<T>(
+    app_data_dir: &std::path::Path,
+    key: &[u8; 32],
+    f: impl FnOnce(&mut Vec<Profile>) -> Result<T, String>,
+) -> Result<T, String> {
+    update_vault(app_data_dir, |vault| {
+        let mut accounts = decrypt_accounts(std::mem::take(&mut vault.accounts), key)?;
+        let result = f(&mut accounts)?;
+        vault.accounts = encrypt_accounts(&accounts, key)?;
+        Ok(result)
+    })
+}
+
+/// Load all accounts from the encrypted vault
+pub fn load_accounts(
+    app_data_dir: &std::path::Path,
+    key: &[u8; 32],
+) -> Result<Vec<Profile>, String> {
+    if !vault_path(app_data_dir).exists() {
+        return Ok(vec![]);
+    }
+
+    decrypt_accounts(read_vault(app_data_dir)?.accounts, key)
+}
+
+// Helper: Decrypt each account's cookie, in display order
+fn decrypt_accounts(
+    encrypted: Vec<EncryptedAccount>,
+    key: &[u8; 32],
+) -> Result<Vec<Profile>, String> {
+    let mut accounts = encrypted
+        .into_iter()
+        .map(|enc_acc| decrypt_account(enc_acc, key))
+        .collect::<Result<Vec<Profile>, String>>()?;
+
+    // Stable, so vaults written before custom ordering keep their file order
+    accounts.sort_by_key(|a| a.sort_order);
+    Ok(accounts)
+}
+
+// Helper: Encrypt each account's cookie
+fn encrypt_accounts(accounts: &[Profile], key: &[u8; 32]) -> Result<Vec<EncryptedAccount>, String> {
+    accounts.iter().map(|acc| encrypt_account(acc, key)).collect()
+}
+
+pub(crate) fn decrypt_account(
+    enc_acc: EncryptedAccount,
+    key: &[u8; 32],
+) -> Result<Profile, String> {
+    let cookie = decrypt_string(&enc_acc.encrypted_cookie, key)?;
+    Ok(Profile {
+        id: enc_acc.id,
+        cookie,
+        user_id: enc_acc.user_id,
+        username: enc_acc.username,
+        display_name: enc_acc.display_name,
+        thumbnail: enc_acc.thumbnail,
+        alias: enc_acc.alias,
+        description: enc_acc.description,
+        tags: enc_acc.tags,
+        folder: enc_acc.folder,
+        sort_order: enc_acc.sort_order,
+        is_favorite: enc_acc.is_favorite,
+        last_played_at: enc_acc.last_played_at,
+        password: None,
+        created_at: Some(enc_acc.created_at),
+        is_premium: None,
+    })
+}
+
+/// Load one account by ID
+pub fn find_account(
+    app_data_dir: &std::path::Path,
+    key: &[u8; 32],
+    account_id: &str,
+) -> Result<Profile, String> {
+    load_accounts(app_data_dir, key)?
+        .into_iter()
+        .find(|a| a.id == account_id)
+        .ok_or_else(|| "Account not found".to_string())
+}
+
+/// Decrypt one account's cookie for the user after re-checking the master
+/// password. Every attempt is written to the audit log; if that fails the
+/// cookie is not revealed.
+pub fn reveal_cookie(
+    app_data_dir: &std::path::Path,
+    account_id: &str,
+    password: &str,
+) -> Result<String, String> {
+    let result = match verify_password(app_data_dir, password)? {
+        Some(key) => find_account(app_data_dir, &key, account_id).map(|a| a.cookie),
+        None => Err("Incorrect password".to_string()),
+    };
+
+    crate::audit::record(app_data_dir, "reveal_cookie", Some(account_id), result.is_ok())?;
+    result
+}
+
+/// Replace all accounts in the encrypted vault (see `update_accounts` to
+/// change the current ones)
+pub fn save_accounts(
+    app_data_dir: &std::path::Path,
+    key: &[u8; 32],
+    accounts: &[Profile],
+) -> Result<(), String> {
+    // The verification string and trash are kept as they are
+    update_vault(app_data_dir, |vault| {
+        vault.accounts = encrypt_accounts(accounts, key)?;
+        Ok(())
+    })
+}
+
+pub(crate) fn encrypt_account(acc: &Profile, key: &[u8; 32]) -> Result<EncryptedAccount, String> {
+    let encrypted_cookie = encrypt_string(&acc.cookie, key)?;
+    Ok(EncryptedAccount {
+        id: acc.id.clone(),
+        encrypted_cookie,
+        user_id: acc.user_id,
+        username: acc.username.clone(),
+        display_name: acc.display_name.clone(),
+        thumbnail: acc.thumbnail.clone(),
+        alias: acc.alias.clone(),
+        description: acc.description.clone(),
+        tags: acc.tags.clone(),
+        folder: acc.folder.clone(),
+        sort_order: acc.sort_order,
+        is_favorite: acc.is_favorite,
+        last_played_at: acc.last_played_at,
+        created_at: acc
+            .created_at
+            .unwrap_or_else(|| chrono::Utc::now().timestamp() as u64),
+    })
+}
+
+/// Validate a cookie with Roblox and add the account to the vault
+pub async fn add_account_with_cookie(
+    app_data_dir: &std::path::Path,
+    key: &[u8; 32],
+    cookie: &str,
+) -> Result<Profile, String> {
+    // Validate cookie and get user data from Roblox API
+    let user_data = crate::roblox::validate_and_get_user(cookie).await?;
+
+    // Create new profile
+    let mut profile = Profile {
+        id: uuid::Uuid::new_v4().to_string(),
+        cookie: cookie.to_string(),
+        user_id: user_data.id,
+        username: user_data.name,
+        display_name: user_data.display_name,
+        thumbnail: user_data.thumbnail,
+        alias: String::new(),
+        description: String::new(),
+        tags: Vec::new(),
+        folder: String::new(),
+        sort_order: 0,
+        is_favorite: false,
+        last_played_at: 0,
+        password: None,
+        created_at: Some(chrono::Utc::now().timestamp() as u64),
+        is_premium: None,
+    };
+
+    update_accounts(app_data_dir, key, |accounts| {
+        profile.sort_order = next_sort_order(accounts);
+
+        // Check for duplicate
+        if accounts.iter().any(|a| a.user_id == profile.user_id) {
+            return Err(format!(
+                "Account {} is already added",
+                profile.display_name
+            ));
+        }
+
+        accounts.push(profile.clone());
+        Ok(())
+    })?;
+
+    Ok(profile)
+}
+
+/// Sort order that places a new account after all existing ones
+pub fn next_sort_order(accounts: &[Profile]) -> u32 {
+    accounts.iter().map(|a| a.sort_order + 1).max().unwrap_or(0)
+}
+
+/// Move one account to the recycle bin (see `trash`)
+pub fn remove_account(app_data_dir: &std::path::Path, id: &str) -> Result<(), String> {
+    match crate::trash::move_to_trash(app_data_dir, &[id.to_string()])? {
+        0 => Err("Account not found".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Raw encrypted vault file content (for backup)
+pub fn export_backup(app_data_dir: &std::path::Path) -> Result<String, String> {
+    let vault_file = vault_path(app_data_dir);
+
+    if !vault_file.exists() {
+        return Ok("{}".to_string());
+    }
+
+    // Return the raw encrypted vault file content
+    fs::read_to_string(&vault_file).map_err(|e| e.to_string())
+}
+
+/// Import accounts from a backup made with `export_backup`.
+/// Returns the number of accounts that could be decrypted with `key`.
+pub fn import_backup(
+    app_data_dir: &std::path::Path,
+    key: &[u8; 32],
+    data: &str,
+    merge: bool,
+) -> Result<usize, String> {
+    // Parse imported vault data
+    let imported_vault: VaultData = serde_json::from_str(data)
+        .map_err(|e| format!("Invalid backup file: {}", e))?;
+
+    // Decrypt imported accounts
+    let imported_accounts: Vec<Profile> = imported_vault
+        .accounts
+        .into_iter()
+        .filter_map(|enc_acc| decrypt_account(enc_acc, key).ok())
+        .collect();
+
+    let count = imported_accounts.len();
+
+    if merge {
+        // Merge with existing accounts
+        update_accounts(app_data_dir, key, |existing| {
+            for mut acc in imported_accounts {
+                if !existing.iter().any(|e| e.user_id == acc.user_id) {
+                    acc.sort_order = next_sort_order(existing);
+                    existing.push(acc);
+                }
+            }
+            Ok(())
+        })?;
+    } else {
+        // Replace all accounts, keeping the old ones in the recycle bin
+        update_vault(app_data_dir, |vault| {
+            let ids: Vec<String> = vault.accounts.iter().map(|a| a.id.clone()).collect();
+            crate::trash::trash_in(vault, &ids);
+            vault.accounts = encrypt_accounts(&imported_accounts, key)?;
+            Ok(())
+        })?;
+    }
+
+    Ok(count)
+}
+
+/// Re-encrypt the whole vault under a new master password.
+/// Returns the new key.
+pub fn change_password(
+    app_data_dir: &std::path::Path,
+    old_key: &[u8; 32],
+    new_password: &str,
+) -> Result<[u8; 32], String> {
+    let new_key = derive_key(new_password);
+
+    // One write, so the vault never mixes old and new encryption
+    update_vault(app_data_dir, |vault| {
+        vault.verification = encrypt_string(VAULT_VERIFICATION, &new_key)?;
+        let accounts = decrypt_accounts(std::mem::take(&mut vault.accounts), old_key)?;
+        vault.accounts = encrypt_accounts(&accounts, &new_key)?;
+        for item in vault.trash.iter_mut() {
+            let cookie = decrypt_string(&item.account.encrypted_cookie, old_key)?;
+            item.account.encrypted_cookie = encrypt_string(&cookie, &new_key)?;
+        }
+        Ok(())
+    })?;
+
+    Ok(new_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::write_empty_vault;
+    use crate::test_support::{profile, temp_dir};
+    use std::path::PathBuf;
+
+    fn temp_vault(password: &str) -> (PathBuf, [u8; 32]) {
+        let dir = temp_dir("vault");
+        let key = derive_key(password);
+        write_empty_vault(&dir, &key).unwrap();
+        (dir, key)
+    }
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        let (dir, key) = temp_vault("pw");
+        save_accounts(&dir, &key, &[profile(1), profile(2)]).unwrap();
+
+        let accounts = load_accounts(&dir, &key).unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts[0].cookie, "COOKIE_1");
+        assert_eq!(accounts[1].username, "user2");
+
+        // Cookies never hit the disk in plain text
+        let raw = fs::read_to_string(vault_path(&dir)).unwrap();
+        assert!(!raw.contains("COOKIE_1"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_updates_are_not_lost() {
+        let (dir, key) = temp_vault("pw");
+        save_accounts(&dir, &key, &[profile(1)]).unwrap();
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    update_accounts(&dir, &key, |accounts| {
+                        accounts[0].last_played_at += 1;
+                        Ok(())
+                    })
+                    .unwrap()
+                });
+            }
+        });
+        assert_eq!(load_accounts(&dir, &key).unwrap()[0].last_played_at, 8);
+
+        // A failed update writes nothing
+        let result = update_accounts(&dir, &key, |accounts| {
+            accounts.clear();
+            Err::<(), _>("nope".to_string())
+        });
+        assert!(result.is_err());
+        assert_eq!(load_accounts(&dir, &key).unwrap().len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_keeps_created_at_and_order() {
+        let (dir, key) = temp_vault("pw");
+        let mut first = profile(1);
+        first.created_at = Some(1_600_000_000);
+        first.sort_order = 1;
+        let mut second = profile(2);
+        second.tags = vec!["farm".to_string()];
+        save_accounts(&dir, &key, &[first, second]).unwrap();
+
+        let accounts = load_accounts(&dir, &key).unwrap();
+        assert_eq!(accounts[0].id, "acc-2");
+        assert_eq!(accounts[0].tags, vec!["farm".to_string()]);
+        assert_eq!(accounts[1].created_at, Some(1_600_000_000));
+
+        // A second save must not touch the stored timestamp
+        save_accounts(&dir, &key, &accounts).unwrap();
+        let accounts = load_accounts(&dir, &key).unwrap();
+        assert_eq!(accounts[1].created_at, Some(1_600_000_000));
+        assert_eq!(next_sort_order(&accounts), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_remove_account() {
+        let (dir, key) = temp_vault("pw");
+        save_accounts(&dir, &key, &[profile(1), profile(2)]).unwrap();
+
+        remove_account(&dir, "acc-1").unwrap();
+        assert_eq!(
+            remove_account(&dir, "acc-1"),
+            Err("Account not found".to_string())
+        );
+
+        let accounts = load_accounts(&dir, &key).unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].id, "acc-2");
+
+        // Removed accounts wait in the recycle bin
+        let trash = read_vault(&dir).unwrap().trash;
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].account.id, "acc-1");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_import_backup_merge_skips_duplicates() {
+        let (dir, key) = temp_vault("pw");
+        save_accounts(&dir, &key, &[profile(1), profile(2)]).unwrap();
+        let backup = export_backup(&dir).unwrap();
+
+        save_accounts(&dir, &key, &[profile(2), profile(3)]).unwrap();
+        assert_eq!(import_backup(&dir, &key, &backup, true).unwrap(), 2);
+        assert_eq!(load_accounts(&dir, &key).unwrap().len(), 3);
+
+        assert_eq!(import_backup(&dir, &key, &backup, false).unwrap(), 2);
+        assert_eq!(load_accounts(&dir, &key).unwrap().len(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reveal_cookie_is_audited() {
+        let (dir, key) = temp_vault("pw");
+        save_accounts(&dir, &key, &[profile(1)]).unwrap();
+
+        assert_eq!(
+            reveal_cookie(&dir, "acc-1", "wrong"),
+            Err("Incorrect password".to_string())
+        );
+        assert_eq!(reveal_cookie(&dir, "acc-1", "pw").unwrap(), "COOKIE_1");
+
+        let log = crate::audit::list(&dir, 10).unwrap();
+        assert_eq!(log.len(), 2);
+        assert!(log[0].success);
+        assert!(!log[1].success);
+        assert_eq!(log[1].account_id.as_deref(), Some("acc-1"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_change_password() {
+        let (dir, old_key) = temp_vault("old");
+        save_accounts(&dir, &old_key, &[profile(1), profile(2)]).unwrap();
+        remove_account(&dir, "acc-2").unwrap();
+
+        let new_key = change_password(&dir, &old_key, "new").unwrap();
+
+        assert_eq!(verify_password(&dir, "old").unwrap(), None);
+        assert_eq!(verify_password(&dir, "new").unwrap(), Some(new_key));
+        assert_eq!(load_accounts(&dir, &new_key).unwrap()[0].cookie, "COOKIE_1");
+        assert!(load_accounts(&dir, &old_key).is_err());
+        let trashed = read_vault(&dir).unwrap().trash.remove(0).account;
+        assert_eq!(decrypt_account(trashed, &new_key).unwrap().cookie, "COOKIE_2");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
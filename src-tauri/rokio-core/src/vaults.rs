@@ -0,0 +1,364 @@
+//! ROKIO Vaults - Several named vaults, each with its own master password
+//!
+//! The default vault lives directly in the app data directory, where single
+//! vault installs always kept it; other vaults live in `vaults/<id>/`. Each
+//! vault directory holds its own `vault.dat` (accounts and recycle bin) and
+//! `environments/`. `crypto::vault_path` and `environment::profile_dir_in`
+//! resolve through the active vault, so the rest of the crate keeps taking the
+//! app data directory. Names and the active vault are kept in `vaults.json`.
+//!
+//! `vaults.json` is shared with every other ROKIO process, so a process holding
+//! a vault key pins the vault it unlocked (`pin`): a switch made elsewhere,
+//! e.g. by the CLI, then never pairs its key with another vault's files.
+//!
+//! Launch presets live in each vault's directory too. Shared by all vaults:
+//! settings, the automation API token and `rokio.db` (game cache, launch
+//! history and audit log, which only hold account IDs and usernames).
+
+use crate::crypto::{verify_password_at, write_empty_vault_at, VAULT_FILE};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// ID of the vault stored directly in the app data directory
+pub const DEFAULT_VAULT_ID: &str = "default";
+const REGISTRY_FILE: &str = "vaults.json";
+const MAX_NAME_LEN: usize = 40;
+
+/// Vault this process is pinned to, with the app data directory it is in
+static PINNED: Mutex<Option<(PathBuf, VaultEntry)>> = Mutex::new(None);
+
+/// A named vault
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+/// Vault as listed to the user
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultInfo {
+    pub id: String,
+    pub name: String,
+    pub created_at: i64,
+    /// The vault commands currently work on (the pinned one, if any)
+    pub active: bool,
+    /// Whether a master password has been set (the default vault starts empty)
+    pub exists: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Registry {
+    active: String,
+    vaults: Vec<VaultEntry>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_VAULT_ID.to_string(),
+            vaults: vec![VaultEntry {
+                id: DEFAULT_VAULT_ID.to_string(),
+                name: "Default".to_string(),
+                created_at: 0,
+            }],
+        }
+    }
+}
+
+impl Registry {
+    fn find(&self, query: &str) -> Result<&VaultEntry, String> {
+        self.vaults
+            .iter()
+            .find(|v| v.id == query || v.name.eq_ignore_ascii_case(query))
+            .ok_or_else(|| format!("Vault '{}' not found", query))
+    }
+
+    /// Trimmed `name`, unless it is empty, too long or taken by another vault
+    fn check_name(&self, name: &str, except_id: Option<&str>) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Vault name cannot be empty".to_string());
+        }
+        if name.chars().count() > MAX_NAME_LEN {
+            return Err(format!(
+                "Vault name is longer than {} characters",
+                MAX_NAME_LEN
+            ));
+        }
+        let taken = self
+            .vaults
+            .iter()
+            .any(|v| Some(v.id.as_str()) != except_id && v.name.eq_ignore_ascii_case(name));
+        if taken {
+            return Err(format!("A vault named '{}' already exists", name));
+        }
+        Ok(name.to_string())
+    }
+}
+
+// Helper: Read the registry; a missing file means only the default vault
+fn load_registry(app_data_dir: &Path) -> Result<Registry, String> {
+    let path = app_data_dir.join(REGISTRY_FILE);
+    if !path.exists() {
+        return Ok(Registry::default());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let mut registry: Registry =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", REGISTRY_FILE, e))?;
+
+    if !registry.vaults.iter().any(|v| v.id == DEFAULT_VAULT_ID) {
+        registry
+            .vaults
+            .insert(0, Registry::default().vaults.remove(0));
+    }
+    if !registry.vaults.iter().any(|v| v.id == registry.active) {
+        registry.active = DEFAULT_VAULT_ID.to_string();
+    }
+    Ok(registry)
+}
+
+fn save_registry(app_data_dir: &Path, registry: &Registry) -> Result<(), String> {
+    fs::create_dir_all(app_data_dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(registry).map_err(|e| e.to_string())?;
+    fs::write(app_data_dir.join(REGISTRY_FILE), json)
+        .map_err(|e| format!("Failed to save vaults: {}", e))
+}
+
+/// Directory holding a vault's files
+pub fn vault_dir(app_data_dir: &Path, id: &str) -> PathBuf {
+    if id == DEFAULT_VAULT_ID {
+        app_data_dir.to_path_buf()
+    } else {
+        app_data_dir.join("vaults").join(id)
+    }
+}
+
+/// Pin this process to vault `id`: until `unpin`, it is the active vault here
+/// whatever other processes switch to, and is resolved without reading
+/// `vaults.json`. Set while holding the vault's key.
+pub fn pin(app_data_dir: &Path, id: &str) {
+    let entry = load_registry(app_data_dir)
+        .unwrap_or_default()
+        .vaults
+        .into_iter()
+        .find(|v| v.id == id)
+        .unwrap_or_else(|| VaultEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            created_at: 0,
+        });
+    *PINNED.lock().unwrap() = Some((app_data_dir.to_path_buf(), entry));
+}
+
+/// Follow the active vault in `vaults.json` again
+pub fn unpin() {
+    *PINNED.lock().unwrap() = None;
+}
+
+// Helper: The vault this process is pinned to in `app_data_dir`
+fn pinned(app_data_dir: &Path) -> Option<VaultEntry> {
+    match &*PINNED.lock().unwrap() {
+        Some((dir, entry)) if dir == app_data_dir => Some(entry.clone()),
+        _ => None,
+    }
+}
+
+// Helper: ID of the active vault, the pinned one first
+fn active_id(app_data_dir: &Path, registry: &Registry) -> String {
+    pinned(app_data_dir).map_or_else(|| registry.active.clone(), |v| v.id)
+}
+
+/// The active vault: the pinned one, otherwise the one `vaults.json` names
+/// (the default one if it can't be read)
+pub fn active(app_data_dir: &Path) -> VaultEntry {
+    if let Some(entry) = pinned(app_data_dir) {
+        return entry;
+    }
+    let registry = load_registry(app_data_dir).unwrap_or_default();
+    let id = registry.active.clone();
+    registry
+        .vaults
+        .into_iter()
+        .find(|v| v.id == id)
+        .unwrap_or_else(|| VaultEntry {
+            name: id.clone(),
+            id,
+            created_at: 0,
+        })
+}
+
+/// Directory of the active vault
+pub fn active_dir(app_data_dir: &Path) -> PathBuf {
+    vault_dir(app_data_dir, &active(app_data_dir).id)
+}
+
+/// Every vault, in creation order
+pub fn list(app_data_dir: &Path) -> Result<Vec<VaultInfo>, String> {
+    let registry = load_registry(app_data_dir)?;
+    let active_id = active_id(app_data_dir, &registry);
+    Ok(registry
+        .vaults
+        .iter()
+        .map(|v| VaultInfo {
+            id: v.id.clone(),
+            name: v.name.clone(),
+            created_at: v.created_at,
+            active: v.id == active_id,
+            exists: vault_dir(app_data_dir, &v.id).join(VAULT_FILE).exists(),
+        })
+        .collect())
+}
+
+/// Create an empty vault protected by `key`. The active vault is unchanged.
+pub fn create(app_data_dir: &Path, name: &str, key: &[u8; 32]) -> Result<VaultEntry, String> {
+    let mut registry = load_registry(app_data_dir)?;
+    let entry = VaultEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: registry.check_name(name, None)?,
+        created_at: chrono::Utc::now().timestamp(),
+    };
+
+    write_empty_vault_at(&vault_dir(app_data_dir, &entry.id).join(VAULT_FILE), key)?;
+    registry.vaults.push(entry.clone());
+    save_registry(app_data_dir, &registry)?;
+    Ok(entry)
+}
+
+/// Rename a vault (by ID or name)
+pub fn rename(app_data_dir: &Path, query: &str, name: &str) -> Result<VaultEntry, String> {
+    let mut registry = load_registry(app_data_dir)?;
+    let id = registry.find(query)?.id.clone();
+    let name = registry.check_name(name, Some(&id))?;
+
+    let entry = registry
+        .vaults
+        .iter_mut()
+        .find(|v| v.id == id)
+        .ok_or("Vault not found")?;
+    entry.name = name;
+    let entry = entry.clone();
+
+    save_registry(app_data_dir, &registry)?;
+    if let Some((dir, pinned)) = &mut *PINNED.lock().unwrap() {
+        if dir == app_data_dir && pinned.id == entry.id {
+            *pinned = entry.clone();
+        }
+    }
+    Ok(entry)
+}
+
+/// Make a vault (by ID or name) the active one
+pub fn switch(app_data_dir: &Path, query: &str) -> Result<VaultEntry, String> {
+    let mut registry = load_registry(app_data_dir)?;
+    let entry = registry.find(query)?.clone();
+    registry.active = entry.id.clone();
+    save_registry(app_data_dir, &registry)?;
+    Ok(entry)
+}
+
+/// Delete a vault with its accounts and environments for good. Needs the
+/// vault's own master password; the active and the default vault are kept.
+pub fn delete(app_data_dir: &Path, query: &str, password: &str) -> Result<(), String> {
+    let mut registry = load_registry(app_data_dir)?;
+    let entry = registry.find(query)?.clone();
+    if entry.id == DEFAULT_VAULT_ID {
+        return Err("The default vault cannot be deleted".to_string());
+    }
+    if entry.id == registry.active || entry.id == active_id(app_data_dir, &registry) {
+        return Err("Switch to another vault before deleting this one".to_string());
+    }
+
+    let dir = vault_dir(app_data_dir, &entry.id);
+    let vault_file = dir.join(VAULT_FILE);
+    if vault_file.exists() && verify_password_at(&vault_file, password)?.is_none() {
+        return Err("Wrong password".to_string());
+    }
+
+    registry.vaults.retain(|v| v.id != entry.id);
+    save_registry(app_data_dir, &registry)?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete vault: {}", e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{derive_key, vault_path, verify_password, write_empty_vault};
+    use crate::environment::profile_dir_in;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_switching_moves_vault_and_environments() {
+        let root = temp_dir("vaults");
+        write_empty_vault(&root, &derive_key("home")).unwrap();
+        assert_eq!(vault_path(&root), root.join(VAULT_FILE));
+
+        let work = create(&root, " Work ", &derive_key("work")).unwrap();
+        assert_eq!(work.name, "Work");
+        assert!(create(&root, "work", &derive_key("x")).is_err());
+        assert_eq!(active(&root).id, DEFAULT_VAULT_ID);
+
+        switch(&root, "work").unwrap();
+        let work_dir = root.join("vaults").join(&work.id);
+        assert_eq!(vault_path(&root), work_dir.join(VAULT_FILE));
+        assert_eq!(
+            profile_dir_in(&root, "acc-1"),
+            work_dir.join("environments").join("acc-1")
+        );
+        assert!(verify_password(&root, "work").unwrap().is_some());
+        assert!(verify_password(&root, "home").unwrap().is_none());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_pinned_vault_ignores_other_switches() {
+        let root = temp_dir("vaults");
+        let work = create(&root, "Work", &derive_key("work")).unwrap();
+        let home = create(&root, "Home", &derive_key("home")).unwrap();
+
+        switch(&root, &work.id).unwrap();
+        pin(&root, &work.id);
+        // Another process switching the registry doesn't move this one
+        switch(&root, &home.id).unwrap();
+        assert_eq!(active(&root).id, work.id);
+        assert!(verify_password(&root, "work").unwrap().is_some());
+        assert!(list(&root).unwrap().iter().any(|v| v.id == work.id && v.active));
+        assert!(delete(&root, &work.id, "work").is_err());
+
+        unpin();
+        assert_eq!(active(&root).id, home.id);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_rename_and_delete() {
+        let root = temp_dir("vaults");
+        let test = create(&root, "Test", &derive_key("pw")).unwrap();
+
+        assert!(rename(&root, &test.id, "default").is_err());
+        rename(&root, "test", "QA").unwrap();
+        assert!(delete(&root, DEFAULT_VAULT_ID, "").is_err());
+
+        switch(&root, "QA").unwrap();
+        assert!(delete(&root, "QA", "pw").is_err());
+        switch(&root, DEFAULT_VAULT_ID).unwrap();
+
+        assert!(delete(&root, "QA", "wrong").is_err());
+        delete(&root, "QA", "pw").unwrap();
+        assert!(!vault_dir(&root, &test.id).exists());
+        assert_eq!(list(&root).unwrap().len(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,441 @@
+//! Automation API - Local HTTP/JSON interface for scripting ROKIO
+//!
+//! Opt-in (Settings → Advanced). The server only binds to 127.0.0.1 and every
+//! request except `GET /openapi.json` needs `Authorization: Bearer <token>`.
+//! The token is generated once per install and stored next to the vault; the
+//! server keeps it in memory and swaps it when it is regenerated.
+//! Requests are served by a fixed pool of workers, the token is checked before
+//! the body is read, and bodies are capped at a few kilobytes.
+//!
+//! Routes are thin wrappers around the existing Tauri commands, so they follow
+//! the same vault lock rules as the UI: everything but `/v1/status` answers
+//! 423 Locked while the vault is locked.
+
+use crate::crypto::CryptoState;
+use crate::{game_detection, launcher, roblox, vault};
+use rand::RngCore;
+use rokio_core::settings::AppSettings;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// OpenAPI 3 description of the routes below
+const OPENAPI: &str = include_str!("openapi.json");
+const TOKEN_FILE: &str = "automation_token";
+/// Worker threads serving requests (launches block one for a few seconds)
+const WORKERS: usize = 4;
+/// Largest request body accepted; every route takes a small JSON object
+const MAX_BODY_BYTES: usize = 16 * 1024;
+
+/// Running server (port + handle used to stop it) and the bearer token
+#[derive(Default)]
+pub struct AutomationApiState {
+    server: Mutex<Option<(u16, Arc<Server>)>>,
+    /// Loaded from disk on first use, replaced by `generate_token`
+    token: Mutex<Option<String>>,
+}
+
+/// Connection details shown in Settings
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationApiInfo {
+    pub enabled: bool,
+    pub running: bool,
+    pub base_url: String,
+    pub token: String,
+}
+
+/// Account as exposed over the API (no cookie)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiAccount {
+    id: String,
+    user_id: i64,
+    username: String,
+    display_name: String,
+    alias: String,
+    tags: Vec<String>,
+    folder: String,
+    is_favorite: bool,
+    last_played_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchBody {
+    account_id: String,
+    place_id: Option<u64>,
+    job_id: Option<String>,
+    link: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchVipBody {
+    account_id: String,
+    place_id: u64,
+    link_code: String,
+}
+
+// ============================================================================
+// SERVER LIFECYCLE
+// ============================================================================
+
+/// Start, restart or stop the server to match the current settings
+pub fn sync_with_settings(app: &AppHandle, settings: &AppSettings) {
+    let state = app.state::<AutomationApiState>();
+    let mut running = state.server.lock().unwrap();
+
+    let wanted = settings
+        .automation_api_enabled
+        .then_some(settings.automation_api_port);
+
+    if let Some((port, server)) = running.as_ref() {
+        if wanted == Some(*port) {
+            return;
+        }
+        // Each call wakes one worker
+        for _ in 0..WORKERS {
+            server.unblock();
+        }
+        *running = None;
+        log::info!("Automation API stopped");
+    }
+
+    if let Some(port) = wanted {
+        match start(app, port) {
+            Ok(server) => {
+                *running = Some((port, server));
+                log::info!("Automation API listening on 127.0.0.1:{}", port);
+            }
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+}
+
+fn start(app: &AppHandle, port: u16) -> Result<Arc<Server>, String> {
+    // Make sure a token exists before anything can connect
+    current_token(app)?;
+
+    let server = Server::http(("127.0.0.1", port))
+        .map_err(|e| format!("Failed to start automation API on port {}: {}", port, e))?;
+    let server = Arc::new(server);
+
+    for _ in 0..WORKERS {
+        let listener = server.clone();
+        let app = app.clone();
+        std::thread::spawn(move || {
+            for request in listener.incoming_requests() {
+                respond(&app, request);
+            }
+        });
+    }
+
+    Ok(server)
+}
+
+// ============================================================================
+// TOKEN
+// ============================================================================
+
+// One token for the whole app, shared by all vaults: requests act on the
+// vault the app has unlocked
+fn token_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data_dir.join(TOKEN_FILE))
+}
+
+fn generate_token(app: &AppHandle) -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    let path = token_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(&path, &token).map_err(|e| format!("Failed to write API token: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+    }
+
+    *app.state::<AutomationApiState>().token.lock().unwrap() = Some(token.clone());
+    Ok(token)
+}
+
+// Helper: The token in memory, read from disk (or created) the first time
+fn current_token(app: &AppHandle) -> Result<String, String> {
+    if let Some(token) = app.state::<AutomationApiState>().token.lock().unwrap().clone() {
+        return Ok(token);
+    }
+
+    match fs::read_to_string(token_path(app)?) {
+        Ok(token) if !token.trim().is_empty() => {
+            let token = token.trim().to_string();
+            *app.state::<AutomationApiState>().token.lock().unwrap() = Some(token.clone());
+            Ok(token)
+        }
+        _ => generate_token(app),
+    }
+}
+
+/// Compare the bearer token without short-circuiting on the first mismatch
+fn is_authorized(app: &AppHandle, request: &Request) -> bool {
+    let Ok(expected) = current_token(app) else {
+        return false;
+    };
+
+    let provided = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .and_then(|h| h.value.as_str().strip_prefix("Bearer "))
+        .unwrap_or("");
+
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+// ============================================================================
+// REQUEST HANDLING
+// ============================================================================
+
+fn respond(app: &AppHandle, mut request: Request) {
+    let (status, body) = handle(app, &mut request);
+
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+
+    if let Err(e) = request.respond(response) {
+        log::warn!("Automation API failed to respond: {}", e);
+    }
+}
+
+fn error(status: u16, message: &str) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
+
+/// Map command errors onto HTTP status codes
+fn command_error(message: String) -> (u16, Value) {
+    let status = match message.as_str() {
+        "Vault is locked" => 423,
+        "Account not found" => 404,
+        m if m.starts_with("No running instance") => 404,
+        _ => 500,
+    };
+    error(status, &message)
+}
+
+fn vault_unlocked(app: &AppHandle) -> bool {
+    app.state::<CryptoState>().key.lock().unwrap().is_some()
+}
+
+fn handle(app: &AppHandle, request: &mut Request) -> (u16, Value) {
+    let method = request.method().clone();
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+
+    if method == Method::Get && path == "/openapi.json" {
+        return (200, serde_json::from_str(OPENAPI).unwrap_or(Value::Null));
+    }
+
+    if !is_authorized(app, request) {
+        return error(401, "Missing or invalid bearer token");
+    }
+
+    if request.body_length().is_some_and(|len| len > MAX_BODY_BYTES) {
+        return error(413, "Request body is too large");
+    }
+    // Chunked bodies have no length up front
+    let mut body = String::new();
+    let mut reader = request.as_reader().take(MAX_BODY_BYTES as u64 + 1);
+    if reader.read_to_string(&mut body).is_err() {
+        return error(400, "Request body is not valid UTF-8");
+    }
+    if body.len() > MAX_BODY_BYTES {
+        return error(413, "Request body is too large");
+    }
+
+    if path != "/v1/status" && !vault_unlocked(app) {
+        return error(423, "Vault is locked");
+    }
+
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (&method, segments.as_slice()) {
+        (Method::Get, ["v1", "status"]) => (
+            200,
+            json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "unlocked": vault_unlocked(app),
+            }),
+        ),
+
+        (Method::Get, ["v1", "accounts"]) => {
+            match vault::get_accounts(app.clone(), app.state()) {
+                Ok(accounts) => {
+                    let accounts: Vec<ApiAccount> = accounts
+                        .into_iter()
+                        .map(|a| ApiAccount {
+                            id: a.id,
+                            user_id: a.user_id,
+                            username: a.username,
+                            display_name: a.display_name,
+                            alias: a.alias,
+                            tags: a.tags,
+                            folder: a.folder,
+                            is_favorite: a.is_favorite,
+                            last_played_at: a.last_played_at,
+                        })
+                        .collect();
+                    (200, json!(accounts))
+                }
+                Err(e) => command_error(e),
+            }
+        }
+
+        (Method::Get, ["v1", "accounts", account_id, "presence"]) => {
+            let user_id = match vault::get_accounts(app.clone(), app.state()) {
+                Ok(accounts) => accounts
+                    .into_iter()
+                    .find(|a| a.id == *account_id)
+                    .map(|a| a.user_id),
+                Err(e) => return command_error(e),
+            };
+            let Some(user_id) = user_id else {
+                return error(404, "Account not found");
+            };
+
+            match tauri::async_runtime::block_on(roblox::get_user_presence(user_id)) {
+                Ok(presence) => (200, json!(presence)),
+                Err(e) => command_error(e),
+            }
+        }
+
+        (Method::Post, ["v1", "launch"]) => {
+            let body: LaunchBody = match serde_json::from_str(&body) {
+                Ok(body) => body,
+                Err(e) => return error(400, &format!("Invalid body: {}", e)),
+            };
+
+            let result = tauri::async_runtime::block_on(launcher::launch_game(
+                app.clone(),
+                body.account_id,
+                body.place_id,
+                body.job_id,
+                body.link,
+                app.state(),
+                app.state(),
+            ));
+            match result {
+                Ok(instance) => (200, json!(instance)),
+                Err(e) => command_error(e),
+            }
+        }
+
+        (Method::Post, ["v1", "launch", "vip"]) => {
+            let body: LaunchVipBody = match serde_json::from_str(&body) {
+                Ok(body) => body,
+                Err(e) => return error(400, &format!("Invalid body: {}", e)),
+            };
+
+            let result = tauri::async_runtime::block_on(launcher::launch_vip_server(
+                app.clone(),
+                body.account_id,
+                body.place_id,
+                body.link_code,
+                app.state(),
+                app.state(),
+            ));
+            match result {
+                Ok(instance) => (200, json!(instance)),
+                Err(e) => command_error(e),
+            }
+        }
+
+        (Method::Get, ["v1", "instances"]) => {
+            match launcher::get_active_instances(app.clone(), app.state()) {
+                Ok(instances) => (200, json!(instances)),
+                Err(e) => command_error(e),
+            }
+        }
+
+        (Method::Delete, ["v1", "instances", pid]) => {
+            let Ok(pid) = pid.parse::<u32>() else {
+                return error(400, "Invalid PID");
+            };
+            match launcher::kill_instance(app.clone(), pid, app.state()) {
+                Ok(()) => (200, json!({ "killed": pid })),
+                Err(e) => command_error(e),
+            }
+        }
+
+        (Method::Get, ["v1", "games", place_id, "servers"]) => {
+            let Ok(place_id) = place_id.parse::<u64>() else {
+                return error(400, "Invalid place ID");
+            };
+            let cursor = query.split('&').find_map(|pair| {
+                pair.strip_prefix("cursor=")
+                    .map(|c| urlencoding::decode(c).map(|c| c.into_owned()).unwrap_or_default())
+            });
+
+            match tauri::async_runtime::block_on(game_detection::get_game_servers(
+                place_id,
+                cursor,
+                app.state(),
+            )) {
+                Ok((servers, next_page_cursor)) => (
+                    200,
+                    json!({ "data": servers, "nextPageCursor": next_page_cursor }),
+                ),
+                Err(e) => command_error(e),
+            }
+        }
+
+        _ => error(404, "Not found"),
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Connection details for the Settings screen
+#[tauri::command]
+pub fn get_automation_api_info(app: AppHandle) -> Result<AutomationApiInfo, String> {
+    let settings = crate::settings::get_settings(app.clone())?;
+    let running = app
+        .state::<AutomationApiState>()
+        .server
+        .lock()
+        .unwrap()
+        .is_some();
+
+    Ok(AutomationApiInfo {
+        enabled: settings.automation_api_enabled,
+        running,
+        base_url: format!("http://127.0.0.1:{}", settings.automation_api_port),
+        token: current_token(&app)?,
+    })
+}
+
+/// Replace the bearer token (existing scripts stop working immediately)
+#[tauri::command]
+pub fn regenerate_automation_token(app: AppHandle) -> Result<String, String> {
+    generate_token(&app)
+}
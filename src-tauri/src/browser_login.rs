@@ -1,6 +1,9 @@
 //! Browser Login Module
 //! Opens a webview to Roblox login and auto-extracts cookie after successful login
 
+use crate::crypto::CryptoState;
+use rokio_core::profiles::AccountSummary;
+use rokio_core::vault::add_account_with_cookie;
 use tauri::{AppHandle, Manager, WebviewWindowBuilder, WebviewUrl};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -39,31 +42,42 @@ pub async fn browser_login_open(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-/// Check if the login window has the .ROBLOSECURITY cookie
+/// Check if the login window has the .ROBLOSECURITY cookie and, once it
+/// does, add the account (the cookie never reaches the main webview)
 #[tauri::command]
-pub async fn browser_login_check(app: AppHandle) -> Result<Option<String>, String> {
+pub async fn browser_login_check(
+    app: AppHandle,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<Option<AccountSummary>, String> {
     // Try to get the login window
     let login_window = match app.get_webview_window("roblox-login") {
         Some(w) => w,
         None => return Ok(None), // Window closed, no cookie
     };
 
+    // Checked before the window is closed, which loses its cookie
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+
     // Get cookies from the webview
     let cookies = login_window
         .cookies()
         .map_err(|e| format!("Failed to get cookies: {}", e))?;
 
     // Look for .ROBLOSECURITY cookie
-    for cookie in cookies {
-        if cookie.name() == ".ROBLOSECURITY" {
-            let value = cookie.value().to_string();
-            // Close the login window
-            let _ = login_window.close();
-            return Ok(Some(value));
-        }
-    }
+    let Some(value) = cookies
+        .iter()
+        .find(|c| c.name() == ".ROBLOSECURITY")
+        .map(|c| c.value().to_string())
+    else {
+        return Ok(None);
+    };
+
+    // Close the login window
+    let _ = login_window.close();
 
-    Ok(None)
+    let profile = add_account_with_cookie(&app_data_dir, &key, &value).await?;
+    Ok(Some(AccountSummary::from(&profile)))
 }
 
 /// Close the browser login window
@@ -0,0 +1,64 @@
+//! Cookie File Commands
+//! Tauri adapters over `rokio_core::cookie_files` (Netscape cookies.txt / JSON sessions).
+
+use crate::crypto::CryptoState;
+use rokio_core::bulk_import::RowResult;
+use rokio_core::cookie_files::{self as core, CookieFormat};
+use rokio_core::vault::load_accounts;
+use std::path::PathBuf;
+use tauri::Manager;
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Write the accounts' sessions as cookie files, one per account, into `dir`
+/// (default: `Downloads/rokio-cookies`). Returns the written paths.
+#[tauri::command]
+pub fn export_account_cookies(
+    app_handle: tauri::AppHandle,
+    account_ids: Vec<String>,
+    format: CookieFormat,
+    dir: Option<String>,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<Vec<String>, String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+
+    let out_dir = match dir {
+        Some(dir) => PathBuf::from(dir),
+        None => app_handle
+            .path()
+            .download_dir()
+            .map_err(|e| e.to_string())?
+            .join("rokio-cookies"),
+    };
+
+    let mut accounts = load_accounts(&app_data_dir, &key)?;
+    accounts.retain(|a| account_ids.contains(&a.id));
+    if accounts.is_empty() {
+        return Err("Account not found".to_string());
+    }
+
+    let paths = core::export_sessions(&app_data_dir, &accounts, format, &out_dir)?;
+    Ok(paths.iter().map(|p| p.display().to_string()).collect())
+}
+
+/// Add accounts from a Netscape cookies.txt or JSON cookie export
+#[tauri::command]
+pub async fn import_cookie_file(
+    app_handle: tauri::AppHandle,
+    data: String,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<Vec<RowResult>, String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+
+    core::import_cookie_file(&app_data_dir, &key, &data).await
+}
@@ -0,0 +1,74 @@
+//! Game Discovery Commands
+//! Tauri adapters over `rokio_core::discovery` plus the TTL result cache.
+
+use rokio_core::discovery::{self as core, Discovery, DiscoveryCache};
+use rokio_core::game_detection::PopularGame;
+use std::sync::Mutex;
+
+/// Discovery and search results cached this session
+#[derive(Default)]
+pub struct DiscoveryState {
+    pub cache: Mutex<DiscoveryCache>,
+}
+
+/// Cached sorts, fetched again once stale or when `refresh` is set
+async fn load_discovery(state: &DiscoveryState, refresh: bool) -> Discovery {
+    if !refresh {
+        if let Some(cached) = state.cache.lock().unwrap().sorts() {
+            return cached.clone();
+        }
+    }
+
+    let discovery = core::discover().await;
+    state.cache.lock().unwrap().insert_sorts(discovery.clone());
+    discovery
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Get the discovery sorts (bundled list with `offline` set when Roblox can't be reached)
+#[tauri::command]
+pub async fn get_discovery_sorts(
+    refresh: Option<bool>,
+    state: tauri::State<'_, DiscoveryState>,
+) -> Result<Discovery, String> {
+    Ok(load_discovery(&state, refresh.unwrap_or(false)).await)
+}
+
+/// Get popular games with thumbnails (the first discovery sort)
+#[tauri::command]
+pub async fn get_popular_games(
+    state: tauri::State<'_, DiscoveryState>,
+) -> Result<Vec<PopularGame>, String> {
+    let discovery = load_discovery(&state, false).await;
+    Ok(discovery
+        .sorts
+        .into_iter()
+        .next()
+        .map(|s| s.games)
+        .unwrap_or_default())
+}
+
+/// Search games by keyword
+#[tauri::command]
+pub async fn search_games(
+    query: String,
+    state: tauri::State<'_, DiscoveryState>,
+) -> Result<Vec<PopularGame>, String> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    if let Some(cached) = state.cache.lock().unwrap().search(&query) {
+        return Ok(cached.clone());
+    }
+
+    let games = core::search_games(query.trim()).await?;
+    state
+        .cache
+        .lock()
+        .unwrap()
+        .insert_search(&query, games.clone());
+    Ok(games)
+}
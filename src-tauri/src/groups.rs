@@ -1,57 +1,13 @@
-//! Groups API - Join Group with CSRF Token Handling
+//! Groups API - Join and leave groups
 //!
-//! The CSRF Challenge Pattern:
-//! 1. Send POST request
-//! 2. If 403 with x-csrf-token header, extract token
-//! 3. Retry with X-CSRF-TOKEN header
+//! Both are authenticated writes behind Roblox's CSRF challenge, which
+//! `rokio_core::roblox::send_authenticated` answers.
 
 use serde::{Deserialize, Serialize};
 use crate::crypto::CryptoState;
+use rokio_core::roblox::{csrf_post, send_authenticated};
 use tauri::Manager;
 
-/// CSRF Retry Wrapper
-/// Handles the Roblox CSRF token challenge automatically
-pub async fn csrf_post(
-    client: &reqwest::Client,
-    url: &str,
-    cookie: &str,
-    body: &str,
-) -> Result<reqwest::Response, String> {
-    // First attempt without CSRF token
-    let response = client
-        .post(url)
-        .header("Cookie", format!(".ROBLOSECURITY={}", cookie))
-        .header("Content-Type", "application/json")
-        .body(body.to_string())
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
-
-    // If 403, extract CSRF token and retry
-    if response.status() == 403 {
-        if let Some(csrf_token) = response.headers().get("x-csrf-token") {
-            let token_str = csrf_token
-                .to_str()
-                .map_err(|_| "Invalid CSRF token header")?;
-
-            // Retry with CSRF token
-            let retry_response = client
-                .post(url)
-                .header("Cookie", format!(".ROBLOSECURITY={}", cookie))
-                .header("Content-Type", "application/json")
-                .header("X-CSRF-TOKEN", token_str)
-                .body(body.to_string())
-                .send()
-                .await
-                .map_err(|e| format!("Retry request failed: {}", e))?;
-
-            return Ok(retry_response);
-        }
-    }
-
-    Ok(response)
-}
-
 /// Join a Roblox group
 #[tauri::command]
 pub async fn join_group(
@@ -98,35 +54,14 @@ pub async fn leave_group(
         group_id, user_id
     );
 
-    // First attempt
-    let response = client
-        .delete(&url)
-        .header("Cookie", format!(".ROBLOSECURITY={}", cookie))
-        .send()
+    let response = send_authenticated(client.delete(&url), &cookie)
         .await
         .map_err(|e| format!("Request failed: {}", e))?;
 
-    // Handle CSRF if needed
-    let final_response = if response.status() == 403 {
-        if let Some(csrf_token) = response.headers().get("x-csrf-token") {
-            client
-                .delete(&url)
-                .header("Cookie", format!(".ROBLOSECURITY={}", cookie))
-                .header("X-CSRF-TOKEN", csrf_token.to_str().unwrap_or(""))
-                .send()
-                .await
-                .map_err(|e| format!("Retry failed: {}", e))?
-        } else {
-            response
-        }
-    } else {
-        response
-    };
-
-    if final_response.status().is_success() {
+    if response.status().is_success() {
         Ok("Successfully left group".to_string())
     } else {
-        let body = final_response.text().await.unwrap_or_default();
+        let body = response.text().await.unwrap_or_default();
         Err(format!("Failed to leave group: {}", body))
     }
 }
@@ -177,7 +112,7 @@ fn get_account_cookie(
         .ok_or("Vault is locked")?;
 
     let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
-    let accounts = crate::vault::load_accounts(&app_data_dir, &key)?;
+    let accounts = rokio_core::vault::load_accounts(&app_data_dir, &key)?;
 
     accounts
         .iter()
@@ -199,7 +134,7 @@ fn get_account_user_id(
         .ok_or("Vault is locked")?;
 
     let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
-    let accounts = crate::vault::load_accounts(&app_data_dir, &key)?;
+    let accounts = rokio_core::vault::load_accounts(&app_data_dir, &key)?;
 
     accounts
         .iter()
@@ -0,0 +1,50 @@
+//! Launch History Commands
+//! Tauri adapters over `rokio_core::history` (launches, playtime, recent servers).
+
+use rokio_core::history::{self as core, LaunchRecord, Playtime, RecentServer};
+use tauri::Manager;
+
+/// Default number of rows returned by the list commands
+const DEFAULT_LIMIT: u32 = 50;
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Get recent launches, newest first
+#[tauri::command]
+pub fn get_launch_history(
+    app_handle: tauri::AppHandle,
+    limit: Option<u32>,
+) -> Result<Vec<LaunchRecord>, String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::list_launches(&app_data_dir, limit.unwrap_or(DEFAULT_LIMIT))
+}
+
+/// Get playtime per account and game, optionally for one account or place
+#[tauri::command]
+pub fn get_playtime(
+    app_handle: tauri::AppHandle,
+    account_id: Option<String>,
+    place_id: Option<u64>,
+) -> Result<Vec<Playtime>, String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::playtime(&app_data_dir, account_id.as_deref(), place_id)
+}
+
+/// Get servers recently joined by job ID (to rejoin them)
+#[tauri::command]
+pub fn get_recent_servers(
+    app_handle: tauri::AppHandle,
+    limit: Option<u32>,
+) -> Result<Vec<RecentServer>, String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::recent_servers(&app_data_dir, limit.unwrap_or(DEFAULT_LIMIT))
+}
+
+/// Delete all launch history
+#[tauri::command]
+pub fn clear_launch_history(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::clear(&app_data_dir)
+}
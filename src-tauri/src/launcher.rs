@@ -1,28 +1,26 @@
-//! ROKIO Launcher Module
-//! Handles launching Roblox with deep links (cross-platform).
+//! ROKIO Launcher Commands
+//! Tauri adapters over `rokio_core::launcher` plus in-memory instance tracking.
 
 use crate::crypto::CryptoState;
-use crate::environment;
-use crate::settings::get_settings;
-use crate::vault::{load_accounts, save_accounts};
-use serde::{Deserialize, Serialize};
+use rokio_core::history;
+use rokio_core::launcher::{launch_account, take_exit_code, ActiveInstance};
+use rokio_core::process_utils::is_process_running;
+use rokio_core::roblox_link::RobloxLink;
+use rokio_core::server_hop::{self as core, HopResult, HopStrategy, CLIENT_CLOSED_ERROR};
+use rokio_core::vaults;
 use std::collections::HashMap;
-use std::process::Command;
-#[cfg(not(target_os = "windows"))]
-use std::process::Stdio;
+use std::path::Path;
 use std::sync::Mutex;
-use tauri::Manager;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
 
-/// Active Roblox instance
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ActiveInstance {
-    pub pid: u32,
-    pub account_id: String,
-    pub username: String,
-    pub place_id: u64,
-    pub started_at: u64,
-}
+/// Event sent with the `ActiveInstance` of a client that exited on its own
+pub const INSTANCE_CLOSED_EVENT: &str = "instance-closed";
+
+/// Interval between checks of the tracked clients
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+/// Time given to a reaper thread to store the exit code of a client that just exited
+const REAP_GRACE: Duration = Duration::from_millis(100);
 
 /// Global state for tracking active instances
 pub struct LauncherState {
@@ -37,462 +35,45 @@ impl Default for LauncherState {
     }
 }
 
-// ============================================================================
-// DEEP LINK LAUNCH (Cross-platform) - Simple mode
-// ============================================================================
-
-/// Build the Roblox deep link URL
-fn build_deep_link(place_id: u64, job_id: Option<&str>) -> String {
-    if let Some(job) = job_id {
-        format!(
-            "roblox://experiences/start?placeId={}&gameInstanceId={}",
-            place_id, job
-        )
-    } else {
-        format!("roblox://experiences/start?placeId={}", place_id)
-    }
-}
-
-/// Build VIP/Private server deep link URL
-fn build_vip_deep_link(place_id: u64, link_code: &str) -> String {
-    format!("roblox://placeId={}&linkCode={}", place_id, link_code)
-}
-
-/// Find all running RobloxPlayer PIDs (macOS only)
-#[cfg(target_os = "macos")]
-fn find_roblox_pids() -> Vec<u32> {
-    Command::new("pgrep")
-        .arg("RobloxPlayer")
-        .output()
-        .ok()
-        .map(|output| {
-            String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .filter_map(|line| line.trim().parse::<u32>().ok())
-                .collect()
-        })
-        .unwrap_or_default()
-}
-
-/// Find the new RobloxPlayer PID (the one that wasn't in before_pids)
-#[cfg(target_os = "macos")]
-fn find_new_roblox_pid(before_pids: &[u32]) -> Result<u32, String> {
-    // Try a few times in case Roblox is slow to start
-    for _ in 0..5 {
-        let after_pids = find_roblox_pids();
-        for pid in &after_pids {
-            if !before_pids.contains(pid) {
-                return Ok(*pid);
-            }
-        }
-        std::thread::sleep(std::time::Duration::from_millis(500));
-    }
-    
-    // If no new PID found, return the latest one (or error)
-    find_roblox_pids()
-        .into_iter()
-        .max()
-        .ok_or_else(|| "Failed to find Roblox process. Is Roblox installed?".to_string())
-}
-
-/// Launch Roblox to game menu (no specific game)
-#[cfg(target_os = "macos")]
-pub fn launch_roblox_menu() -> Result<u32, String> {
-    // Count existing Roblox processes before launch
-    let before_pids = find_roblox_pids();
-    
-    Command::new("open")
-        .arg("-a")
-        .arg("Roblox")
-        .spawn()
-        .map_err(|e| format!("Failed to open Roblox: {}", e))?;
-
-    // Wait a moment for Roblox to start, then find the new PID
-    std::thread::sleep(std::time::Duration::from_millis(1500));
-    find_new_roblox_pid(&before_pids)
-}
-
-#[cfg(target_os = "windows")]
-pub fn launch_roblox_menu() -> Result<u32, String> {
-    let before_pids = find_roblox_pids();
-
-    Command::new("cmd")
-        .args(["/C", "start", "", "roblox://"])
-        .spawn()
-        .map_err(|e| format!("Failed to open Roblox: {}", e))?;
-
-    std::thread::sleep(std::time::Duration::from_millis(2000));
-    find_new_roblox_pid(&before_pids)
-}
-
-#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-pub fn launch_roblox_menu() -> Result<u32, String> {
-    Err("Roblox launch is not supported on this platform".to_string())
-}
-
-/// Launch Roblox via deep link on macOS
-#[cfg(target_os = "macos")]
-pub fn launch_roblox_deeplink(place_id: u64, job_id: Option<&str>) -> Result<u32, String> {
-    // If place_id is 0, launch to menu instead
-    if place_id == 0 {
-        return launch_roblox_menu();
-    }
-    
-    let deep_link = build_deep_link(place_id, job_id);
-
-    // Count existing Roblox processes before launch
-    let before_pids = find_roblox_pids();
-    
-    Command::new("open")
-        .arg(&deep_link)
-        .spawn()
-        .map_err(|e| format!("Failed to open Roblox: {}", e))?;
-
-    // Wait a moment for Roblox to start, then find the new PID
-    std::thread::sleep(std::time::Duration::from_millis(1500));
-    find_new_roblox_pid(&before_pids)
-}
-
-// ============================================================================
-// WINDOWS SUPPORT - PID detection via wmic/tasklist
-// ============================================================================
-
-/// Find all running RobloxPlayerBeta PIDs (Windows)
-#[cfg(target_os = "windows")]
-fn find_roblox_pids() -> Vec<u32> {
-    // Use wmic to get RobloxPlayerBeta process IDs
-    Command::new("wmic")
-        .args(["process", "where", "name='RobloxPlayerBeta.exe'", "get", "ProcessId"])
-        .output()
-        .ok()
-        .map(|output| {
-            String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .filter_map(|line| line.trim().parse::<u32>().ok())
-                .collect()
-        })
-        .unwrap_or_default()
-}
-
-/// Find the new RobloxPlayerBeta PID (Windows)
-#[cfg(target_os = "windows")]
-fn find_new_roblox_pid(before_pids: &[u32]) -> Result<u32, String> {
-    for _ in 0..10 {
-        let after_pids = find_roblox_pids();
-        for pid in &after_pids {
-            if !before_pids.contains(pid) {
-                return Ok(*pid);
-            }
-        }
-        std::thread::sleep(std::time::Duration::from_millis(500));
-    }
-    
-    find_roblox_pids()
-        .into_iter()
-        .max()
-        .ok_or_else(|| "Failed to find Roblox process. Is Roblox installed?".to_string())
-}
-
-#[cfg(target_os = "windows")]
-pub fn launch_roblox_deeplink(place_id: u64, job_id: Option<&str>) -> Result<u32, String> {
-    if place_id == 0 {
-        return launch_roblox_menu();
-    }
-    
-    let deep_link = build_deep_link(place_id, job_id);
-    let before_pids = find_roblox_pids();
-    
-    Command::new("cmd")
-        .args(["/C", "start", "", &deep_link])
-        .spawn()
-        .map_err(|e| format!("Failed to open Roblox: {}", e))?;
-
-    std::thread::sleep(std::time::Duration::from_millis(2000));
-    find_new_roblox_pid(&before_pids)
-}
-
-// ============================================================================
-// LINUX SUPPORT - Sober (Roblox via Flatpak)
-// ============================================================================
-
-/// Find all running Sober PIDs (Linux)
-#[cfg(target_os = "linux")]
-fn find_sober_pids() -> Vec<u32> {
-    Command::new("pgrep")
-        .args(["-f", "org.vinegarhq.Sober"])
-        .output()
-        .ok()
-        .map(|output| {
-            String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .filter_map(|line| line.trim().parse::<u32>().ok())
-                .collect()
-        })
-        .unwrap_or_default()
-}
-
-/// Find the new Sober PID (Linux)
-#[cfg(target_os = "linux")]
-fn find_new_sober_pid(before_pids: &[u32]) -> Result<u32, String> {
-    for _ in 0..10 {
-        let after_pids = find_sober_pids();
-        for pid in &after_pids {
-            if !before_pids.contains(pid) {
-                return Ok(*pid);
-            }
-        }
-        std::thread::sleep(std::time::Duration::from_millis(500));
-    }
-    
-    find_sober_pids()
-        .into_iter()
-        .max()
-        .ok_or_else(|| "Failed to find Sober process. Is Sober installed via Flatpak?".to_string())
-}
-
-/// Launch Sober to menu (Linux)
-#[cfg(target_os = "linux")]
-pub fn launch_roblox_menu() -> Result<u32, String> {
-    let before_pids = find_sober_pids();
-    
-    Command::new("flatpak")
-        .args(["run", "org.vinegarhq.Sober"])
-        .spawn()
-        .map_err(|e| format!("Failed to launch Sober: {}", e))?;
-
-    std::thread::sleep(std::time::Duration::from_millis(2000));
-    find_new_sober_pid(&before_pids)
-}
-
-/// Launch Sober with deeplink (Linux)
-#[cfg(target_os = "linux")]
-pub fn launch_roblox_deeplink(place_id: u64, job_id: Option<&str>) -> Result<u32, String> {
-    if place_id == 0 {
-        return launch_roblox_menu();
-    }
-    
-    let deep_link = build_deep_link(place_id, job_id);
-    let before_pids = find_sober_pids();
-    
-    Command::new("flatpak")
-        .args(["run", "org.vinegarhq.Sober", &deep_link])
-        .spawn()
-        .map_err(|e| format!("Failed to launch Sober: {}", e))?;
-
-    std::thread::sleep(std::time::Duration::from_millis(2000));
-    find_new_sober_pid(&before_pids)
-}
-
-/// Launch Sober with custom HOME for multi-instance (Linux)
-#[cfg(target_os = "linux")]
-pub fn launch_with_custom_home(
-    home_dir: &std::path::Path,
-    place_id: u64,
-    job_id: Option<&str>,
-) -> Result<u32, String> {
-    let before_pids = find_sober_pids();
-    
-    let mut cmd = Command::new("flatpak");
-    cmd.arg("run")
-        .arg("--env=HOME=".to_owned() + home_dir.to_str().unwrap_or("/tmp"))
-        .arg("org.vinegarhq.Sober");
-    
-    if place_id > 0 {
-        cmd.arg(build_deep_link(place_id, job_id));
+impl LauncherState {
+    /// Start tracking a launched instance
+    pub fn track(&self, instance: &ActiveInstance) {
+        self.instances
+            .lock()
+            .unwrap()
+            .insert(instance.pid, instance.clone());
     }
-    
-    cmd.stdin(Stdio::null())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()
-        .map_err(|e| format!("Failed to launch Sober: {}", e))?;
 
-    std::thread::sleep(std::time::Duration::from_millis(2000));
-    find_new_sober_pid(&before_pids)
-}
-
-/// Fallback for unsupported platforms
-#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-pub fn launch_roblox_deeplink(_place_id: u64, _job_id: Option<&str>) -> Result<u32, String> {
-    Err("Roblox launch is not supported on this platform".to_string())
-}
-
-// ============================================================================
-// MULTI-INSTANCE LAUNCH (macOS only) - Launches RobloxPlayer with custom HOME
-// ============================================================================
-
-/// Find the Roblox.app path on macOS
-#[cfg(target_os = "macos")]
-fn find_roblox_app() -> Result<std::path::PathBuf, String> {
-    use std::path::PathBuf;
-    
-    // Check common installation locations
-    let mut locations = vec![PathBuf::from("/Applications/Roblox.app")];
-    
-    if let Some(home) = dirs::home_dir() {
-        locations.push(home.join("Applications/Roblox.app"));
-    }
-
-    for loc in locations {
-        if loc.exists() {
-            return Ok(loc);
+    /// Also track clients launched elsewhere (the CLI) that are still running,
+    /// from the open rows of the launch history
+    fn adopt_running(&self, app_data_dir: &Path) {
+        let running = match history::running(app_data_dir) {
+            Ok(running) => running,
+            Err(e) => return log::warn!("Failed to read running launches: {}", e),
+        };
+        let mut instances = self.instances.lock().unwrap();
+        for instance in running.into_iter().filter(|i| is_process_running(i.pid)) {
+            instances.entry(instance.pid).or_insert(instance);
         }
     }
 
-    Err("Roblox.app not found. Please install Roblox first.".to_string())
-}
-
-/// Launch Roblox with custom HOME directory for multi-instance support
-/// Note: This launches to main menu. Game join is handled separately.
-#[cfg(target_os = "macos")]
-pub fn launch_with_custom_home(
-    home_dir: &std::path::Path,
-    _place_id: u64,
-    _job_id: Option<&str>,
-) -> Result<u32, String> {
-    let roblox_app = find_roblox_app()?;
-    let player_path = roblox_app
-        .join("Contents")
-        .join("MacOS")
-        .join("RobloxPlayer");
-
-    if !player_path.exists() {
-        return Err(format!(
-            "RobloxPlayer not found at: {}",
-            player_path.display()
-        ));
+    /// Stop tracking an instance of the active vault's accounts
+    fn untrack(&self, app_data_dir: &Path, pid: u32) -> Option<ActiveInstance> {
+        let vault_id = vaults::active(app_data_dir).id;
+        let mut instances = self.instances.lock().unwrap();
+        instances.get(&pid).filter(|i| i.vault_id == vault_id)?;
+        instances.remove(&pid)
     }
-
-    // Launch RobloxPlayer with custom HOME environment (like raptormanager)
-    // Roblox will read cookies from {HOME}/Library/HTTPStorages/
-    // Use Stdio::null() to let the process run independently
-    let child = Command::new(&player_path)
-        .env("HOME", home_dir)
-        .stdin(Stdio::null())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()
-        .map_err(|e| format!("Failed to launch Roblox: {}", e))?;
-
-    Ok(child.id())
-}
-
-/// Launch with custom AppData for multi-instance (Windows)
-#[cfg(target_os = "windows")]
-pub fn launch_with_custom_home(
-    home_dir: &std::path::Path,
-    place_id: u64,
-    job_id: Option<&str>,
-) -> Result<u32, String> {
-    let local_appdata = home_dir.join("LocalAppData");
-    let appdata = home_dir.join("AppData");
-
-    // Build deep link
-    let deep_link = if place_id > 0 {
-        build_deep_link(place_id, job_id)
-    } else {
-        "roblox://".to_string()
-    };
-
-    let before_pids = find_roblox_pids();
-
-    // Launch Roblox with custom environment variables
-    // This makes Roblox store cookies and data in isolated directories
-    Command::new("cmd")
-        .args(["/C", "start", "", &deep_link])
-        .env("LOCALAPPDATA", &local_appdata)
-        .env("APPDATA", &appdata)
-        .spawn()
-        .map_err(|e| format!("Failed to launch Roblox: {}", e))?;
-
-    std::thread::sleep(std::time::Duration::from_millis(2000));
-    find_new_roblox_pid(&before_pids)
-}
-
-#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-pub fn launch_with_custom_home(
-    _home_dir: &std::path::Path,
-    _place_id: u64,
-    _job_id: Option<&str>,
-) -> Result<u32, String> {
-    Err("Multi-instance launch is not supported on this platform".to_string())
-}
-
-// ============================================================================
-// PROCESS UTILITIES
-// ============================================================================
-
-// Re-export specific functions if needed, or just use crate::process_utils directly
-use crate::process_utils::{kill_process, is_process_running};
-
-// ============================================================================
-// TAURI COMMANDS
-// ============================================================================
-
-use crate::profiles::Profile;
-use crate::settings::AppSettings;
-use std::path::PathBuf;
-
-/// Inject cookie into the SYSTEM Roblox cookie paths (real ~/Library/)
-/// so that single-instance launch uses the correct account.
-fn inject_system_cookie(cookie_value: &str) -> Result<(), String> {
-    use crate::binarycookies::{BinaryCookies, Cookie as BinaryCookie, Page};
-    use std::fs::{self, File};
-    use std::io::Write;
-    use std::time::{Duration, SystemTime};
-
-    let home = dirs::home_dir().ok_or("Cannot determine home directory")?;
-
-    let cookie = BinaryCookie {
-        domain: ".roblox.com".into(),
-        name: ".ROBLOSECURITY".into(),
-        path: Some("/".into()),
-        value: cookie_value.to_string(),
-        secure: Some(true),
-        http_only: Some(true),
-        expiration: Some(SystemTime::now() + Duration::from_secs(60 * 60 * 24 * 30)),
-        creation: Some(SystemTime::now()),
-    };
-
-    let page = Page::new(vec![cookie]);
-    let binary_cookies = BinaryCookies::new(vec![page]);
-    let bytes = binary_cookies.build();
-
-    // Write to ~/Library/HTTPStorages/
-    let http_storages = home.join("Library").join("HTTPStorages");
-    fs::create_dir_all(&http_storages).map_err(|e| e.to_string())?;
-    let f1 = http_storages.join("com.roblox.RobloxPlayer.binarycookies");
-    File::create(&f1)
-        .and_then(|mut f| f.write_all(&bytes))
-        .map_err(|e| format!("Failed to write system cookie (HTTPStorages): {}", e))?;
-
-    // Write to ~/Library/Cookies/
-    let cookies_dir = home.join("Library").join("Cookies");
-    fs::create_dir_all(&cookies_dir).map_err(|e| e.to_string())?;
-    let f2 = cookies_dir.join("com.roblox.RobloxPlayer.binarycookies");
-    File::create(&f2)
-        .and_then(|mut f| f.write_all(&bytes))
-        .map_err(|e| format!("Failed to write system cookie (Cookies): {}", e))?;
-
-    Ok(())
-}
-
-/// Shared launch preparation: unlock vault, load accounts, get settings & timestamps
-struct LaunchContext {
-    account: Profile,
-    accounts: Vec<Profile>,
-    key: [u8; 32],
-    app_data_dir: PathBuf,
-    settings: AppSettings,
-    now_ms: u64,
-    now_secs: u64,
 }
 
-fn prepare_launch(
+/// Run the launch pipeline for the GUI and track the instance
+async fn launch_target(
     app_handle: &tauri::AppHandle,
     account_id: &str,
+    target: Option<RobloxLink>,
     crypto_state: &tauri::State<'_, CryptoState>,
-) -> Result<LaunchContext, String> {
+    launcher_state: &tauri::State<'_, LauncherState>,
+) -> Result<ActiveInstance, String> {
     let key = crypto_state
         .key
         .lock()
@@ -501,138 +82,122 @@ fn prepare_launch(
 
     let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
 
-    let accounts = load_accounts(&app_data_dir, &key)?;
-    let account = accounts
-        .iter()
-        .find(|a| a.id == account_id)
-        .ok_or("Account not found")?
-        .clone();
-
-    let now_ms = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_millis() as u64;
-    let now_secs = now_ms / 1000;
-
-    let settings = get_settings(app_handle.clone()).unwrap_or_default();
-
-    Ok(LaunchContext {
-        account,
-        accounts,
-        key,
-        app_data_dir,
-        settings,
-        now_ms,
-        now_secs,
-    })
-}
-
-/// Shared launch finalization: update timestamp, save accounts, track instance
-fn finalize_launch(
-    ctx: LaunchContext,
-    pid: u32,
-    place_id: u64,
-    account_id: &str,
-    launcher_state: &tauri::State<'_, LauncherState>,
-) -> Result<ActiveInstance, String> {
-    let mut accounts = ctx.accounts;
-
-    // Update last_played_at timestamp
-    if let Some(acc) = accounts.iter_mut().find(|a| a.id == account_id) {
-        acc.last_played_at = ctx.now_ms;
-    }
-    let _ = save_accounts(&ctx.app_data_dir, &ctx.key, &accounts);
-
-    // Track the instance
-    let instance = ActiveInstance {
-        pid,
-        account_id: ctx.account.id.clone(),
-        username: ctx.account.username.clone(),
-        place_id,
-        started_at: ctx.now_secs,
-    };
-
-    launcher_state
-        .instances
-        .lock()
-        .unwrap()
-        .insert(pid, instance.clone());
+    let instance = launch_account(&app_data_dir, &key, account_id, target).await?;
+    launcher_state.track(&instance);
 
     Ok(instance)
 }
 
-/// Set up multi-instance environment (keychain, cookies, custom HOME)
-fn setup_multi_instance_env(
-    app_handle: &tauri::AppHandle,
-    account_id: &str,
-    cookie: &str,
-) -> Result<std::path::PathBuf, String> {
-    environment::create_environment(app_handle.clone(), account_id.to_string())?;
-    environment::create_keychain(app_handle.clone(), account_id.to_string())?;
-    environment::unlock_keychain(app_handle.clone(), account_id.to_string())?;
-    environment::write_cookies(
-        app_handle.clone(),
-        account_id.to_string(),
-        cookie.to_string(),
-    )?;
-    Ok(environment::get_launch_home_dir(app_handle, account_id))
-}
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
 
-/// Launch a game with a specific account
+/// Launch a game with a specific account.
+/// `link` accepts any format understood by `RobloxLink` (share links are resolved
+/// with the account's session) and takes precedence over `place_id`/`job_id`;
+/// a place ID of 0 (or nothing) opens the menu.
 #[tauri::command]
 pub async fn launch_game(
     app_handle: tauri::AppHandle,
     account_id: String,
-    place_id: u64,
+    place_id: Option<u64>,
     job_id: Option<String>,
+    link: Option<String>,
     crypto_state: tauri::State<'_, CryptoState>,
     launcher_state: tauri::State<'_, LauncherState>,
 ) -> Result<ActiveInstance, String> {
-    let ctx = prepare_launch(&app_handle, &account_id, &crypto_state)?;
-
-    let pid = if ctx.settings.multi_instance {
-        let home_dir =
-            setup_multi_instance_env(&app_handle, &account_id, &ctx.account.cookie)?;
-        launch_with_custom_home(&home_dir, place_id, job_id.as_deref())?
-    } else {
-        // Inject cookie into system Roblox paths so the correct account is used
-        inject_system_cookie(&ctx.account.cookie)?;
-        launch_roblox_deeplink(place_id, job_id.as_deref())?
+    let target = match link {
+        Some(raw) => Some(RobloxLink::parse(&raw)?),
+        None => place_id
+            .filter(|id| *id > 0)
+            .map(|id| RobloxLink::from_parts(id, job_id)),
     };
 
-    finalize_launch(ctx, pid, place_id, &account_id, &launcher_state)
+    launch_target(&app_handle, &account_id, target, &crypto_state, &launcher_state).await
 }
 
 /// Kill a running Roblox instance
 #[tauri::command]
 pub fn kill_instance(
+    app_handle: tauri::AppHandle,
     pid: u32,
     launcher_state: tauri::State<'_, LauncherState>,
 ) -> Result<(), String> {
-    kill_process(pid)?;
-    launcher_state.instances.lock().unwrap().remove(&pid);
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    // Only clients ROKIO launched for this vault and still tracks. Untracked
+    // first so the exit watcher doesn't record the kill as an exit of its own.
+    let instance = launcher_state
+        .untrack(&app_data_dir, pid)
+        .ok_or_else(|| format!("No running instance with PID {}", pid))?;
+    if let Err(e) = instance.backend(&app_data_dir).and_then(|b| b.terminate(pid)) {
+        launcher_state.track(&instance);
+        return Err(e);
+    }
+
+    if let Err(e) = history::record_killed(&app_data_dir, &instance) {
+        log::warn!("Failed to record kill: {}", e);
+    }
     Ok(())
 }
 
-/// Get all active instances
+/// Watch the tracked clients in the background: record each exit in the
+/// launch history as it happens and tell the frontend about it
+pub fn watch_instances(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(WATCH_INTERVAL);
+
+        let launcher_state = app.state::<LauncherState>();
+        let exited: Vec<u32> = launcher_state
+            .instances
+            .lock()
+            .unwrap()
+            .keys()
+            .copied()
+            .filter(|pid| !is_process_running(*pid))
+            .collect();
+        if exited.is_empty() {
+            continue;
+        }
+        std::thread::sleep(REAP_GRACE);
+
+        let app_data_dir = app.path().app_data_dir().ok();
+        for pid in exited {
+            // Gone already when it was killed or hopped in the meantime
+            let Some(instance) = launcher_state.instances.lock().unwrap().remove(&pid) else {
+                continue;
+            };
+            if let Some(dir) = &app_data_dir {
+                if let Err(e) = history::record_exited(dir, &instance, take_exit_code(pid)) {
+                    log::warn!("Failed to record exit: {}", e);
+                }
+            }
+            if let Err(e) = app.emit(INSTANCE_CLOSED_EVENT, &instance) {
+                log::warn!("Failed to emit instance exit: {}", e);
+            }
+        }
+    });
+}
+
+/// Get the active instances of the active vault's accounts
 #[tauri::command]
 pub fn get_active_instances(
+    app_handle: tauri::AppHandle,
     launcher_state: tauri::State<'_, LauncherState>,
-) -> Vec<ActiveInstance> {
-    let mut instances = launcher_state.instances.lock().unwrap();
-
-    // Clean up dead instances
-    let dead_pids: Vec<u32> = instances
-        .iter()
-        .filter(|(pid, _)| !is_process_running(**pid))
-        .map(|(pid, _)| *pid)
-        .collect();
-
-    for pid in dead_pids {
-        instances.remove(&pid);
-    }
+) -> Result<Vec<ActiveInstance>, String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    let vault_id = vaults::active(&app_data_dir).id;
+    launcher_state.adopt_running(&app_data_dir);
 
-    instances.values().cloned().collect()
+    Ok(launcher_state
+        .instances
+        .lock()
+        .unwrap()
+        .values()
+        .filter(|i| i.vault_id == vault_id)
+        .cloned()
+        .collect())
 }
 
 /// Bypass the singleton mutex (placeholder for multi-instance)
@@ -651,54 +216,59 @@ pub async fn launch_vip_server(
     crypto_state: tauri::State<'_, CryptoState>,
     launcher_state: tauri::State<'_, LauncherState>,
 ) -> Result<ActiveInstance, String> {
-    let ctx = prepare_launch(&app_handle, &account_id, &crypto_state)?;
-
-    let vip_link = build_vip_deep_link(place_id, &link_code);
+    let target = RobloxLink::PrivateServer {
+        place_id,
+        link_code,
+    };
 
-    // Set up multi-instance environment if enabled, otherwise inject system cookie
-    if ctx.settings.multi_instance {
-        let _ = setup_multi_instance_env(&app_handle, &account_id, &ctx.account.cookie)?;
-    } else {
-        inject_system_cookie(&ctx.account.cookie)?;
-    }
+    launch_target(
+        &app_handle,
+        &account_id,
+        Some(target),
+        &crypto_state,
+        &launcher_state,
+    )
+    .await
+}
 
-    // Launch via platform-specific deeplink
-    #[cfg(target_os = "macos")]
-    let pid = {
-        let before_pids = find_roblox_pids();
-        Command::new("open")
-            .arg(&vip_link)
-            .spawn()
-            .map_err(|e| format!("Failed to open Roblox: {}", e))?;
-        std::thread::sleep(std::time::Duration::from_millis(1500));
-        find_new_roblox_pid(&before_pids)?
-    };
+/// Move a running instance to another server of the same place
+/// (closes the client and relaunches the account into the chosen server)
+#[tauri::command]
+pub async fn server_hop(
+    app_handle: tauri::AppHandle,
+    pid: u32,
+    strategy: HopStrategy,
+    crypto_state: tauri::State<'_, CryptoState>,
+    launcher_state: tauri::State<'_, LauncherState>,
+) -> Result<HopResult, String> {
+    let key = crypto_state
+        .key
+        .lock()
+        .unwrap()
+        .ok_or("Vault is locked")?;
 
-    #[cfg(target_os = "windows")]
-    let pid = {
-        let before_pids = find_roblox_pids();
-        Command::new("cmd")
-            .args(["/C", "start", "", &vip_link])
-            .spawn()
-            .map_err(|e| format!("Failed to open Roblox: {}", e))?;
-        std::thread::sleep(std::time::Duration::from_millis(2000));
-        find_new_roblox_pid(&before_pids)?
-    };
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
 
-    #[cfg(target_os = "linux")]
-    let pid = {
-        let before_pids = find_sober_pids();
-        Command::new("flatpak")
-            .args(["run", "org.vinegarhq.Sober", &vip_link])
-            .spawn()
-            .map_err(|e| format!("Failed to launch Sober: {}", e))?;
-        std::thread::sleep(std::time::Duration::from_millis(2000));
-        find_new_sober_pid(&before_pids)?
+    // Untracked while hopping so the exit watcher leaves the old client alone
+    let instance = launcher_state
+        .untrack(&app_data_dir, pid)
+        .ok_or("Instance not found")?;
+    let hop = match core::server_hop(&app_data_dir, &key, &instance, strategy).await {
+        Ok(hop) => hop,
+        // The old client is gone, so it stays untracked
+        Err(e) if e.starts_with(CLIENT_CLOSED_ERROR) => {
+            if let Err(e) = app_handle.emit(INSTANCE_CLOSED_EVENT, &instance) {
+                log::warn!("Failed to emit instance exit: {}", e);
+            }
+            return Err(e);
+        }
+        Err(e) => {
+            launcher_state.track(&instance);
+            return Err(e);
+        }
     };
 
-    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
-    let pid: u32 = return Err("VIP server launch not supported on this platform".to_string());
+    launcher_state.track(&hop.instance);
 
-    finalize_launch(ctx, pid, place_id, &account_id, &launcher_state)
+    Ok(hop)
 }
-
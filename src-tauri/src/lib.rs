@@ -1,25 +1,44 @@
 //! ROKIO - Roblox Alt Manager
 //! Main library entry point for Tauri.
+//! Commands here are thin adapters over the `rokio_core` crate.
 
 // Module declarations
-mod binarycookies;
+mod automation_api;
 mod browser_login;
+mod cookie_files;
 mod crypto;
+mod discovery;
 mod environment;
 mod game_detection;
 mod groups;
+mod history;
 mod launcher;
-mod process_utils;
-mod profiles;
+mod organize;
+mod presets;
 mod quick_login;
+mod regions;
 mod roblox;
+mod roblox_link;
+mod security;
+mod server_details;
+mod server_finder;
+mod session;
 mod settings;
+mod share_links;
+mod trash;
 mod utils;
 mod vault;
+mod vaults;
 
+use automation_api::AutomationApiState;
 use crypto::CryptoState;
+use discovery::DiscoveryState;
 use launcher::LauncherState;
+use regions::RegionState;
+use server_finder::ServerFinderState;
+use rokio_core::process_utils::is_process_running;
 use serde::Serialize;
+use tauri::Manager;
 
 /// App initialization response
 #[derive(Debug, Clone, Serialize)]
@@ -46,7 +65,7 @@ fn init_app_check() -> AppInfo {
     AppInfo {
         version: env!("CARGO_PKG_VERSION").to_string(),
         platform: platform.to_string(),
-        hwid: crypto::get_machine_id(),
+        hwid: rokio_core::crypto::get_machine_id(),
     }
 }
 
@@ -58,6 +77,36 @@ pub fn run() {
         // Manage state
         .manage(CryptoState::default())
         .manage(LauncherState::default())
+        .manage(AutomationApiState::default())
+        .manage(ServerFinderState::default())
+        .manage(RegionState::default())
+        .manage(DiscoveryState::default())
+        .setup(|app| {
+            // Start the automation API if the user enabled it
+            let handle = app.handle().clone();
+            let settings = settings::get_settings(handle.clone()).unwrap_or_default();
+            automation_api::sync_with_settings(&handle, &settings);
+
+            // Keep vault cookies in step with Roblox session rotation
+            session::install_rotation_handler(&handle);
+
+            // Record client exits as they happen
+            launcher::watch_instances(&handle);
+
+            // Launches from a previous run whose client is gone can't be timed anymore
+            if let Ok(app_data_dir) = handle.path().app_data_dir() {
+                if let Err(e) = rokio_core::history::close_orphaned(&app_data_dir, is_process_running) {
+                    log::warn!("Failed to close orphaned launches: {}", e);
+                }
+
+                // Enforce the recycle bin retention even if the vault is never unlocked
+                let retention = settings.trash_retention_days;
+                if let Err(e) = rokio_core::trash::purge_expired(&app_data_dir, retention) {
+                    log::warn!("Failed to purge the recycle bin: {}", e);
+                }
+            }
+            Ok(())
+        })
         // Register all commands
         .invoke_handler(tauri::generate_handler![
             // App commands
@@ -68,6 +117,11 @@ pub fn run() {
             crypto::create_vault,
             crypto::unlock_vault,
             crypto::lock_vault,
+            vaults::get_vaults,
+            vaults::create_named_vault,
+            vaults::rename_vault,
+            vaults::delete_vault,
+            vaults::switch_vault,
             // Account commands
             vault::get_accounts,
             vault::add_account,
@@ -75,7 +129,28 @@ pub fn run() {
             vault::delete_account,
             vault::export_accounts,
             vault::import_accounts,
+            vault::bulk_import_accounts,
+            cookie_files::export_account_cookies,
+            cookie_files::import_cookie_file,
             vault::clear_accounts,
+            vault::reveal_cookie,
+            vault::get_audit_log,
+            session::sign_out_and_delete_account,
+            session::get_account_sessions,
+            session::sign_out_other_sessions,
+            // Security audit commands
+            security::get_security_report,
+            security::export_security_report,
+            // Account organization commands
+            organize::query_accounts,
+            organize::get_account_labels,
+            organize::bulk_update_accounts,
+            organize::reorder_accounts,
+            organize::rename_folder,
+            // Recycle bin commands
+            trash::get_trash,
+            trash::restore_accounts,
+            trash::purge_trash,
             // Roblox API commands
             roblox::validate_cookie,
             roblox::refresh_account_data,
@@ -88,7 +163,21 @@ pub fn run() {
             launcher::launch_vip_server,
             launcher::kill_instance,
             launcher::get_active_instances,
+            // Launch Preset commands
+            presets::get_presets,
+            presets::save_preset,
+            presets::delete_preset,
+            presets::run_preset,
+            // Launch History commands
+            history::get_launch_history,
+            history::get_playtime,
+            history::get_recent_servers,
+            history::clear_launch_history,
             launcher::bypass_mutex,
+            launcher::server_hop,
+            // Link commands
+            roblox_link::parse_roblox_link,
+            share_links::resolve_share_link,
             // Quick Login commands
             quick_login::quick_login_create,
             quick_login::quick_login_poll,
@@ -100,9 +189,24 @@ pub fn run() {
             // Game Detection commands
             game_detection::get_universe_id,
             game_detection::get_game_info,
+            game_detection::batch_get_game_info,
             game_detection::batch_get_game_icons,
+            game_detection::get_game_icon_data,
+            game_detection::clear_game_cache,
             game_detection::get_game_servers,
-            game_detection::get_popular_games,
+            // Game Discovery commands
+            discovery::get_popular_games,
+            discovery::get_discovery_sorts,
+            discovery::search_games,
+            // Server Finder commands
+            server_finder::find_servers,
+            server_finder::cancel_server_search,
+            // Server Region commands
+            regions::resolve_server_regions,
+            regions::import_region_table,
+            regions::reset_region_table,
+            // Server Details commands
+            server_details::get_server_details,
             // Group commands
             groups::join_group,
             groups::leave_group,
@@ -115,6 +219,9 @@ pub fn run() {
             settings::get_settings,
             settings::save_settings,
             settings::reset_settings,
+            // Automation API commands
+            automation_api::get_automation_api_info,
+            automation_api::regenerate_automation_token,
             // Environment commands (for multi-instance launching)
             environment::create_environment,
             environment::write_cookies,
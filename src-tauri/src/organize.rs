@@ -0,0 +1,78 @@
+//! Account Organization Commands
+//! Tauri adapters over `rokio_core::organize` (tags, folders, custom order, bulk edits).
+
+use crate::crypto::CryptoState;
+use rokio_core::organize::{self as core, AccountLabels, AccountQuery, BulkAction};
+use rokio_core::profiles::Profile;
+use rokio_core::vault::load_accounts;
+use tauri::Manager;
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Get accounts matching a tag and/or folder, in custom order
+#[tauri::command]
+pub fn query_accounts(
+    app_handle: tauri::AppHandle,
+    query: AccountQuery,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<Vec<Profile>, String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    Ok(core::query_accounts(load_accounts(&app_data_dir, &key)?, &query))
+}
+
+/// Get every tag and folder in use with account counts
+#[tauri::command]
+pub fn get_account_labels(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<AccountLabels, String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    Ok(core::account_labels(&load_accounts(&app_data_dir, &key)?))
+}
+
+/// Apply one change to a selection of accounts, returning how many changed
+#[tauri::command]
+pub fn bulk_update_accounts(
+    app_handle: tauri::AppHandle,
+    account_ids: Vec<String>,
+    action: BulkAction,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<usize, String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    core::apply_bulk(&app_data_dir, &key, &account_ids, &action)
+}
+
+/// Persist the custom account order
+#[tauri::command]
+pub fn reorder_accounts(
+    app_handle: tauri::AppHandle,
+    account_ids: Vec<String>,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<(), String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    core::reorder_accounts(&app_data_dir, &key, &account_ids)
+}
+
+/// Rename or move a folder (nested folders move with it)
+#[tauri::command]
+pub fn rename_folder(
+    app_handle: tauri::AppHandle,
+    from: String,
+    to: String,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<usize, String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    core::rename_folder(&app_data_dir, &key, &from, &to)
+}
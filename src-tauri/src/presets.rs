@@ -0,0 +1,55 @@
+//! Launch Preset Commands
+//! Tauri adapters over `rokio_core::presets`; launched instances are tracked
+//! like any other launch.
+
+use crate::crypto::CryptoState;
+use crate::launcher::LauncherState;
+use rokio_core::launcher::ActiveInstance;
+use rokio_core::presets::{self as core, LaunchPreset, PresetRunResult};
+use tauri::Manager;
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Get all saved launch presets
+#[tauri::command]
+pub fn get_presets(app_handle: tauri::AppHandle) -> Result<Vec<LaunchPreset>, String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::load_presets(&app_data_dir)
+}
+
+/// Create a preset (empty `id`) or replace an existing one
+#[tauri::command]
+pub fn save_preset(app_handle: tauri::AppHandle, preset: LaunchPreset) -> Result<LaunchPreset, String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::upsert_preset(&app_data_dir, preset)
+}
+
+/// Delete a preset
+#[tauri::command]
+pub fn delete_preset(app_handle: tauri::AppHandle, preset_id: String) -> Result<bool, String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::delete_preset(&app_data_dir, &preset_id)
+}
+
+/// Launch every account of a preset
+#[tauri::command]
+pub async fn run_preset(
+    app_handle: tauri::AppHandle,
+    preset_id: String,
+    crypto_state: tauri::State<'_, CryptoState>,
+    launcher_state: tauri::State<'_, LauncherState>,
+) -> Result<PresetRunResult, String> {
+    let key = crypto_state
+        .key
+        .lock()
+        .unwrap()
+        .ok_or("Vault is locked")?;
+
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    let preset = core::find_preset(&app_data_dir, &preset_id)?;
+
+    let on_launch = |instance: &ActiveInstance| launcher_state.track(instance);
+    core::run_preset(&app_data_dir, &key, &preset, &on_launch).await
+}
@@ -3,9 +3,14 @@
 //! Flow:
 //! 1. Generate: POST /login/create → Returns code + QR URL
 //! 2. Poll: POST /login/status every 2s until CONFIRMED
-//! 3. Extract: Get .ROBLOSECURITY cookie
+//! 3. Extract: Get .ROBLOSECURITY cookie and add the account to the vault
 
+use crate::crypto::CryptoState;
+use rokio_core::profiles::AccountSummary;
+use rokio_core::session::set_cookie_value;
+use rokio_core::vault::add_account_with_cookie;
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
 
 /// Response from /login/create
 #[derive(Debug, Deserialize)]
@@ -93,9 +98,13 @@ pub async fn quick_login_poll(code: String) -> Result<String, String> {
     Ok(data.status)
 }
 
-/// Complete the Quick Login - extract cookie
+/// Complete the Quick Login - extract the cookie and add the account
 #[tauri::command]
-pub async fn quick_login_complete(code: String) -> Result<String, String> {
+pub async fn quick_login_complete(
+    app: AppHandle,
+    code: String,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<AccountSummary, String> {
     let client = reqwest::Client::new();
 
     // Redeem the code for authentication
@@ -112,50 +121,13 @@ pub async fn quick_login_complete(code: String) -> Result<String, String> {
     }
 
     // Extract .ROBLOSECURITY from Set-Cookie header
-    let cookie = response
-        .headers()
-        .get_all("set-cookie")
-        .iter()
-        .find_map(|v| {
-            let s = v.to_str().ok()?;
-            if s.contains(".ROBLOSECURITY=") {
-                let start = s.find(".ROBLOSECURITY=")? + 15;
-                let end = s[start..].find(';').map(|i| start + i).unwrap_or(s.len());
-                Some(s[start..end].to_string())
-            } else {
-                None
-            }
-        })
+    let cookie = set_cookie_value(response.headers())
+        .filter(|c| !c.is_empty())
         .ok_or_else(|| "No .ROBLOSECURITY cookie in response".to_string())?;
 
-    // Validate the cookie by fetching user info
-    let user_response = client
-        .get("https://users.roblox.com/v1/users/authenticated")
-        .header("Cookie", format!(".ROBLOSECURITY={}", cookie))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to validate cookie: {}", e))?;
-
-    if !user_response.status().is_success() {
-        return Err("Cookie validation failed".to_string());
-    }
-
-    #[derive(Deserialize)]
-    struct AuthUser {
-        id: u64,
-        name: String,
-        #[serde(rename = "displayName")]
-        display_name: String,
-    }
-
-    let user: AuthUser = user_response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse user: {}", e))?;
-
-    // Return data for frontend to use with add_account
-    Ok(format!(
-        "{}|{}|{}|{}",
-        user.id, user.name, user.display_name, cookie
-    ))
+    // Validate and store it here so the cookie never reaches the webview
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let profile = add_account_with_cookie(&app_data_dir, &key, &cookie).await?;
+    Ok(AccountSummary::from(&profile))
 }
@@ -0,0 +1,77 @@
+//! Server Region Commands
+//! Tauri adapters over `rokio_core::regions` plus the per-job region cache.
+
+use crate::crypto::CryptoState;
+use rokio_core::regions::{self as core, RegionCache, RegionTable, ServerRegion};
+use rokio_core::vault::load_accounts;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::Manager;
+
+/// Regions resolved recently (see `RegionCache`)
+#[derive(Default)]
+pub struct RegionState {
+    pub cache: Mutex<RegionCache>,
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Resolve the datacenter region of servers using an account's session.
+/// Cached job IDs are answered without a request; servers that can't be
+/// joined (full, shut down) are missing from the result.
+#[tauri::command]
+pub async fn resolve_server_regions(
+    app_handle: tauri::AppHandle,
+    account_id: String,
+    place_id: u64,
+    job_ids: Vec<String>,
+    crypto_state: tauri::State<'_, CryptoState>,
+    region_state: tauri::State<'_, RegionState>,
+) -> Result<HashMap<String, ServerRegion>, String> {
+    let key = crypto_state
+        .key
+        .lock()
+        .unwrap()
+        .ok_or("Vault is locked")?;
+
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    let missing = region_state.cache.lock().unwrap().missing(&job_ids);
+    if !missing.is_empty() {
+        let account = load_accounts(&app_data_dir, &key)?
+            .into_iter()
+            .find(|a| a.id == account_id)
+            .ok_or("Account not found")?;
+        let table = RegionTable::load(&app_data_dir);
+
+        let resolved = core::resolve_regions(&table, &account.cookie, place_id, missing).await;
+
+        let mut cache = region_state.cache.lock().unwrap();
+        for (job_id, region) in resolved {
+            cache.insert(job_id, region);
+        }
+    }
+
+    let cache = region_state.cache.lock().unwrap();
+    Ok(job_ids
+        .into_iter()
+        .filter_map(|id| cache.get(&id).cloned().map(|region| (id, region)))
+        .collect())
+}
+
+/// Import an IP-range table (`{"ranges": [{"cidr", "code", "name"}]}`),
+/// replacing any earlier one. Returns the number of ranges.
+#[tauri::command]
+pub fn import_region_table(app_handle: tauri::AppHandle, data: String) -> Result<usize, String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::install_table(&app_data_dir, &data)
+}
+
+/// Remove the imported IP-range table
+#[tauri::command]
+pub fn reset_region_table(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::reset_table(&app_data_dir)
+}
@@ -0,0 +1,10 @@
+//! Link Commands
+//! Tauri adapter over `rokio_core::roblox_link`.
+
+use rokio_core::roblox_link::RobloxLink;
+
+/// Parse a pasted link so the UI can show what it points at
+#[tauri::command]
+pub fn parse_roblox_link(url: String) -> Result<RobloxLink, String> {
+    RobloxLink::parse(&url)
+}
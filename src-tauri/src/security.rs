@@ -0,0 +1,34 @@
+//! Security Audit Commands
+//! Tauri adapters over `rokio_core::security` (email, 2SV, phone, PIN per account).
+
+use crate::crypto::CryptoState;
+use rokio_core::security::{self as core, SecurityReport};
+use rokio_core::vault::load_accounts;
+use tauri::Manager;
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Check the security settings of every account (or only `account_ids`)
+#[tauri::command]
+pub async fn get_security_report(
+    app_handle: tauri::AppHandle,
+    account_ids: Option<Vec<String>>,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<Vec<SecurityReport>, String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    let mut accounts = load_accounts(&app_data_dir, &key)?;
+    if let Some(ids) = account_ids {
+        accounts.retain(|a| ids.contains(&a.id));
+    }
+    Ok(core::audit_accounts(accounts).await)
+}
+
+/// Export reports as CSV
+#[tauri::command]
+pub fn export_security_report(reports: Vec<SecurityReport>) -> String {
+    core::reports_to_csv(&reports)
+}
@@ -0,0 +1,41 @@
+//! Server Details Commands
+//! Tauri adapter over `rokio_core::server_details` (player headshots, own accounts).
+
+use crate::crypto::CryptoState;
+use crate::regions::RegionState;
+use rokio_core::server_details::{self as core, ServerDetails};
+use rokio_core::vault::load_accounts;
+use tauri::Manager;
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Get a server's player headshots and which vault accounts are in it.
+/// With the vault locked no accounts are checked.
+#[tauri::command]
+pub async fn get_server_details(
+    app_handle: tauri::AppHandle,
+    place_id: u64,
+    job_id: String,
+    crypto_state: tauri::State<'_, CryptoState>,
+    region_state: tauri::State<'_, RegionState>,
+) -> Result<ServerDetails, String> {
+    let key = *crypto_state.key.lock().unwrap();
+
+    let accounts = match key {
+        Some(key) => {
+            let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+            load_accounts(&app_data_dir, &key)?
+        }
+        None => Vec::new(),
+    };
+
+    let mut details = core::get_server_details(place_id, &job_id, &accounts).await?;
+    region_state
+        .cache
+        .lock()
+        .unwrap()
+        .annotate(std::slice::from_mut(&mut details.server));
+    Ok(details)
+}
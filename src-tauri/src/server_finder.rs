@@ -0,0 +1,89 @@
+//! Server Finder Commands
+//! Runs `rokio_core::server_finder` scans and streams matches to the UI as events.
+
+use crate::regions::RegionState;
+use rokio_core::game_detection::ServerInfo;
+use rokio_core::server_finder::{self as core, ServerQuery, ServerSearchResult};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+
+/// Event carrying each batch of matching servers
+pub const SERVER_BATCH_EVENT: &str = "server-search://batch";
+
+/// Cancellation flags of the searches that are still running
+#[derive(Default)]
+pub struct ServerFinderState {
+    searches: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+/// Payload of `SERVER_BATCH_EVENT`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerBatch<'a> {
+    search_id: &'a str,
+    servers: &'a [ServerInfo],
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Scan every public server of a place. Matches are emitted as
+/// `server-search://batch` events while scanning; the sorted list is returned at the end.
+#[tauri::command]
+pub async fn find_servers(
+    app: AppHandle,
+    search_id: String,
+    place_id: u64,
+    query: ServerQuery,
+    state: tauri::State<'_, ServerFinderState>,
+    region_state: tauri::State<'_, RegionState>,
+) -> Result<ServerSearchResult, String> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    state
+        .searches
+        .lock()
+        .unwrap()
+        .insert(search_id.clone(), cancel.clone());
+
+    let on_batch = |servers: &[ServerInfo]| {
+        let mut servers = servers.to_vec();
+        region_state.cache.lock().unwrap().annotate(&mut servers);
+        let batch = ServerBatch {
+            search_id: &search_id,
+            servers: &servers,
+        };
+        if let Err(e) = app.emit(SERVER_BATCH_EVENT, batch) {
+            log::warn!("Failed to emit server batch: {}", e);
+        }
+    };
+
+    let result = core::find_servers(place_id, &query, &cancel, &on_batch).await;
+
+    state.searches.lock().unwrap().remove(&search_id);
+    let mut result = result?;
+    region_state
+        .cache
+        .lock()
+        .unwrap()
+        .annotate(&mut result.servers);
+    Ok(result)
+}
+
+/// Stop a running search (it resolves with the servers found so far)
+#[tauri::command]
+pub fn cancel_server_search(
+    search_id: String,
+    state: tauri::State<'_, ServerFinderState>,
+) -> bool {
+    match state.searches.lock().unwrap().get(&search_id) {
+        Some(cancel) => {
+            cancel.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
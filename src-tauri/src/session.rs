@@ -0,0 +1,74 @@
+//! Session Commands
+//! Tauri adapters over `rokio_core::session` (rotated `.ROBLOSECURITY` cookies,
+//! active sessions, sign-out).
+
+use crate::crypto::CryptoState;
+use rokio_core::session::{
+    self as core, apply_rotation, set_rotation_handler, sign_out_and_remove, ActiveSession,
+};
+use tauri::{AppHandle, Manager};
+
+/// Store cookies Roblox rotates on any authenticated response.
+/// Rotations seen while the vault is locked cannot be saved and are only logged.
+pub fn install_rotation_handler(app: &AppHandle) {
+    let app = app.clone();
+    set_rotation_handler(move |old_cookie, new_cookie| {
+        let Some(key) = *app.state::<CryptoState>().key.lock().unwrap() else {
+            log::warn!("Roblox rotated a session cookie while the vault is locked");
+            return;
+        };
+        let app_data_dir = match app.path().app_data_dir() {
+            Ok(dir) => dir,
+            Err(e) => return log::warn!("Failed to store rotated cookie: {}", e),
+        };
+
+        match apply_rotation(&app_data_dir, &key, old_cookie, new_cookie) {
+            Ok(Some(account_id)) => log::info!("Stored rotated cookie for {}", account_id),
+            Ok(None) => {}
+            Err(e) => log::warn!("Failed to store rotated cookie: {}", e),
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Sign an account out on Roblox, then delete it for good with its environment
+#[tauri::command]
+pub async fn sign_out_and_delete_account(
+    app_handle: tauri::AppHandle,
+    id: String,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<(), String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    sign_out_and_remove(&app_data_dir, &key, &id).await
+}
+
+/// List where an account is logged in
+#[tauri::command]
+pub async fn get_account_sessions(
+    app_handle: tauri::AppHandle,
+    account_id: String,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<Vec<ActiveSession>, String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    core::list_account_sessions(&app_data_dir, &key, &account_id).await
+}
+
+/// Sign an account out everywhere except ROKIO (its cookie is refreshed)
+#[tauri::command]
+pub async fn sign_out_other_sessions(
+    app_handle: tauri::AppHandle,
+    account_id: String,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<(), String> {
+    let key = state.key.lock().unwrap().ok_or("Vault is locked")?;
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    core::sign_out_other_sessions(&app_data_dir, &key, &account_id).await
+}
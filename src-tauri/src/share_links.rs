@@ -0,0 +1,41 @@
+//! Share Link Commands
+//! Tauri adapter over `rokio_core::share_links`.
+
+use crate::crypto::CryptoState;
+use rokio_core::roblox_link::RobloxLink;
+use rokio_core::share_links::{resolve_share_code, ResolvedShareLink};
+use rokio_core::vault::load_accounts;
+use tauri::Manager;
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Resolve a share link with the given account's session
+#[tauri::command]
+pub async fn resolve_share_link(
+    app_handle: tauri::AppHandle,
+    account_id: String,
+    url: String,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<ResolvedShareLink, String> {
+    let (code, link_type) = match RobloxLink::parse(&url)? {
+        RobloxLink::Share { code, link_type } => (code, link_type),
+        _ => return Err("Not a roblox.com/share link".to_string()),
+    };
+
+    let key = state
+        .key
+        .lock()
+        .unwrap()
+        .ok_or("Vault is locked")?;
+
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    let cookie = load_accounts(&app_data_dir, &key)?
+        .into_iter()
+        .find(|a| a.id == account_id)
+        .map(|a| a.cookie)
+        .ok_or("Account not found")?;
+
+    resolve_share_code(&cookie, &code, &link_type).await
+}
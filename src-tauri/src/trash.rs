@@ -0,0 +1,52 @@
+//! Recycle Bin Commands
+//! Tauri adapters over `rokio_core::trash` (deleted accounts kept in the vault).
+
+use crate::crypto::CryptoState;
+use rokio_core::settings::load_settings;
+use rokio_core::trash::{self as core, TrashEntry};
+use tauri::Manager;
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Get deleted accounts, purging the ones past the retention period first
+#[tauri::command]
+pub fn get_trash(
+    app_handle: tauri::AppHandle,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<Vec<TrashEntry>, String> {
+    state.key.lock().unwrap().ok_or("Vault is locked")?;
+
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    let retention = load_settings(&app_data_dir)?.trash_retention_days;
+
+    core::purge_expired(&app_data_dir, retention)?;
+    core::list_trash(&app_data_dir, retention)
+}
+
+/// Move accounts back from the recycle bin, returning how many were restored
+#[tauri::command]
+pub fn restore_accounts(
+    app_handle: tauri::AppHandle,
+    account_ids: Vec<String>,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<usize, String> {
+    state.key.lock().unwrap().ok_or("Vault is locked")?;
+
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::restore(&app_data_dir, &account_ids)
+}
+
+/// Delete accounts from the recycle bin for good (all of them when `account_ids` is omitted)
+#[tauri::command]
+pub fn purge_trash(
+    app_handle: tauri::AppHandle,
+    account_ids: Option<Vec<String>>,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<usize, String> {
+    state.key.lock().unwrap().ok_or("Vault is locked")?;
+
+    let app_data_dir = app_handle.path().app_data_dir().map_err(|e| e.to_string())?;
+    core::purge(&app_data_dir, account_ids.as_deref())
+}
@@ -1,6 +1,7 @@
 //! Utilities Module
 //! Helper functions for Roblox integration
 
+use rokio_core::roblox_link::RobloxLink;
 use rand::Rng;
 
 /// List of common user agents for rotation
@@ -21,7 +22,7 @@ pub fn get_random_user_agent() -> &'static str {
     USER_AGENTS[idx]
 }
 
-/// Extract Place ID from various Roblox URL formats
+/// Extract Place ID from any supported Roblox link (see `RobloxLink`)
 /// Supports:
 /// - https://www.roblox.com/games/123456789/Game-Name
 /// - https://roblox.com/games/123456789
@@ -29,54 +30,14 @@ pub fn get_random_user_agent() -> &'static str {
 /// - 123456789 (raw ID)
 
 pub fn extract_place_id_from_url(url: &str) -> Option<u64> {
-    let url = url.trim();
-
-    // Try parsing as raw number first
-    if let Ok(id) = url.parse::<u64>() {
-        return Some(id);
-    }
-
-    // Try roblox:// protocol
-    if url.starts_with("roblox://") {
-        // roblox://placeId=123456789
-        if let Some(start) = url.find("placeId=") {
-            let after = &url[start + 8..];
-            let end = after.find('&').unwrap_or(after.len());
-            if let Ok(id) = after[..end].parse::<u64>() {
-                return Some(id);
-            }
-        }
-    }
-
-    // Try web URL: /games/123456789/...
-    if url.contains("/games/") {
-        let parts: Vec<&str> = url.split("/games/").collect();
-        if parts.len() > 1 {
-            let after_games = parts[1];
-            // Take until next slash or end
-            let end = after_games.find('/').unwrap_or(after_games.len());
-            if let Ok(id) = after_games[..end].parse::<u64>() {
-                return Some(id);
-            }
-        }
-    }
-
-    None
+    RobloxLink::parse(url).ok()?.place_id()
 }
 
 /// Extract Job ID from a Roblox deep link
 /// roblox://placeId=123&gameInstanceId=abc-def-ghi
 
 pub fn extract_job_id_from_url(url: &str) -> Option<String> {
-    if let Some(start) = url.find("gameInstanceId=") {
-        let after = &url[start + 15..];
-        let end = after.find('&').unwrap_or(after.len());
-        let job_id = &after[..end];
-        if !job_id.is_empty() {
-            return Some(job_id.to_string());
-        }
-    }
-    None
+    RobloxLink::parse(url).ok()?.job_id().map(str::to_string)
 }
 
 /// Open URL in system default browser
@@ -0,0 +1,92 @@
+//! Vault Commands
+//! Tauri adapters over `rokio_core::vaults` (named vaults, each with its own password).
+
+use crate::crypto::CryptoState;
+use rokio_core::crypto::derive_key;
+use rokio_core::vaults::{self as core, VaultEntry, VaultInfo};
+use tauri::Manager;
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// List every vault
+#[tauri::command]
+pub fn get_vaults(app_handle: tauri::AppHandle) -> Result<Vec<VaultInfo>, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+    core::list(&app_data_dir)
+}
+
+/// Create a vault with its own master password, then switch to it unlocked
+#[tauri::command]
+pub fn create_named_vault(
+    app_handle: tauri::AppHandle,
+    name: String,
+    password: String,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<VaultEntry, String> {
+    if password.is_empty() {
+        return Err("Password cannot be empty".to_string());
+    }
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+
+    let key = derive_key(&password);
+    let entry = core::create(&app_data_dir, &name, &key)?;
+
+    state.close();
+    core::switch(&app_data_dir, &entry.id)?;
+    state.open(&app_data_dir, &entry.id, key);
+
+    Ok(entry)
+}
+
+/// Rename a vault
+#[tauri::command]
+pub fn rename_vault(
+    app_handle: tauri::AppHandle,
+    vault_id: String,
+    name: String,
+) -> Result<VaultEntry, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+    core::rename(&app_data_dir, &vault_id, &name)
+}
+
+/// Delete an inactive vault for good (needs that vault's master password)
+#[tauri::command]
+pub fn delete_vault(
+    app_handle: tauri::AppHandle,
+    vault_id: String,
+    password: String,
+) -> Result<(), String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+    core::delete(&app_data_dir, &vault_id, &password)
+}
+
+/// Make another vault active. The open vault is locked first.
+#[tauri::command]
+pub fn switch_vault(
+    app_handle: tauri::AppHandle,
+    vault_id: String,
+    state: tauri::State<'_, CryptoState>,
+) -> Result<VaultEntry, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+
+    // Forget the key of the vault being left
+    state.close();
+    core::switch(&app_data_dir, &vault_id)
+}
@@ -3,10 +3,10 @@
 
 import { writable, derived } from "svelte/store";
 import { invoke } from "@tauri-apps/api/core";
-import type { Profile } from "$lib/types";
+import type { AccountSummary, BulkAction, CookieFormat, TrashEntry } from "$lib/types";
 
 interface AccountsState {
-  accounts: Profile[];
+  accounts: AccountSummary[];
   loading: boolean;
   error: string | null;
   selectedId: string | null;
@@ -27,7 +27,7 @@ function createAccountsStore() {
     load: async () => {
       update((s) => ({ ...s, loading: true, error: null }));
       try {
-        const accounts = await invoke<Profile[]>("get_accounts");
+        const accounts = await invoke<AccountSummary[]>("get_accounts");
         update((s) => ({ ...s, accounts, loading: false }));
       } catch (err) {
         update((s) => ({ ...s, error: String(err), loading: false }));
@@ -35,10 +35,10 @@ function createAccountsStore() {
     },
 
     // Add a new account
-    add: async (cookie: string): Promise<Profile> => {
+    add: async (cookie: string): Promise<AccountSummary> => {
       update((s) => ({ ...s, loading: true, error: null }));
       try {
-        const profile = await invoke<Profile>("add_account", { cookie });
+        const profile = await invoke<AccountSummary>("add_account", { cookie });
         update((s) => ({
           ...s,
           accounts: [...s.accounts, profile],
@@ -52,7 +52,7 @@ function createAccountsStore() {
     },
 
     // Update an existing account
-    update: async (profile: Profile) => {
+    update: async (profile: AccountSummary) => {
       try {
         await invoke("update_account", { profile });
         update((s) => ({
@@ -79,6 +79,15 @@ function createAccountsStore() {
       }
     },
 
+    // Sign the account out on Roblox, then delete it for good (no recycle bin)
+    signOutAndDelete: async (id: string) => {
+      await invoke("sign_out_and_delete_account", { id });
+      update((s) => ({
+        ...s,
+        accounts: s.accounts.filter((a) => a.id !== id),
+      }));
+    },
+
     // Toggle favorite
     toggleFavorite: async (id: string) => {
       update((s) => {
@@ -95,6 +104,51 @@ function createAccountsStore() {
       });
     },
 
+    // Apply one change to several accounts, then reload
+    bulkUpdate: async (accountIds: string[], action: BulkAction): Promise<number> => {
+      const count = await invoke<number>("bulk_update_accounts", { accountIds, action });
+      const accounts = await invoke<AccountSummary[]>("get_accounts");
+      update((s) => ({ ...s, accounts }));
+      return count;
+    },
+
+    // Persist a custom order (ids in their new order)
+    reorder: async (accountIds: string[]) => {
+      update((s) => {
+        const position = (id: string) => {
+          const i = accountIds.indexOf(id);
+          return i === -1 ? accountIds.length : i;
+        };
+        const accounts = [...s.accounts]
+          .sort((a, b) => position(a.id) - position(b.id))
+          .map((a, i) => ({ ...a, sortOrder: i }));
+        return { ...s, accounts };
+      });
+      try {
+        await invoke("reorder_accounts", { accountIds });
+      } catch (err) {
+        update((s) => ({ ...s, error: String(err) }));
+        throw err;
+      }
+    },
+
+    // Recycle bin
+    listTrash: async (): Promise<TrashEntry[]> => {
+      return await invoke<TrashEntry[]>("get_trash");
+    },
+
+    restore: async (accountIds: string[]): Promise<number> => {
+      const count = await invoke<number>("restore_accounts", { accountIds });
+      const accounts = await invoke<AccountSummary[]>("get_accounts");
+      update((s) => ({ ...s, accounts }));
+      return count;
+    },
+
+    // Delete for good (empties the bin when no ids are given)
+    purgeTrash: async (accountIds?: string[]): Promise<number> => {
+      return await invoke<number>("purge_trash", { accountIds: accountIds ?? null });
+    },
+
     // Select an account
     select: (id: string | null) => {
       update((s) => ({ ...s, selectedId: id }));
@@ -124,14 +178,19 @@ function createAccountsStore() {
     importAccounts: async (data: string, merge: boolean = true): Promise<number> => {
       const count = await invoke<number>("import_accounts", { data, merge });
       // Reload accounts after import
-      const accounts = await invoke<Profile[]>("get_accounts");
+      const accounts = await invoke<AccountSummary[]>("get_accounts");
       update((s) => ({ ...s, accounts }));
       return count;
     },
 
+    // Write each account's session as a cookie file (Downloads/rokio-cookies)
+    exportCookies: async (accountIds: string[], format: CookieFormat): Promise<string[]> => {
+      return await invoke<string[]>("export_account_cookies", { accountIds, format, dir: null });
+    },
+
     // Clear all accounts
     clearAll: async () => {
-      if (confirm("Are you sure you want to delete ALL accounts? This cannot be undone.")) {
+      if (confirm("Move ALL accounts to the recycle bin?")) {
         await invoke("clear_accounts");
         update((s) => ({ ...s, accounts: [] }));
       }
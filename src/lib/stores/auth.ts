@@ -1,11 +1,13 @@
 // ROKIO Auth Store
-// Manages vault lock/unlock state
+// Manages vault lock/unlock state and switching between named vaults
 
 import { writable } from "svelte/store";
 import { invoke } from "@tauri-apps/api/core";
-import type { VaultStatus } from "$lib/types";
+import type { VaultInfo, VaultStatus } from "$lib/types";
 
 interface AuthState {
+  vaultId: string;
+  vaultName: string;
   vaultExists: boolean;
   unlocked: boolean;
   loading: boolean;
@@ -14,6 +16,8 @@ interface AuthState {
 
 function createAuthStore() {
   const { subscribe, update, set } = writable<AuthState>({
+    vaultId: "default",
+    vaultName: "Default",
     vaultExists: false,
     unlocked: false,
     loading: true,
@@ -30,6 +34,8 @@ function createAuthStore() {
         const status = await invoke<VaultStatus>("get_vault_status");
         update((s) => ({
           ...s,
+          vaultId: status.vaultId,
+          vaultName: status.vaultName,
           vaultExists: status.exists,
           unlocked: status.unlocked,
           loading: false,
@@ -89,6 +95,52 @@ function createAuthStore() {
       }
     },
 
+    // List every vault
+    listVaults: async (): Promise<VaultInfo[]> => {
+      return await invoke<VaultInfo[]>("get_vaults");
+    },
+
+    // Make another vault active (locks the open one)
+    switchVault: async (vaultId: string) => {
+      await invoke("switch_vault", { vaultId });
+      const status = await invoke<VaultStatus>("get_vault_status");
+      update((s) => ({
+        ...s,
+        vaultId: status.vaultId,
+        vaultName: status.vaultName,
+        vaultExists: status.exists,
+        unlocked: status.unlocked,
+        error: null,
+      }));
+    },
+
+    // Create a vault with its own password and open it
+    createNamedVault: async (name: string, password: string) => {
+      const vault = await invoke<{ id: string; name: string }>("create_named_vault", {
+        name,
+        password,
+      });
+      update((s) => ({
+        ...s,
+        vaultId: vault.id,
+        vaultName: vault.name,
+        vaultExists: true,
+        unlocked: true,
+        error: null,
+      }));
+    },
+
+    // Rename a vault
+    renameVault: async (vaultId: string, name: string) => {
+      const vault = await invoke<{ id: string; name: string }>("rename_vault", { vaultId, name });
+      update((s) => (s.vaultId === vault.id ? { ...s, vaultName: vault.name } : s));
+    },
+
+    // Delete an inactive vault for good
+    deleteVault: async (vaultId: string, password: string) => {
+      await invoke("delete_vault", { vaultId, password });
+    },
+
     // Clear error
     clearError: () => {
       update((s) => ({ ...s, error: null }));
@@ -12,7 +12,16 @@ export interface ActiveInstance {
   accountId: string;
   username: string;
   placeId: number;
+  jobId?: string | null;
   startedAt: number;
+  launchId?: number | null;
+}
+
+export type HopStrategy = "leastPlayers" | "mostPlayers" | "random" | "notVisited";
+
+export interface PresetRunResult {
+  launched: ActiveInstance[];
+  failed: { accountId: string; error: string }[];
 }
 
 interface LauncherState {
@@ -30,18 +39,14 @@ const initialState: LauncherState = {
 function createLauncherStore() {
   const { subscribe, set, update } = writable<LauncherState>(initialState);
 
-  // Listen for instance-closed events
+  // Listen for instance-closed events (sent by the backend as clients exit)
   let unlistenFn: (() => void) | null = null;
   let pollInterval: ReturnType<typeof setInterval> | null = null;
 
   async function setupListener() {
     if (unlistenFn) return;
 
-    unlistenFn = await listen<{
-      pid: number;
-      accountId: string;
-      username: string;
-    }>("instance-closed", (event) => {
+    unlistenFn = await listen<ActiveInstance>("instance-closed", (event) => {
       update((state) => ({
         ...state,
         instances: state.instances.filter((i) => i.pid !== event.payload.pid),
@@ -49,7 +54,7 @@ function createLauncherStore() {
     });
   }
 
-  // Start polling for process status (to detect when Roblox closes)
+  // Start polling the tracked instances (picks up launches made elsewhere, e.g. the API)
   function startPolling() {
     if (pollInterval) return;
     pollInterval = setInterval(async () => {
@@ -60,7 +65,6 @@ function createLauncherStore() {
       })();
       
       if (hasInstances) {
-        // Refresh will clean up dead instances on the backend
         const instances = await invoke<ActiveInstance[]>("get_active_instances");
         update((state) => ({ ...state, instances }));
       }
@@ -157,6 +161,42 @@ function createLauncherStore() {
       }
     },
 
+    /**
+     * Relaunch a running instance into another server of the same place
+     */
+    async hop(pid: number, strategy: HopStrategy): Promise<ActiveInstance | null> {
+      try {
+        const result = await invoke<{ instance: ActiveInstance }>("server_hop", { pid, strategy });
+        update((state) => ({
+          ...state,
+          instances: [...state.instances.filter((i) => i.pid !== pid), result.instance],
+          error: null,
+        }));
+        return result.instance;
+      } catch (err) {
+        update((state) => ({ ...state, error: String(err) }));
+        return null;
+      }
+    },
+
+    /**
+     * Launch every account of a saved preset
+     */
+    async runPreset(presetId: string): Promise<PresetRunResult | null> {
+      try {
+        const result = await invoke<PresetRunResult>("run_preset", { presetId });
+        update((state) => ({
+          ...state,
+          instances: [...state.instances, ...result.launched],
+          error: null,
+        }));
+        return result;
+      } catch (err) {
+        update((state) => ({ ...state, error: String(err) }));
+        return null;
+      }
+    },
+
     /**
      * Bypass the singleton mutex for multi-instance
      */
@@ -2,19 +2,19 @@
 // Manages modals and UI state
 
 import { writable } from "svelte/store";
-import type { Profile } from "$lib/types";
+import type { AccountSummary } from "$lib/types";
 
 interface UIState {
   addAccountModalOpen: boolean;
   settingsModalOpen: boolean;
   launchModalOpen: boolean;
-  launchModalAccount: Profile | null;
+  launchModalAccount: AccountSummary | null;
   quickLoginModalOpen: boolean;
   browserLoginModalOpen: boolean;
   editAccountModalOpen: boolean;
-  editModalAccount: Profile | null;
+  editModalAccount: AccountSummary | null;
   accountInfoModalOpen: boolean;
-  infoModalAccount: Profile | null;
+  infoModalAccount: AccountSummary | null;
 }
 
 function createUIStore() {
@@ -50,7 +50,7 @@ function createUIStore() {
       update((s) => ({ ...s, settingsModalOpen: false }));
     },
 
-    openLaunchModal: (account: Profile) => {
+    openLaunchModal: (account: AccountSummary) => {
       update((s) => ({ ...s, launchModalOpen: true, launchModalAccount: account }));
     },
 
@@ -74,7 +74,7 @@ function createUIStore() {
       update((s) => ({ ...s, browserLoginModalOpen: false }));
     },
 
-    openEditAccount: (account: Profile) => {
+    openEditAccount: (account: AccountSummary) => {
       update((s) => ({ ...s, editAccountModalOpen: true, editModalAccount: account }));
     },
 
@@ -82,7 +82,7 @@ function createUIStore() {
       update((s) => ({ ...s, editAccountModalOpen: false, editModalAccount: null }));
     },
 
-    openAccountInfo: (account: Profile) => {
+    openAccountInfo: (account: AccountSummary) => {
       update((s) => ({ ...s, accountInfoModalOpen: true, infoModalAccount: account }));
     },
 
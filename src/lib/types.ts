@@ -1,24 +1,104 @@
 // ROKIO TypeScript Types
 
-export interface Profile {
+// Account as sent by the backend: the cookie never leaves the vault
+export interface AccountSummary {
   id: string;
-  cookie: string;
   userId: number;
   username: string;
   displayName: string;
   thumbnail: string | null;
   alias: string;
   description: string;
+  tags: string[];
+  folder: string; // "/"-separated path, "" = ungrouped
+  sortOrder: number;
   isFavorite: boolean;
   lastPlayedAt: number;
-  password?: string;
   createdAt?: number;
   isPremium?: boolean;
 }
 
+export interface LabelCount {
+  name: string;
+  count: number;
+}
+
+export interface AccountLabels {
+  tags: LabelCount[];
+  folders: LabelCount[]; // parents included, counts cover nested folders
+}
+
+export type BulkAction =
+  | { type: "addTags"; tags: string[] }
+  | { type: "removeTags"; tags: string[] }
+  | { type: "setTags"; tags: string[] }
+  | { type: "moveToFolder"; folder: string }
+  | { type: "setFavorite"; favorite: boolean }
+  | { type: "delete" };
+
+// One place an account is logged in (Roblox session management)
+export interface ActiveSession {
+  device: string;
+  location: string | null;
+  ip: string | null;
+  lastActive: number | null; // Unix seconds
+  isCurrent: boolean;
+}
+
+// Security settings of one account; null = the check failed
+export interface SecurityReport {
+  accountId: string;
+  userId: number;
+  username: string;
+  emailSet: boolean | null;
+  emailVerified: boolean | null;
+  twoStepEnabled: boolean | null;
+  twoStepType: string | null;
+  phoneVerified: boolean | null;
+  pinEnabled: boolean | null;
+  ageBracket: string | null;
+  issues: string[];
+  error: string | null;
+}
+
+// Result of one input row of a bulk import
+/** Cookie file format for session exports */
+export type CookieFormat = "netscape" | "json";
+
+export interface ImportRowResult {
+  row: number;
+  status: "added" | "duplicate" | "invalid";
+  accountId: string | null;
+  username: string | null;
+  message: string | null;
+}
+
+export interface TrashEntry {
+  id: string;
+  userId: number;
+  username: string;
+  displayName: string;
+  thumbnail: string | null;
+  alias: string;
+  deletedAt: number;
+  expiresAt: number | null; // null = kept forever
+}
+
 export interface VaultStatus {
   exists: boolean;
   unlocked: boolean;
+  /** Active vault, the one `exists` and `unlocked` refer to */
+  vaultId: string;
+  vaultName: string;
+}
+
+export interface VaultInfo {
+  id: string;
+  name: string;
+  createdAt: number;
+  active: boolean;
+  /** Whether a master password has been set */
+  exists: boolean;
 }
 
 export interface AppInfo {
@@ -39,7 +119,33 @@ export interface ActiveInstance {
   accountId: string;
   username: string;
   placeId: number;
+  jobId?: string | null;
   startedAt: number;
+  launchId?: number | null;
+  options?: {
+    multiInstance?: boolean | null;
+    launcherPreference?: string | null;
+  };
+}
+
+export type PresetDestination =
+  | { type: "menu" }
+  | { type: "place"; placeId: number }
+  | { type: "server"; placeId: number; jobId: string }
+  | { type: "privateServer"; placeId: number; linkCode: string }
+  | { type: "link"; link: string };
+
+export interface LaunchPreset {
+  id: string; // empty for a new preset
+  name: string;
+  destination: PresetDestination;
+  accountIds: string[];
+  options: {
+    multiInstance?: boolean | null; // null = use settings
+    launcherPreference?: string | null;
+    staggerMs: number;
+  };
+  createdAt: number;
 }
 
 export interface AppSettings {
@@ -50,9 +156,20 @@ export interface AppSettings {
   compactMode: boolean;
   accentColor: string;  // "red" | "orange" | "yellow" | "green" | "teal" | "blue" | "indigo" | "purple" | "pink"
   multiInstance: boolean;  // Enable multi-instance launching
-  launcherPreference: string;  // "default" | "bloxstrap" | "fishstrap" | "froststrap" | "client"
+  launcherPreference: string;  // "default" | "bloxstrap" | "fishstrap" | "froststrap" | "client" | "custom"
+  customLaunchCommand: string;  // Template for the custom launcher ({deeplink}, {home}, {place_id}, {job_id})
   quarantineInstallers: boolean;  // Prevent Roblox update popups (Windows)
   saveLogs: boolean;  // Save session logs
   forceHandleClosure: boolean;  // Aggressive handle resolution
   lowCpuMode: boolean;  // Reduce CPU usage
+  trashRetentionDays: number;  // Days deleted accounts are kept, 0 = forever
+  automationApiEnabled: boolean;  // Serve the local automation API
+  automationApiPort: number;  // Port on 127.0.0.1
+}
+
+export interface AutomationApiInfo {
+  enabled: boolean;
+  running: boolean;
+  baseUrl: string;
+  token: string;
 }
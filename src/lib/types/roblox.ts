@@ -1,9 +1,60 @@
+export interface ServerRegion {
+  code: string; // e.g. "US-LAX", "??" when unknown
+  name: string;
+  address: string;
+}
+
 export interface ServerInfo {
   id: string;
   playing: number;
   maxPlayers: number;
   ping?: number;
   fps?: number;
+  region?: ServerRegion;
+}
+
+export interface ServerPlayer {
+  token: string;
+  headshot?: string;
+}
+
+export interface OwnAccountInServer {
+  accountId: string;
+  userId: number;
+  username: string;
+  displayName: string;
+}
+
+export interface ServerDetails {
+  placeId: number;
+  server: ServerInfo;
+  players: ServerPlayer[];
+  ownAccounts: OwnAccountInServer[];
+}
+
+export type ServerSortField = "playing" | "freeSlots" | "ping" | "fps";
+
+/** Filters & sorting for `find_servers` (full scan of every page) */
+export interface ServerQuery {
+  minFreeSlots?: number;
+  maxPing?: number;
+  minFps?: number;
+  sortBy?: ServerSortField;
+  descending?: boolean;
+  maxPages?: number;
+}
+
+/** Payload of the `server-search://batch` event */
+export interface ServerBatch {
+  searchId: string;
+  servers: ServerInfo[];
+}
+
+export interface ServerSearchResult {
+  servers: ServerInfo[];
+  scanned: number;
+  pages: number;
+  cancelled: boolean;
 }
 
 export interface GameInfo {
@@ -25,9 +76,28 @@ export interface BrowsedGame {
   thumbnail?: string;
 }
 
+export interface DiscoverySort {
+  id: string;
+  title: string;
+  games: BrowsedGame[];
+}
+
+export interface GameDiscovery {
+  sorts: DiscoverySort[];
+  offline: boolean; // bundled fallback list
+}
+
 export interface FavoriteGame {
   placeId: number;
   universeId: number;
   name: string;
   thumbnail?: string;
 }
+
+export interface RecentServer {
+  placeId: number;
+  jobId: string;
+  accountId: string;
+  username: string;
+  joinedAt: number; // unix seconds
+}